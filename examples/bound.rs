@@ -0,0 +1,53 @@
+//! Measures the performance of the hot paths which have out-of-line slow
+//! paths: `bound` (rejection sampling) and `SecureRng::u64` (buffer refills).
+
+use std::hint::black_box;
+use std::time::Instant;
+use ya_rand::*;
+
+const ITERATIONS: usize = 1 << 26;
+
+fn main() {
+    // Small bounds almost never hit the rejection path, while bounds just
+    // above a power of 2 hit it for nearly half of all values.
+    let maxes = [6, 1000, (1 << 32) + 1, (1 << 63) + 1];
+    println!(
+        "Generating {} values || Average nanoseconds per value generated:\n\
+         ----------------------------------------------------------------",
+        ITERATIONS
+    );
+    for max in maxes {
+        let mut rng = new_rng();
+        let shiro = time_in_nanos(|| {
+            for _ in 0..ITERATIONS {
+                black_box(rng.bound(black_box(max)));
+            }
+        });
+        let mut rng = new_rng_secure();
+        let secure = time_in_nanos(|| {
+            for _ in 0..ITERATIONS {
+                black_box(rng.bound(black_box(max)));
+            }
+        });
+        println!("`bound({max:>20})` ShiroRng: {shiro:>5.2} || SecureRng: {secure:>5.2}");
+    }
+    let mut rng = new_rng_secure();
+    let secure = time_in_nanos(|| {
+        for _ in 0..ITERATIONS {
+            black_box(rng.u64());
+        }
+    });
+    println!(
+        "`u64()`                                          || SecureRng: {secure:>5.2}\n\
+         ----------------------------------------------------------------"
+    );
+}
+
+#[inline(never)]
+fn time_in_nanos<F: FnOnce()>(op: F) -> f64 {
+    let start = Instant::now();
+    op();
+    let delta = start.elapsed().as_secs_f64();
+    let time = delta / (ITERATIONS as f64);
+    time * 1e9
+}
@@ -7,6 +7,7 @@ use rand::{SeedableRng, rngs::StdRng};
 use rayon::prelude::*;
 use std::hint::black_box;
 use std::time::Instant;
+use ya_rand::entropy::{EntropySource, OsEntropy};
 use ya_rand::*;
 
 const ITERATIONS: usize = 1 << 24;
@@ -161,9 +162,9 @@ struct SecureStdRng {
 }
 
 impl Generator for SecureStdRng {
-    fn try_new() -> Result<Self, getrandom::Error> {
+    fn try_new() -> Result<Self, Error> {
         let mut data = <StdRng as SeedableRng>::Seed::default();
-        getrandom::fill(&mut data)?;
+        OsEntropy.fill(&mut data)?;
         let internal = StdRng::from_seed(data);
         Ok(Self { internal })
     }
@@ -181,9 +182,9 @@ impl Generator for SecureStdRng {
 // }
 
 // impl Generator for SecureChaCha20 {
-//     fn try_new() -> Result<Self, getrandom::Error> {
+//     fn try_new() -> Result<Self, Error> {
 //         let mut data = <ChaCha8Rng as SeedableRng>::Seed::default();
-//         getrandom::fill(&mut data)?;
+//         OsEntropy.fill(&mut data)?;
 //         let internal = ChaCha8Rng::from_seed(data);
 //         Ok(Self { internal })
 //     }
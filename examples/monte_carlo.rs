@@ -1,8 +1,10 @@
 //! Approximation of PI using the monte carlo method,
-//! for both `f32` and `f64` types.
+//! for both `f32` and `f64` types, followed by the
+//! quasi-monte carlo method using a Sobol sequence.
 
 use std::f32::consts::PI as f32_PI;
 use std::f64::consts::PI as f64_PI;
+use ya_rand::quasi::Sobol;
 use ya_rand::*;
 
 const ITERATIONS: u64 = 1 << 24;
@@ -14,6 +16,7 @@ fn main() {
         test_f64(&mut rng);
         println!();
     }
+    test_f64_sobol();
 }
 
 fn test_f32(rng: &mut ShiroRng) {
@@ -55,3 +58,25 @@ fn test_f64(rng: &mut ShiroRng) {
         (f64_PI - simulated).abs()
     );
 }
+
+fn test_f64_sobol() {
+    let mut sobol = Sobol::new(2);
+    let mut point = [0.0; 2];
+    let mut in_circle: u64 = 0;
+    for _ in 0..ITERATIONS {
+        sobol.next_point(&mut point);
+        let [x, y] = point;
+        let distance = (x * x) + (y * y);
+        if distance <= 1.0 {
+            in_circle += 1;
+        }
+    }
+
+    let simulated = 4.0 * (in_circle as f64) / (ITERATIONS as f64);
+    println!("f64 const: {}", f64_PI);
+    println!("Sobol:     {}", simulated);
+    println!(
+        "Delta between const and quasi-simulated π: {}",
+        (f64_PI - simulated).abs()
+    );
+}
@@ -0,0 +1,127 @@
+use crate::rng::Generator;
+use std::time::Duration;
+
+/// How a [`Backoff`] randomizes each of its delays.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Jitter {
+    /// Delays aren't randomized at all.
+    None,
+    /// Delays are uniformly distributed between zero and the full backoff, which spreads
+    /// out competing clients the most.
+    #[default]
+    Full,
+    /// Delays are uniformly distributed between half of the backoff and the full backoff,
+    /// which guarantees a minimum delay while still spreading out competing clients.
+    Equal,
+}
+
+/// Produces successive randomized delays for retrying a failed operation.
+///
+/// The un-randomized backoff starts at `initial` and is multiplied by the multiplier
+/// (2.0 by default) after every delay, until it reaches `cap`. Every delay is then
+/// randomized according to the [`Jitter`] strategy ([`Jitter::Full`] by default),
+/// using the generator passed to [`Backoff::next_delay`]. See
+/// [`Generator::jitter_between`] for how the strategies compare.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use ya_rand::*;
+///
+/// let mut rng = new_rng();
+/// let mut backoff = Backoff::new(Duration::from_millis(100), Duration::from_secs(5))
+///     .with_multiplier(3.0)
+///     .with_jitter(Jitter::Equal);
+/// let delays: Vec<_> = (0..6).map(|_| backoff.next_delay(&mut rng)).collect();
+/// assert!(Duration::from_millis(50) <= delays[0] && delays[0] <= Duration::from_millis(100));
+/// assert!(Duration::from_millis(150) <= delays[1] && delays[1] <= Duration::from_millis(300));
+/// assert!(Duration::from_millis(2500) <= delays[5] && delays[5] <= Duration::from_secs(5));
+/// assert!(backoff.attempts() == 6);
+///
+/// // The operation succeeded, so the next failure starts over.
+/// backoff.reset();
+/// assert!(backoff.next_delay(&mut rng) <= Duration::from_millis(100));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Backoff {
+    initial: Duration,
+    cap: Duration,
+    multiplier: f64,
+    jitter: Jitter,
+    current: Duration,
+    attempts: u32,
+}
+
+impl Backoff {
+    /// Creates a backoff which starts at `initial` and grows up to `cap`.
+    ///
+    /// # Panics
+    ///
+    /// If `initial` is greater than `cap`.
+    #[inline]
+    pub const fn new(initial: Duration, cap: Duration) -> Self {
+        assert!(
+            initial.as_nanos() <= cap.as_nanos(),
+            "`initial` must not be greater than `cap`"
+        );
+        Self {
+            initial,
+            cap,
+            multiplier: 2.0,
+            jitter: Jitter::Full,
+            current: initial,
+            attempts: 0,
+        }
+    }
+
+    /// Sets the factor the backoff grows by after every delay.
+    ///
+    /// # Panics
+    ///
+    /// If `multiplier` is less than 1.0 or isn't finite.
+    #[inline]
+    pub const fn with_multiplier(mut self, multiplier: f64) -> Self {
+        assert!(
+            multiplier >= 1.0 && multiplier.is_finite(),
+            "`multiplier` must be finite and at least 1.0"
+        );
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Sets how every delay is randomized.
+    #[inline]
+    pub const fn with_jitter(mut self, jitter: Jitter) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Returns the amount of delays produced since creation or the last call
+    /// to [`Backoff::reset`].
+    #[inline]
+    pub const fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Starts over from the initial backoff, usually after the operation succeeded.
+    #[inline]
+    pub const fn reset(&mut self) {
+        self.current = self.initial;
+        self.attempts = 0;
+    }
+
+    /// Returns the next randomized delay, using `rng` as the source of randomness.
+    pub fn next_delay<G: Generator>(&mut self, rng: &mut G) -> Duration {
+        let backoff = self.current;
+        // Growth stops at the cap, so it can never overflow `Duration`.
+        self.current = Duration::try_from_secs_f64(backoff.as_secs_f64() * self.multiplier)
+            .map_or(self.cap, |next| next.min(self.cap));
+        self.attempts = self.attempts.saturating_add(1);
+        match self.jitter {
+            Jitter::None => backoff,
+            Jitter::Full => rng.jitter_between(Duration::ZERO, backoff),
+            Jitter::Equal => rng.jitter_between(backoff / 2, backoff),
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::entropy::EntropySource;
+use crate::error::Error;
+#[cfg(feature = "secure")]
+use crate::rng::SecureGenerator;
+use crate::rng::{Generator, SeedableGenerator};
+use core::fmt;
+
+/// A wrapper which serves small requests from the unused bits of previous draws.
+///
+/// Normally every call to [`Generator::u32`] (and `u16`, `u8`, `bool`, or [`Generator::bits`]
+/// with a `bit_count` of at most 32) discards the unused part of a fresh `u64`. This
+/// wrapper keeps those leftover bits around and uses them for the next small request,
+/// which roughly halves the amount of generator output needed by `u32` heavy workloads,
+/// and lets a single `u64` provide 64 values from [`Generator::bool`].
+///
+/// Requests for more than 32 bits, as well as [`Generator::u64`] itself, always draw a
+/// fresh value from the wrapped generator. Since the order in which bits are handed out
+/// differs, a wrapped generator produces different values than the generator on its own.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+/// use ya_rand::testing::CountingRng;
+///
+/// let mut rng = CachingRng::new_with_rng(CountingRng::new_with_rng(new_rng()));
+/// let mut pixels = [0_u32; 1000];
+/// pixels.iter_mut().for_each(|p| *p = rng.u32());
+/// assert!(rng.inner().draws() == 500);
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct CachingRng<G> {
+    rng: G,
+    cache: u64,
+    available: u32,
+}
+
+impl<G> fmt::Debug for CachingRng<G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("all `CachingRng` fields are private")
+    }
+}
+
+impl<G: Generator> CachingRng<G> {
+    /// Wraps `rng`, starting with an empty cache.
+    #[inline]
+    pub const fn new_with_rng(rng: G) -> Self {
+        Self {
+            rng,
+            cache: 0,
+            available: 0,
+        }
+    }
+
+    /// Returns a reference to the wrapped generator.
+    #[inline]
+    pub const fn inner(&self) -> &G {
+        &self.rng
+    }
+
+    /// Returns the wrapped generator, discarding any cached bits.
+    #[inline]
+    pub fn into_inner(self) -> G {
+        self.rng
+    }
+}
+
+impl<G: SeedableGenerator> SeedableGenerator for CachingRng<G> {
+    #[inline]
+    fn new_with_seed(seed: u64) -> Self {
+        Self::new_with_rng(G::new_with_seed(seed))
+    }
+}
+
+#[cfg(feature = "secure")]
+impl<G: SecureGenerator> SecureGenerator for CachingRng<G> {
+    #[inline]
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.rng.fill_bytes(dst);
+    }
+}
+
+impl<G: Generator> Generator for CachingRng<G> {
+    #[inline]
+    fn try_new() -> Result<Self, Error> {
+        G::try_new().map(Self::new_with_rng)
+    }
+
+    #[inline]
+    fn try_new_from<S: EntropySource + ?Sized>(source: &mut S) -> Result<Self, Error> {
+        G::try_new_from(source).map(Self::new_with_rng)
+    }
+
+    #[inline]
+    fn u64(&mut self) -> u64 {
+        self.rng.u64()
+    }
+
+    #[inline]
+    fn bits(&mut self, bit_count: u32) -> u64 {
+        match bit_count {
+            0 => 0,
+            1..=32 => {
+                if self.available < bit_count {
+                    self.cache = self.rng.u64();
+                    self.available = u64::BITS;
+                }
+                let ret = self.cache >> (u64::BITS - bit_count);
+                // Consumed bits are shifted out, so they're never handed out twice.
+                self.cache <<= bit_count;
+                self.available -= bit_count;
+                ret
+            }
+            _ => self.rng.bits(bit_count),
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! Random colors, returned as their red, green, and blue components.
+//!
+//! # Examples
+//!
+//! ```
+//! use ya_rand::*;
+//!
+//! let mut rng = new_rng();
+//! let [r, g, b] = color::rgb(&mut rng);
+//! let [.., alpha] = color::rgba(&mut rng);
+//! let vivid = color::hsl(&mut rng, 0.9..=1.0, 0.45..=0.55);
+//! ```
+
+use crate::rng::Generator;
+use core::ops::RangeBounds;
+
+/// Returns a uniformly distributed color, as its red, green, and blue components.
+#[inline]
+pub fn rgb(rng: &mut impl Generator) -> [u8; 3] {
+    rng.array()
+}
+
+/// Returns a uniformly distributed color with a uniformly distributed alpha channel,
+/// as its red, green, blue, and alpha components.
+#[inline]
+pub fn rgba(rng: &mut impl Generator) -> [u8; 4] {
+    rng.array()
+}
+
+/// Returns a color with a uniformly distributed hue, and saturation and lightness
+/// uniformly distributed within `saturation` and `lightness` in HSL space,
+/// as its red, green, and blue components.
+///
+/// Restricting saturation and lightness is an easy way to get colors which look
+/// good together, such as muted pastels or vivid colors which stand out against
+/// a dark background, while still getting a wide variety of hues.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// let mut rng = new_rng();
+/// // Pastels.
+/// let [r, g, b] = color::hsl(&mut rng, 0.4..=0.7, 0.8..=0.9);
+/// assert!(r.max(g).max(b) >= 204);
+/// // Full saturation and 50% lightness always has one channel at its max.
+/// let [r, g, b] = color::hsl(&mut rng, 1.0..=1.0, 0.5..=0.5);
+/// assert!(r.max(g).max(b) == 255);
+/// ```
+///
+/// # Panics
+///
+/// If either range would panic in [`Generator::in_range_f64`], or
+/// isn't within the interval [0.0, 1.0].
+pub fn hsl<S, L>(rng: &mut impl Generator, saturation: S, lightness: L) -> [u8; 3]
+where
+    S: RangeBounds<f64>,
+    L: RangeBounds<f64>,
+{
+    let s = rng.in_range_f64(saturation);
+    let l = rng.in_range_f64(lightness);
+    assert!(
+        (0.0..=1.0).contains(&s),
+        "saturation must be within [0.0, 1.0]"
+    );
+    assert!(
+        (0.0..=1.0).contains(&l),
+        "lightness must be within [0.0, 1.0]"
+    );
+    hsl_to_rgb(rng.f64(), s, l)
+}
+
+/// Converts a color from HSL space, with all components in [0.0, 1.0], to RGB.
+pub(crate) fn hsl_to_rgb(h: f64, s: f64, l: f64) -> [u8; 3] {
+    let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let h = h * 6.0;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = l - chroma / 2.0;
+    // Float to int casts saturate, so rounding errors can't wrap around.
+    [r, g, b].map(|c| ((c + m) * 255.0 + 0.5) as u8)
+}
@@ -0,0 +1,236 @@
+//! Implementations of external RNG traits for the generators in this crate.
+
+use crate::error::{Error, ErrorKind};
+use crate::{Generator, RomuQuad, RomuTrio, Xoshiro256pp, Xoshiro512pp};
+#[cfg(feature = "secure")]
+use crate::{SecureGenerator, SecureRng};
+
+/// Fills `dst` using the output of [`Generator::u64`].
+#[inline]
+fn fill_via_u64<G: Generator>(rng: &mut G, dst: &mut [u8]) {
+    for chunk in dst.chunks_mut(size_of::<u64>()) {
+        let bytes = rng.u64().to_le_bytes();
+        chunk.copy_from_slice(&bytes[..chunk.len()]);
+    }
+}
+
+#[cfg(feature = "rand06-compat")]
+macro_rules! impl_rand_core_06 {
+    ($fill:path => $($ty:ty),+) => {$(
+        impl rand_core_06::RngCore for $ty {
+            #[inline]
+            fn next_u32(&mut self) -> u32 {
+                Generator::u32(self)
+            }
+
+            #[inline]
+            fn next_u64(&mut self) -> u64 {
+                Generator::u64(self)
+            }
+
+            #[inline]
+            fn fill_bytes(&mut self, dst: &mut [u8]) {
+                $fill(self, dst);
+            }
+
+            #[inline]
+            fn try_fill_bytes(&mut self, dst: &mut [u8]) -> Result<(), rand_core_06::Error> {
+                $fill(self, dst);
+                Ok(())
+            }
+        }
+    )+};
+}
+
+#[cfg(feature = "rand09-compat")]
+macro_rules! impl_rand_core_09 {
+    ($fill:path => $($ty:ty),+) => {$(
+        impl rand_core_09::RngCore for $ty {
+            #[inline]
+            fn next_u32(&mut self) -> u32 {
+                Generator::u32(self)
+            }
+
+            #[inline]
+            fn next_u64(&mut self) -> u64 {
+                Generator::u64(self)
+            }
+
+            #[inline]
+            fn fill_bytes(&mut self, dst: &mut [u8]) {
+                $fill(self, dst);
+            }
+        }
+    )+};
+}
+
+/// Exposes a mutable reference to any [`Generator`] as a `RngCore`.
+///
+/// Allows generators which don't implement `RngCore` themselves, including
+/// user-defined ones, to be passed to libraries built on `rand` for the
+/// duration of a single call, without being moved or reseeded.
+/// `CryptoRng` is also implemented when `G` implements [`SecureGenerator`].
+// The example relies on `rand` 0.9, so it only makes sense when `rand_core` 0.9 is in use.
+#[cfg_attr(
+    feature = "rand09-compat",
+    doc = r#"
+# Examples
+
+```
+use rand::Rng;
+use ya_rand::*;
+use ya_rand::testing::StepRng;
+
+fn roll<R: Rng>(rng: &mut R) -> u32 {
+    rng.random_range(1..=6)
+}
+
+let mut rng = StepRng::new_with_step(0, 1 << 60);
+let val = roll(&mut RandAdapter(&mut rng));
+assert!((1..=6).contains(&val));
+// The original generator is still usable afterwards.
+rng.u64();
+```
+"#
+)]
+#[derive(Debug)]
+pub struct RandAdapter<'a, G: Generator>(pub &'a mut G);
+
+#[cfg(feature = "rand06-compat")]
+impl<G: Generator> rand_core_06::RngCore for RandAdapter<'_, G> {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.0.u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.0.u64()
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        fill_via_u64(self.0, dst);
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dst: &mut [u8]) -> Result<(), rand_core_06::Error> {
+        fill_via_u64(self.0, dst);
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "rand06-compat", feature = "secure"))]
+impl<G: SecureGenerator> rand_core_06::CryptoRng for RandAdapter<'_, G> {}
+
+#[cfg(feature = "rand09-compat")]
+impl<G: Generator> rand_core_09::RngCore for RandAdapter<'_, G> {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.0.u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.0.u64()
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        fill_via_u64(self.0, dst);
+    }
+}
+
+#[cfg(all(feature = "rand09-compat", feature = "secure"))]
+impl<G: SecureGenerator> rand_core_09::CryptoRng for RandAdapter<'_, G> {}
+
+/// Wraps any `RngCore` from version 0.9 of `rand_core`, implementing [`Generator`]
+/// on top of it.
+///
+/// Makes every method of this crate available for external generators, such
+/// as hardware RNG drivers and HSM-backed generators. [`SecureGenerator`] is
+/// also implemented when `R` implements `CryptoRng`, with [`SecureGenerator::fill_bytes`]
+/// being forwarded directly to the wrapped generator.
+///
+/// Since there is no way to create an arbitrary `RngCore` from entropy,
+/// [`Generator::try_new`] always fails with [`ErrorKind::Unsupported`],
+/// and [`Generator::new`] panics. Wrap an existing generator instead.
+///
+/// # Examples
+///
+/// ```
+/// use rand::{SeedableRng, rngs::StdRng};
+/// use ya_rand::*;
+///
+/// let mut rng = FromRandCore(StdRng::seed_from_u64(69));
+/// let val = rng.bound(420);
+/// assert!(val < 420);
+/// let val = rng.f64();
+/// assert!((0.0..1.0).contains(&val));
+/// ```
+#[cfg(feature = "rand09-compat")]
+#[derive(Clone, Debug)]
+pub struct FromRandCore<R>(pub R);
+
+/// Identical to [`FromRandCore`], but wraps an `RngCore` from version 0.6 of `rand_core`.
+#[cfg(feature = "rand06-compat")]
+#[derive(Clone, Debug)]
+pub struct FromRandCore06<R>(pub R);
+
+#[cfg(feature = "rand09-compat")]
+impl<R: rand_core_09::RngCore> Generator for FromRandCore<R> {
+    /// Always fails with [`ErrorKind::Unsupported`].
+    #[inline]
+    fn try_new() -> Result<Self, Error> {
+        Err(Error::new(ErrorKind::Unsupported))
+    }
+
+    #[inline]
+    fn u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+}
+
+#[cfg(all(feature = "rand09-compat", feature = "secure"))]
+impl<R: rand_core_09::CryptoRng> SecureGenerator for FromRandCore<R> {
+    #[inline]
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.0.fill_bytes(dst);
+    }
+}
+
+#[cfg(feature = "rand06-compat")]
+impl<R: rand_core_06::RngCore> Generator for FromRandCore06<R> {
+    /// Always fails with [`ErrorKind::Unsupported`].
+    #[inline]
+    fn try_new() -> Result<Self, Error> {
+        Err(Error::new(ErrorKind::Unsupported))
+    }
+
+    #[inline]
+    fn u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+}
+
+#[cfg(all(feature = "rand06-compat", feature = "secure"))]
+impl<R: rand_core_06::RngCore + rand_core_06::CryptoRng> SecureGenerator for FromRandCore06<R> {
+    #[inline]
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.0.fill_bytes(dst);
+    }
+}
+
+#[cfg(feature = "rand06-compat")]
+impl_rand_core_06!(fill_via_u64 => Xoshiro256pp, Xoshiro512pp, RomuQuad, RomuTrio);
+#[cfg(all(feature = "rand06-compat", feature = "secure"))]
+impl_rand_core_06!(SecureGenerator::fill_bytes => SecureRng);
+#[cfg(all(feature = "rand06-compat", feature = "secure"))]
+impl rand_core_06::CryptoRng for SecureRng {}
+
+#[cfg(feature = "rand09-compat")]
+impl_rand_core_09!(fill_via_u64 => Xoshiro256pp, Xoshiro512pp, RomuQuad, RomuTrio);
+#[cfg(all(feature = "rand09-compat", feature = "secure"))]
+impl_rand_core_09!(SecureGenerator::fill_bytes => SecureRng);
+#[cfg(all(feature = "rand09-compat", feature = "secure"))]
+impl rand_core_09::CryptoRng for SecureRng {}
@@ -0,0 +1,210 @@
+use crate::drbg::{FrontEnd, Mechanism};
+use crate::entropy::{EntropySource, OsEntropy};
+use crate::error::Error;
+use crate::rng::{Generator, SecureGenerator};
+use aes::Aes256;
+use aes::cipher::{BlockEncrypt, KeyInit};
+use core::fmt;
+
+const KEY_LEN: usize = 32;
+const BLOCK_LEN: usize = 16;
+const SEED_LEN: usize = KEY_LEN + BLOCK_LEN;
+// See table 3 of NIST SP 800-90A.
+const RESEED_INTERVAL: u64 = 1 << 48;
+const MAX_REQUEST_LEN: usize = (1 << 19) / 8;
+
+/// A cryptographically secure random number generator, implementing
+/// the CTR_DRBG mechanism from [NIST SP 800-90A], using AES-256 and no derivation function.
+///
+/// The NIST-specified instantiate, reseed, and generate functions are available
+/// through [`CtrDrbg::instantiate`], [`CtrDrbg::reseed`], and [`CtrDrbg::generate`].
+/// When used through the [`Generator`] and [`SecureGenerator`] traits, output is
+/// buffered and the generator is automatically reseeded using entropy from the
+/// operating system once the reseed interval (2<sup>48</sup> requests) is reached.
+///
+/// Unless you're required to use a NIST-approved DRBG, prefer using [`crate::SecureRng`].
+///
+/// [NIST SP 800-90A]: https://csrc.nist.gov/pubs/sp/800/90/a/r1/final
+pub struct CtrDrbg {
+    front: FrontEnd<CtrState>,
+}
+
+impl fmt::Debug for CtrDrbg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("all `CtrDrbg` fields are private")
+    }
+}
+
+impl CtrDrbg {
+    /// Length of the entropy input used during instantiation and reseeding, in bytes.
+    pub const SEED_LEN: usize = SEED_LEN;
+
+    /// Largest amount of bytes which can be requested from a single call to
+    /// [`CtrDrbg::generate`].
+    pub const MAX_REQUEST_LEN: usize = MAX_REQUEST_LEN;
+
+    /// Instantiates the generator using the provided `entropy` input and
+    /// optional `personalization` string.
+    ///
+    /// # Panics
+    ///
+    /// If `personalization` is longer than [`CtrDrbg::SEED_LEN`].
+    pub fn instantiate(entropy: &[u8; SEED_LEN], personalization: &[u8]) -> Self {
+        let seed_material = xor_padded(entropy, personalization);
+        let mut state = CtrState {
+            cipher: Aes256::new(&[0; KEY_LEN].into()),
+            v: 0,
+            reseed_counter: 0,
+        };
+        state.update(&seed_material);
+        state.reseed_counter = 1;
+        Self {
+            front: FrontEnd::new(state),
+        }
+    }
+
+    /// Reseeds the generator using the provided `entropy` input and
+    /// optional `additional_input`. Any buffered output is discarded.
+    ///
+    /// # Panics
+    ///
+    /// If `additional_input` is longer than [`CtrDrbg::SEED_LEN`].
+    #[inline]
+    pub fn reseed(&mut self, entropy: &[u8; SEED_LEN], additional_input: &[u8]) {
+        self.front.reseed(entropy, additional_input);
+    }
+
+    /// Fills `dst` using the generate function of the DRBG, with optional
+    /// `additional_input`. This bypasses any buffered output.
+    ///
+    /// If the reseed interval has been reached, the generator will first be
+    /// reseeded using entropy from the operating system.
+    ///
+    /// # Panics
+    ///
+    /// If `dst` is longer than [`CtrDrbg::MAX_REQUEST_LEN`], if `additional_input`
+    /// is longer than [`CtrDrbg::SEED_LEN`], or if the operating system fails to provide
+    /// entropy when a reseed is required.
+    #[inline]
+    pub fn generate(&mut self, dst: &mut [u8], additional_input: &[u8]) {
+        self.front.generate(dst, additional_input);
+    }
+
+    /// Fills `dst` using the generate function of the DRBG, with optional
+    /// `additional_input`. This bypasses any buffered output.
+    ///
+    /// Unlike [`CtrDrbg::generate`], failing to retrieve entropy from the operating
+    /// system when a reseed is required returns an error, and leaves `dst` untouched.
+    ///
+    /// # Panics
+    ///
+    /// If `dst` is longer than [`CtrDrbg::MAX_REQUEST_LEN`] or if `additional_input` is longer than [`CtrDrbg::SEED_LEN`].
+    #[inline]
+    pub fn try_generate(&mut self, dst: &mut [u8], additional_input: &[u8]) -> Result<(), Error> {
+        self.front.try_generate(dst, additional_input)
+    }
+}
+
+impl SecureGenerator for CtrDrbg {
+    #[inline]
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.front.fill_bytes(dst);
+    }
+}
+
+impl Generator for CtrDrbg {
+    #[inline]
+    fn try_new() -> Result<Self, Error> {
+        Self::try_new_from(&mut OsEntropy)
+    }
+
+    #[inline]
+    fn try_new_from<S: EntropySource + ?Sized>(source: &mut S) -> Result<Self, Error> {
+        let mut entropy = [0; SEED_LEN];
+        source.fill(&mut entropy)?;
+        Ok(Self::instantiate(&entropy, &[]))
+    }
+
+    #[cfg_attr(feature = "inline", inline)]
+    fn u64(&mut self) -> u64 {
+        self.front.u64()
+    }
+}
+
+/// Internal state of the CTR_DRBG mechanism.
+struct CtrState {
+    cipher: Aes256,
+    v: u128,
+    reseed_counter: u64,
+}
+
+impl CtrState {
+    #[inline]
+    fn next_block(&mut self) -> [u8; BLOCK_LEN] {
+        self.v = self.v.wrapping_add(1);
+        let mut block = self.v.to_be_bytes().into();
+        self.cipher.encrypt_block(&mut block);
+        block.into()
+    }
+
+    /// The CTR_DRBG_Update function, as specified in section 10.2.1.2.
+    fn update(&mut self, provided_data: &[u8; SEED_LEN]) {
+        let mut temp = [0; SEED_LEN];
+        for chunk in temp.chunks_exact_mut(BLOCK_LEN) {
+            chunk.copy_from_slice(&self.next_block());
+        }
+        for (t, p) in temp.iter_mut().zip(provided_data) {
+            *t ^= p;
+        }
+        let (key, v) = temp.split_at(KEY_LEN);
+        self.cipher = Aes256::new(key.into());
+        // The unwrap gets optimized out since `v` is always `BLOCK_LEN` bytes long.
+        self.v = u128::from_be_bytes(v.try_into().unwrap());
+    }
+}
+
+impl Mechanism for CtrState {
+    const ENTROPY_LEN: usize = SEED_LEN;
+    const MAX_REQUEST_LEN: usize = MAX_REQUEST_LEN;
+
+    fn generate(&mut self, dst: &mut [u8], additional_input: &[u8]) -> bool {
+        if self.reseed_counter > RESEED_INTERVAL {
+            return false;
+        }
+        let has_additional_input = !additional_input.is_empty();
+        let additional_input = xor_padded(&[0; SEED_LEN], additional_input);
+        if has_additional_input {
+            self.update(&additional_input);
+        }
+        for chunk in dst.chunks_mut(BLOCK_LEN) {
+            let block = self.next_block();
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+        self.update(&additional_input);
+        self.reseed_counter += 1;
+        true
+    }
+
+    fn reseed(&mut self, entropy: &[u8], additional_input: &[u8]) {
+        // The unwrap gets optimized out since the front-end
+        // always provides `ENTROPY_LEN` bytes of entropy.
+        let seed_material = xor_padded(entropy.try_into().unwrap(), additional_input);
+        self.update(&seed_material);
+        self.reseed_counter = 1;
+    }
+}
+
+/// Returns the result of XORing `x` with `y`, where `y` is padded with zeros.
+#[inline]
+fn xor_padded(x: &[u8; SEED_LEN], y: &[u8]) -> [u8; SEED_LEN] {
+    assert!(
+        y.len() <= SEED_LEN,
+        "CTR_DRBG inputs can't be longer than {} bytes",
+        SEED_LEN
+    );
+    let mut ret = *x;
+    for (r, v) in ret.iter_mut().zip(y) {
+        *r ^= v;
+    }
+    ret
+}
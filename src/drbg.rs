@@ -0,0 +1,143 @@
+use crate::entropy::{EntropySource, OsEntropy};
+use crate::error::Error;
+use crate::util;
+
+const BUF_LEN_U64: usize = 32;
+/// Largest entropy input supported by any mechanism, in bytes.
+pub const MAX_ENTROPY_LEN: usize = 64;
+
+/// The internal interface of a DRBG mechanism from NIST SP 800-90A.
+pub trait Mechanism {
+    /// Length of the entropy input used when reseeding, in bytes.
+    const ENTROPY_LEN: usize;
+
+    /// Largest amount of bytes which can be produced by a single call to `generate`.
+    const MAX_REQUEST_LEN: usize;
+
+    /// Fills `dst` with output from the mechanism, returning `false`
+    /// (and leaving `dst` untouched) when a reseed is required.
+    fn generate(&mut self, dst: &mut [u8], additional_input: &[u8]) -> bool;
+
+    /// Reseeds the mechanism using `entropy`, which is `ENTROPY_LEN` bytes long.
+    fn reseed(&mut self, entropy: &[u8], additional_input: &[u8]);
+}
+
+/// Buffered front-end which allows any [`Mechanism`] to be used
+/// as the backing implementation of a `SecureGenerator`.
+///
+/// Reseeding with entropy from the operating system happens automatically
+/// whenever the mechanism requires it.
+pub struct FrontEnd<M> {
+    buf: [u64; BUF_LEN_U64],
+    index: usize,
+    mechanism: M,
+}
+
+impl<M: Mechanism> FrontEnd<M> {
+    #[inline]
+    pub fn new(mechanism: M) -> Self {
+        const {
+            assert!(
+                M::ENTROPY_LEN <= MAX_ENTROPY_LEN,
+                "DRBG mechanisms can't use more than `MAX_ENTROPY_LEN` (64) bytes of entropy input"
+            )
+        };
+        Self {
+            buf: [0; BUF_LEN_U64],
+            // Forces a refill on the first call to `u64`.
+            index: BUF_LEN_U64,
+            mechanism,
+        }
+    }
+
+    /// Runs the generate function of the mechanism, reseeding it first if needed.
+    ///
+    /// # Panics
+    ///
+    /// If `dst` is longer than `M::MAX_REQUEST_LEN`, or if the operating system fails
+    /// to provide entropy when a reseed is required.
+    #[inline]
+    pub fn generate(&mut self, dst: &mut [u8], additional_input: &[u8]) {
+        generate(&mut self.mechanism, dst, additional_input);
+    }
+
+    /// Runs the generate function of the mechanism, reseeding it first if needed.
+    ///
+    /// Fails, leaving `dst` untouched, if the operating system fails to provide
+    /// entropy when a reseed is required.
+    ///
+    /// # Panics
+    ///
+    /// If `dst` is longer than `M::MAX_REQUEST_LEN`.
+    #[inline]
+    pub fn try_generate(&mut self, dst: &mut [u8], additional_input: &[u8]) -> Result<(), Error> {
+        try_generate(&mut self.mechanism, dst, additional_input)
+    }
+
+    /// Reseeds the mechanism, discarding any buffered output.
+    #[inline]
+    pub fn reseed(&mut self, entropy: &[u8], additional_input: &[u8]) {
+        self.index = BUF_LEN_U64;
+        self.mechanism.reseed(entropy, additional_input);
+    }
+
+    #[inline]
+    pub fn fill_bytes(&mut self, dst: &mut [u8]) {
+        for chunk in dst.chunks_mut(M::MAX_REQUEST_LEN) {
+            self.generate(chunk, &[]);
+        }
+    }
+
+    #[inline]
+    pub fn u64(&mut self) -> u64 {
+        if self.index >= self.buf.len() {
+            self.index = 0;
+            // SAFETY: `u64` is valid as nothing more than a collection of bytes.
+            let buf = unsafe { util::as_raw_bytes_mut(&mut self.buf) };
+            generate(&mut self.mechanism, buf, &[]);
+        }
+        // SAFETY: We've just guaranteed that `self.index` will be
+        // in bounds in the above if-statement.
+        let ret = unsafe { *self.buf.get_unchecked(self.index) };
+        self.index += 1;
+        ret
+    }
+}
+
+/// Panics if the operating system fails to provide entropy when a reseed is required.
+#[inline]
+fn generate<M: Mechanism>(mechanism: &mut M, dst: &mut [u8], additional_input: &[u8]) {
+    try_generate(mechanism, dst, additional_input)
+        .expect("retrieving random data from the operating system should never fail");
+}
+
+#[inline]
+fn try_generate<M: Mechanism>(
+    mechanism: &mut M,
+    dst: &mut [u8],
+    additional_input: &[u8],
+) -> Result<(), Error> {
+    assert!(
+        dst.len() <= M::MAX_REQUEST_LEN,
+        "DRBG requests can't be longer than {} bytes",
+        M::MAX_REQUEST_LEN
+    );
+    if !mechanism.generate(dst, additional_input) {
+        try_reseed_from_os(mechanism)?;
+        let success = mechanism.generate(dst, additional_input);
+        debug_assert!(success, "BUG: freshly reseeded DRBG requires a reseed");
+    }
+    Ok(())
+}
+
+#[inline(never)]
+fn try_reseed_from_os<M: Mechanism>(mechanism: &mut M) -> Result<(), Error> {
+    let mut entropy = [0; MAX_ENTROPY_LEN];
+    let entropy = &mut entropy[..M::ENTROPY_LEN];
+    let result = OsEntropy.fill(entropy);
+    if result.is_ok() {
+        mechanism.reseed(entropy, &[]);
+    }
+    util::wipe_slice(entropy);
+    result
+}
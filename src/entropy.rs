@@ -0,0 +1,577 @@
+/*!
+Sources of entropy used for seeding generators.
+
+Every generator in this crate can be created from any [`EntropySource`] using
+[`Generator::try_new_from`]. [`Generator::try_new`] is just a shortcut for doing so
+with [`OsEntropy`].
+
+For users who don't fully trust any single source, [`EntropyPool`] combines the
+output of multiple sources before it's used to seed a generator.
+Sources which may fail in ways that leave their output degenerate, such as hardware
+noise sources, can be continuously checked by wrapping them in [`HealthTested`].
+
+# Examples
+
+```
+use ya_rand::*;
+use ya_rand::entropy::*;
+
+let user_bytes = b"some bytes from a hardware token";
+let mut pool = EntropyPool::new(OsEntropy).with_bytes(user_bytes);
+let mut rng: SecureRng = pool.seed().unwrap();
+let val = rng.bound(69);
+assert!(val < 69);
+```
+*/
+
+use crate::error::{Error, ErrorKind};
+use crate::rng::Generator;
+
+/// Size of the stack buffer used when combining sources.
+const MIX_BUF_LEN: usize = 64;
+
+/// Trait for types which can provide entropy for seeding generators.
+pub trait EntropySource {
+    /// Fills `dst` with entropy, overwriting its existing contents.
+    fn fill(&mut self, dst: &mut [u8]) -> Result<(), Error>;
+}
+
+impl<S: EntropySource + ?Sized> EntropySource for &mut S {
+    #[inline]
+    fn fill(&mut self, dst: &mut [u8]) -> Result<(), Error> {
+        (**self).fill(dst)
+    }
+}
+
+/// User-supplied bytes are folded into `dst` using XOR, wrapping around to
+/// the start when there are more bytes than `dst` can hold. Bytes of `dst` past
+/// the length of the slice are zeroed, so this should only ever be used as
+/// one of multiple sources in an [`EntropyPool`].
+impl EntropySource for &[u8] {
+    #[inline]
+    fn fill(&mut self, dst: &mut [u8]) -> Result<(), Error> {
+        dst.fill(0);
+        if !dst.is_empty() {
+            for (i, byte) in self.iter().enumerate() {
+                dst[i % dst.len()] ^= byte;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Two sources are combined by XORing their outputs together.
+impl<A: EntropySource, B: EntropySource> EntropySource for (A, B) {
+    fn fill(&mut self, dst: &mut [u8]) -> Result<(), Error> {
+        self.0.fill(dst)?;
+        let mut buf = [0; MIX_BUF_LEN];
+        for chunk in dst.chunks_mut(MIX_BUF_LEN) {
+            let buf = &mut buf[..chunk.len()];
+            self.1.fill(buf)?;
+            chunk.iter_mut().zip(buf.iter()).for_each(|(x, y)| *x ^= y);
+        }
+        Ok(())
+    }
+}
+
+/// Entropy provided by the operating system, through [`getrandom`].
+///
+/// This is the source used by [`Generator::try_new`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OsEntropy;
+
+impl EntropySource for OsEntropy {
+    #[inline]
+    fn fill(&mut self, dst: &mut [u8]) -> Result<(), Error> {
+        event!(
+            trace,
+            bytes = dst.len(),
+            "requesting entropy from the operating system"
+        );
+        getrandom::fill(dst).map_err(|err| {
+            let err = Error::from_os(err);
+            event!(error, error = %err, "operating system failed to provide entropy");
+            err
+        })
+    }
+}
+
+/// Entropy provided directly by the processor, through the `RDSEED` instruction.
+///
+/// Attempting to use this source on a processor which doesn't support `RDSEED` fails
+/// with [`ErrorKind::Unsupported`]. If the instruction repeatedly fails to provide
+/// entropy, an error of kind [`ErrorKind::Entropy`] is returned.
+#[cfg(target_arch = "x86_64")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RdSeed;
+
+#[cfg(target_arch = "x86_64")]
+impl RdSeed {
+    /// Returns `true` if the current processor supports the `RDSEED` instruction.
+    #[inline]
+    pub fn is_available() -> bool {
+        std::is_x86_feature_detected!("rdseed")
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+impl EntropySource for RdSeed {
+    #[inline]
+    fn fill(&mut self, dst: &mut [u8]) -> Result<(), Error> {
+        if !Self::is_available() {
+            return Err(Error::new(ErrorKind::Unsupported));
+        }
+        // SAFETY: We've just checked that the processor supports `RDSEED`.
+        unsafe { rdseed_fill(dst) }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "rdseed")]
+fn rdseed_fill(dst: &mut [u8]) -> Result<(), Error> {
+    // Intel recommends retrying on failure, since `RDSEED` can be exhausted
+    // when it's under heavy load.
+    const RETRIES: usize = 1 << 10;
+    for chunk in dst.chunks_mut(size_of::<u64>()) {
+        let mut val = 0;
+        let mut attempt = 0;
+        while core::arch::x86_64::_rdseed64_step(&mut val) != 1 {
+            attempt += 1;
+            if attempt == RETRIES {
+                event!(warn, "`RDSEED` repeatedly failed to provide entropy");
+                return Err(Error::new(ErrorKind::Entropy));
+            }
+            core::hint::spin_loop();
+        }
+        chunk.copy_from_slice(&val.to_le_bytes()[..chunk.len()]);
+    }
+    Ok(())
+}
+
+/// Entropy collected from timing jitter of the processor, in the spirit of [jitterentropy].
+///
+/// Output is produced by folding together several timing measurements of a memory access
+/// loop per output bit. These vary due to caches, pipelines, interrupts, and other effects
+/// that are very hard to predict, but how much entropy they actually contain depends
+/// heavily on the hardware, and the measurements are mixed with a simple bijective function
+/// rather than a cryptographic conditioner. The output therefore makes no full-entropy
+/// claim, and shouldn't be relied on as the only source of a seed. It is meant to
+/// supplement other sources in an [`EntropyPool`], particularly in environments where
+/// [`OsEntropy`] is distrusted, and is **much** slower than any other source.
+///
+/// Every measurement is checked by the health tests from section 4.4 of NIST SP 800-90B:
+/// the repetition count test and the adaptive proportion test, both assuming 1 bit of
+/// min-entropy per measurement. If either test fails, the source is permanently
+/// disabled and returns [`JitterEntropy::HEALTH_TEST_FAILURE`].
+///
+/// The timer is `RDTSC` on x86_64, and [`std::time::Instant`] everywhere else.
+///
+/// [jitterentropy]: https://www.chronox.de/jent/
+pub struct JitterEntropy {
+    timer: Timer,
+    mem: [u8; JITTER_MEM_LEN],
+    mem_index: usize,
+    prev_time: u64,
+    prev_delta: u64,
+    health: HealthTests,
+}
+
+impl core::fmt::Debug for JitterEntropy {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("all `JitterEntropy` fields are private")
+    }
+}
+
+/// Size of the memory region accessed between measurements.
+const JITTER_MEM_LEN: usize = 1 << 11;
+/// Amount of measurements folded into every output bit.
+const JITTER_OVERSAMPLING: usize = 3;
+/// Amount of measurements checked by [`JitterEntropy::new`] before any output is produced.
+const JITTER_STARTUP_SAMPLES: usize = 1 << 10;
+// Cutoffs of the health tests, as calculated in sections 4.4.1 and 4.4.2 of NIST SP 800-90B.
+const JITTER_RCT_CUTOFF: usize = 31;
+const JITTER_APT_CUTOFF: usize = 410;
+
+impl JitterEntropy {
+    /// Error returned once the health tests have detected a failure of the noise source.
+    pub const HEALTH_TEST_FAILURE: Error = Error::new(ErrorKind::HealthTest);
+
+    /// Attempts to create a new jitter entropy source.
+    ///
+    /// Before returning, the noise source is run through a startup health test. If
+    /// the timer doesn't have enough resolution to observe any jitter, or the health
+    /// tests fail for any other reason, [`JitterEntropy::HEALTH_TEST_FAILURE`] is returned.
+    pub fn new() -> Result<Self, Error> {
+        let timer = Timer::new();
+        let prev_time = timer.now();
+        let mut jitter = Self {
+            timer,
+            mem: [0; JITTER_MEM_LEN],
+            mem_index: 0,
+            prev_time,
+            prev_delta: 0,
+            health: HealthTests::new(JITTER_RCT_CUTOFF, JITTER_APT_CUTOFF),
+        };
+        for _ in 0..JITTER_STARTUP_SAMPLES {
+            jitter.measure()?;
+        }
+        Ok(jitter)
+    }
+
+    /// Attempts to return a `u64` which folds together `JITTER_OVERSAMPLING`
+    /// non-stuck measurements per bit.
+    fn u64(&mut self) -> Result<u64, Error> {
+        let mut acc = 0;
+        let mut samples = 0;
+        while samples < u64::BITS as usize * JITTER_OVERSAMPLING {
+            // Stuck measurements are discarded.
+            if let Some(delta) = self.measure()? {
+                // Multiplication by an odd constant is a bijection, so
+                // no entropy is lost while mixing in new measurements.
+                acc = (acc ^ delta)
+                    .rotate_left(7)
+                    .wrapping_mul(0x9E3779B97F4A7C15);
+                samples += 1;
+            }
+        }
+        Ok(acc)
+    }
+
+    /// Takes a single timing measurement, returning `None` if it was stuck.
+    #[inline(never)]
+    fn measure(&mut self) -> Result<Option<u64>, Error> {
+        // Vary the amount of work using the previous measurement,
+        // which adds even more unpredictability to the timing.
+        let iterations = 1 + (self.prev_time & 0x3F) as usize;
+        for _ in 0..iterations {
+            self.mem_index = (self.mem_index + 67) % JITTER_MEM_LEN;
+            let byte = &mut self.mem[self.mem_index];
+            *byte = core::hint::black_box(byte.wrapping_add(1));
+        }
+        let time = self.timer.now();
+        let delta = time.wrapping_sub(self.prev_time);
+        let delta2 = delta.wrapping_sub(self.prev_delta);
+        self.prev_time = time;
+        self.prev_delta = delta;
+        if !self.health.check(delta) {
+            event!(error, "jitter entropy source failed its health tests");
+            return Err(Self::HEALTH_TEST_FAILURE);
+        }
+        match delta == 0 || delta2 == 0 {
+            true => Ok(None),
+            false => Ok(Some(delta)),
+        }
+    }
+}
+
+impl EntropySource for JitterEntropy {
+    fn fill(&mut self, dst: &mut [u8]) -> Result<(), Error> {
+        for chunk in dst.chunks_mut(size_of::<u64>()) {
+            let val = self.u64()?;
+            chunk.copy_from_slice(&val.to_le_bytes()[..chunk.len()]);
+        }
+        Ok(())
+    }
+}
+
+/// Window size of the adaptive proportion test for non-binary samples.
+const APT_WINDOW: usize = 512;
+/// Negated base 2 logarithm of the false positive probability of the health tests.
+const HEALTH_ALPHA_LOG2: u32 = 30;
+
+/// Continuous health tests from section 4.4 of NIST SP 800-90B.
+#[derive(Clone)]
+struct HealthTests {
+    failed: bool,
+    rct_prev: u64,
+    rct_count: usize,
+    rct_cutoff: usize,
+    apt_base: u64,
+    apt_count: usize,
+    apt_seen: usize,
+    apt_cutoff: usize,
+}
+
+impl HealthTests {
+    const fn new(rct_cutoff: usize, apt_cutoff: usize) -> Self {
+        Self {
+            failed: false,
+            rct_prev: 0,
+            rct_count: 0,
+            rct_cutoff,
+            apt_base: 0,
+            apt_count: 0,
+            apt_seen: APT_WINDOW,
+            apt_cutoff,
+        }
+    }
+
+    /// Creates health tests for samples with `min_entropy` bits of min-entropy each,
+    /// with a false positive probability of 2<sup>-30</sup>.
+    const fn with_min_entropy(min_entropy: u32) -> Self {
+        Self::new(rct_cutoff(min_entropy), apt_cutoff(min_entropy))
+    }
+
+    /// Records `sample`, returning `false` if the health tests have ever failed.
+    fn check(&mut self, sample: u64) -> bool {
+        // Repetition count test.
+        if sample == self.rct_prev {
+            self.rct_count += 1;
+        } else {
+            self.rct_prev = sample;
+            self.rct_count = 1;
+        }
+        // Adaptive proportion test.
+        if self.apt_seen == APT_WINDOW {
+            self.apt_base = sample;
+            self.apt_count = 1;
+            self.apt_seen = 1;
+        } else {
+            self.apt_seen += 1;
+            if sample == self.apt_base {
+                self.apt_count += 1;
+            }
+        }
+        self.failed |= self.rct_count >= self.rct_cutoff || self.apt_count >= self.apt_cutoff;
+        !self.failed
+    }
+}
+
+/// Cutoff of the repetition count test, from section 4.4.1 of NIST SP 800-90B.
+pub(crate) const fn rct_cutoff(min_entropy: u32) -> usize {
+    1 + HEALTH_ALPHA_LOG2.div_ceil(min_entropy) as usize
+}
+
+/// Cutoff of the adaptive proportion test, from section 4.4.2 of NIST SP 800-90B.
+///
+/// This is one more than the smallest count whose binomial CDF, over `APT_WINDOW`
+/// samples with a probability of 2<sup>-`min_entropy`</sup>, reaches 1 - 2<sup>-30</sup>.
+pub(crate) const fn apt_cutoff(min_entropy: u32) -> usize {
+    let p = 1.0 / (1_u64 << min_entropy) as f64;
+    let target = 1.0 - 1.0 / (1_u64 << HEALTH_ALPHA_LOG2) as f64;
+    // The probability of zero successes, which is large enough to
+    // be representable for any window size used here.
+    let mut pmf = 1.0;
+    let mut i = 0;
+    while i < APT_WINDOW {
+        pmf *= 1.0 - p;
+        i += 1;
+    }
+    let mut cdf = pmf;
+    let mut k = 0;
+    while cdf < target && k < APT_WINDOW {
+        pmf *= (APT_WINDOW - k) as f64 / (k + 1) as f64 * p / (1.0 - p);
+        cdf += pmf;
+        k += 1;
+    }
+    1 + k
+}
+
+/// Continuously checks the output of an [`EntropySource`] using the health tests
+/// from section 4.4 of NIST SP 800-90B.
+///
+/// Every byte of output is treated as one sample, and checked by both the
+/// repetition count test and the adaptive proportion test (with a window of 512
+/// samples). The cutoffs of the tests are derived from the min-entropy claimed for
+/// every byte, for a false positive probability of 2<sup>-30</sup> per test. Once
+/// either test fails, the source is considered degenerate and every call to
+/// [`EntropySource::fill`] fails with [`ErrorKind::HealthTest`], without
+/// querying the wrapped source again.
+///
+/// The wrapper doesn't perform the startup tests required by SP 800-90B, which
+/// would require discarding the first 1024 samples of the source before using any
+/// of its output.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+/// use ya_rand::entropy::*;
+///
+/// // A hardware source which claims 4 bits of min-entropy per byte.
+/// let trng = HealthTested::new(OsEntropy, 4);
+/// let mut pool = EntropyPool::new(trng).with_bytes(b"device serial number");
+/// let mut rng: SecureRng = pool.seed().unwrap();
+///
+/// // A source which has gotten stuck.
+/// let mut stuck = HealthTested::new(&[0_u8][..], 4);
+/// let err = stuck.fill(&mut [0; 32]).unwrap_err();
+/// assert!(err.kind() == ErrorKind::HealthTest);
+/// assert!(stuck.has_failed());
+/// ```
+#[derive(Clone)]
+pub struct HealthTested<S> {
+    source: S,
+    health: HealthTests,
+}
+
+impl<S> core::fmt::Debug for HealthTested<S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("all `HealthTested` fields are private")
+    }
+}
+
+impl<S: EntropySource> HealthTested<S> {
+    /// Wraps `source`, which is claimed to provide `min_entropy` bits
+    /// of min-entropy per byte.
+    ///
+    /// # Panics
+    ///
+    /// If `min_entropy` isn't within the interval [1, 8].
+    #[inline]
+    pub const fn new(source: S, min_entropy: u32) -> Self {
+        assert!(
+            1 <= min_entropy && min_entropy <= 8,
+            "`min_entropy` must be within [1, 8]"
+        );
+        Self {
+            source,
+            health: HealthTests::with_min_entropy(min_entropy),
+        }
+    }
+
+    /// Returns `true` once the health tests have detected a failure of the source.
+    #[inline]
+    pub const fn has_failed(&self) -> bool {
+        self.health.failed
+    }
+
+    /// Returns a reference to the wrapped source.
+    #[inline]
+    pub const fn inner(&self) -> &S {
+        &self.source
+    }
+
+    /// Returns the wrapped source.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+}
+
+impl<S: EntropySource> EntropySource for HealthTested<S> {
+    fn fill(&mut self, dst: &mut [u8]) -> Result<(), Error> {
+        const FAILURE: Error = Error::new(ErrorKind::HealthTest);
+        if self.health.failed {
+            return Err(FAILURE);
+        }
+        self.source.fill(dst)?;
+        dst.iter()
+            .for_each(|&byte| _ = self.health.check(byte as u64));
+        match self.health.failed {
+            true => {
+                event!(
+                    error,
+                    source = core::any::type_name::<S>(),
+                    "entropy source failed its health tests and has been disabled"
+                );
+                // Degenerate output is never handed out.
+                dst.fill(0);
+                Err(FAILURE)
+            }
+            false => Ok(()),
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+struct Timer;
+
+#[cfg(target_arch = "x86_64")]
+impl Timer {
+    #[inline]
+    fn new() -> Self {
+        Self
+    }
+
+    #[inline]
+    fn now(&self) -> u64 {
+        // SAFETY: `RDTSC` is available on every x86_64 processor.
+        #[allow(unused_unsafe)]
+        unsafe {
+            core::arch::x86_64::_rdtsc()
+        }
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+struct Timer {
+    epoch: std::time::Instant,
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+impl Timer {
+    #[inline]
+    fn new() -> Self {
+        Self {
+            epoch: std::time::Instant::now(),
+        }
+    }
+
+    #[inline]
+    fn now(&self) -> u64 {
+        self.epoch.elapsed().as_nanos() as u64
+    }
+}
+
+/// Combines multiple [`EntropySource`] values into a single source.
+///
+/// The output of every source in the pool is XORed together, so the combined output
+/// is at least as unpredictable as the output of its best source, as long as the
+/// sources are independent of each other. This lets users who don't fully trust
+/// any single source hedge their bets.
+///
+/// Sources are queried in the order they were added, and if any of them fails the
+/// error is returned immediately.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntropyPool<S> {
+    sources: S,
+}
+
+impl Default for EntropyPool<OsEntropy> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(OsEntropy)
+    }
+}
+
+impl<S: EntropySource> EntropyPool<S> {
+    /// Creates a pool containing only `source`.
+    #[inline]
+    pub const fn new(source: S) -> Self {
+        Self { sources: source }
+    }
+
+    /// Adds `source` to the pool.
+    #[inline]
+    pub fn with<T: EntropySource>(self, source: T) -> EntropyPool<(S, T)> {
+        EntropyPool {
+            sources: (self.sources, source),
+        }
+    }
+
+    /// Adds user-supplied `bytes` to the pool.
+    ///
+    /// The bytes are folded into the output of the pool, so they can't
+    /// reduce its quality even if they're completely predictable.
+    #[inline]
+    pub fn with_bytes(self, bytes: &[u8]) -> EntropyPool<(S, &[u8])> {
+        self.with(bytes)
+    }
+
+    /// Attempts to create a generator of type `G`, seeded using the pool.
+    ///
+    /// Fails with [`ErrorKind::Unsupported`] if `G` doesn't
+    /// implement [`Generator::try_new_from`].
+    #[inline]
+    pub fn seed<G: Generator>(&mut self) -> Result<G, Error> {
+        G::try_new_from(self)
+    }
+}
+
+impl<S: EntropySource> EntropySource for EntropyPool<S> {
+    #[inline]
+    fn fill(&mut self, dst: &mut [u8]) -> Result<(), Error> {
+        self.sources.fill(dst)
+    }
+}
@@ -0,0 +1,103 @@
+use core::fmt;
+
+/// The error type returned by the fallible operations of this crate.
+///
+/// Use [`Error::kind`] to find out what went wrong. When the error was reported by the
+/// operating system, the underlying error code is available using [`Error::raw_os_error`].
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+/// use ya_rand::testing::ReplayRng;
+///
+/// let err = ReplayRng::try_new().unwrap_err();
+/// assert!(err.kind() == ErrorKind::Unsupported);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Error {
+    kind: ErrorKind,
+    os: Option<getrandom::Error>,
+}
+
+/// A list specifying the general categories of [`Error`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The operation isn't supported, such as creating a generator which
+    /// can only be constructed manually from entropy.
+    Unsupported,
+    /// A source of entropy failed to provide any.
+    Entropy,
+    /// The health tests of a noise source detected a failure.
+    HealthTest,
+    /// A seed or generator state was rejected, such as one which is entirely zero.
+    InvalidSeed,
+    /// An argument was outside of the range of values supported by the operation.
+    InvalidParameter,
+    /// A known-answer self-test produced the wrong output, meaning the
+    /// implementation of a generator is broken on the current target.
+    SelfTest,
+    /// Memory couldn't be locked into RAM, such as when a process would
+    /// exceed its limit on locked memory.
+    MemoryLock,
+}
+
+impl Error {
+    /// Creates an error of the given `kind`.
+    ///
+    /// Intended for implementations of [`crate::entropy::EntropySource`]
+    /// and [`crate::Generator`] outside of this crate.
+    #[inline]
+    pub const fn new(kind: ErrorKind) -> Self {
+        Self { kind, os: None }
+    }
+
+    /// Returns the category of the error.
+    #[inline]
+    pub const fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns the error code reported by the operating system, if there is one.
+    #[inline]
+    pub fn raw_os_error(&self) -> Option<i32> {
+        self.os.and_then(|err| err.raw_os_error())
+    }
+
+    /// Wraps an error from the operating system entropy source.
+    #[inline]
+    pub(crate) const fn from_os(err: getrandom::Error) -> Self {
+        Self {
+            kind: ErrorKind::Entropy,
+            os: Some(err),
+        }
+    }
+}
+
+impl From<ErrorKind> for Error {
+    #[inline]
+    fn from(kind: ErrorKind) -> Self {
+        Self::new(kind)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let msg = match self.kind {
+            ErrorKind::Unsupported => "operation is not supported",
+            ErrorKind::Entropy => "retrieving entropy failed",
+            ErrorKind::HealthTest => "noise source failed its health tests",
+            ErrorKind::InvalidSeed => "seed or state is invalid",
+            ErrorKind::InvalidParameter => "argument is out of range",
+            ErrorKind::SelfTest => "known-answer self-test failed",
+            ErrorKind::MemoryLock => "locking memory failed",
+        };
+        match self.os {
+            Some(err) => write!(f, "{msg}: {err}"),
+            None => f.write_str(msg),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
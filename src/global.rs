@@ -0,0 +1,142 @@
+use crate::rng::Generator;
+use crate::secure::SecureRng;
+#[cfg(unix)]
+use core::sync::atomic::AtomicU32;
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard, OnceLock, PoisonError};
+
+/// Set by [`mark_forked`], forcing a reseed on the next access.
+static FORKED: AtomicBool = AtomicBool::new(false);
+/// Id of the process which last seeded the global generator.
+#[cfg(unix)]
+static SEEDED_BY: AtomicU32 = AtomicU32::new(0);
+
+/// Returns a handle to a process-wide [`SecureRng`], which is lazily
+/// seeded the first time it's accessed.
+///
+/// The generator is protected by a lock, which is held until the returned handle
+/// is dropped. Keep the handle alive only as long as needed, since all other threads
+/// trying to access the generator will block until it's released.
+///
+/// Intended for code which occasionally needs secure values (e.g. session tokens)
+/// and has no convenient place to keep a generator around. Code that generates
+/// lots of values should create and reuse its own [`SecureRng`] instead.
+///
+/// On Unix, the id of the current process is checked on every access, and the
+/// generator is reseeded when it differs from the one that seeded it. This prevents
+/// the parent and child of a `fork()` from producing identical output.
+///
+/// This is the only fork-protected generator in the crate. There's deliberately no
+/// thread-local generator, and instances of [`SecureRng`] (or any other generator)
+/// owned by the user are never reseeded after a fork, so the parent and child will
+/// produce identical output from them. Such instances have to be recreated in the
+/// child, or created after forking in the first place.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+/// use ya_rand::encoding::Base64Url;
+///
+/// let token = secure_global().text::<Base64Url>(0);
+/// assert!(!token.is_empty());
+/// ```
+pub fn secure_global() -> MutexGuard<'static, SecureRng> {
+    static GLOBAL: OnceLock<Mutex<SecureRng>> = OnceLock::new();
+    let mut guard = GLOBAL
+        .get_or_init(|| {
+            #[cfg(unix)]
+            SEEDED_BY.store(std::process::id(), Ordering::Relaxed);
+            Mutex::new(SecureRng::new())
+        })
+        .lock()
+        // A panic while the lock was held can't leave the generator in an invalid state.
+        .unwrap_or_else(PoisonError::into_inner);
+    if needs_reseed() {
+        *guard = SecureRng::new();
+    }
+    guard
+}
+
+/// Forces the generator returned by [`secure_global`] to be reseeded the next time it's accessed.
+///
+/// Has no effect on any other generator, including user-owned [`SecureRng`] instances.
+///
+/// Automatic fork detection is only available on Unix, and can't see through
+/// process snapshotting mechanisms it doesn't know about. This should be called
+/// in the child (or restored) process in any such custom setup, before any secure
+/// values are generated.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// let before = secure_global().u64();
+/// mark_forked();
+/// let after = secure_global().u64();
+/// assert!(before != after);
+/// ```
+#[inline]
+pub fn mark_forked() {
+    FORKED.store(true, Ordering::Relaxed);
+}
+
+/// Reports the current virtual machine generation ID, forcing the generator returned
+/// by [`secure_global`] to be reseeded if it differs from the previously reported one.
+///
+/// Hypervisors change the generation ID (Linux `vmgenid`, Windows VM Generation ID)
+/// whenever a VM is restored from a snapshot or cloned, which would otherwise replay
+/// the state of the generator. Returns `true` if a reseed was triggered.
+///
+/// Detection is manual only: this crate never reads the generation ID itself, and
+/// [`secure_global`] doesn't check it on access. Linux doesn't expose the ID to userspace
+/// at all (only a `NEW_VMGENID` uevent is emitted on changes), and Windows only through
+/// the `VmGenerationCounter` device, so the ID or an equivalent counter has to be obtained
+/// by the application or a guest agent and passed here, ideally whenever the service
+/// resumes.
+///
+/// Note that the Linux kernel reseeds its own generator on a generation change, so
+/// [`crate::entropy::OsEntropy`] is unaffected by snapshot restores.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// // The first report is just recorded.
+/// observe_vm_generation(0x1234);
+/// assert!(!observe_vm_generation(0x1234));
+/// // The VM was restored from a snapshot.
+/// assert!(observe_vm_generation(0x5678));
+/// ```
+pub fn observe_vm_generation(id: u128) -> bool {
+    static GENERATION: Mutex<Option<u128>> = Mutex::new(None);
+    let mut prev = GENERATION.lock().unwrap_or_else(PoisonError::into_inner);
+    let changed = prev.is_some_and(|prev| prev != id);
+    *prev = Some(id);
+    if changed {
+        mark_forked();
+    }
+    changed
+}
+
+/// Returns `true` if the global generator has to be reseeded.
+///
+/// Only called while the lock is held, so there are no races with other accesses.
+#[inline]
+fn needs_reseed() -> bool {
+    let forked = FORKED.swap(false, Ordering::Relaxed);
+    #[cfg(unix)]
+    let forked = pid_changed() | forked;
+    forked
+}
+
+/// Returns `true` if the current process didn't seed the global generator,
+/// recording it as the new owner.
+#[cfg(unix)]
+#[inline]
+fn pid_changed() -> bool {
+    let pid = std::process::id();
+    SEEDED_BY.swap(pid, Ordering::Relaxed) != pid
+}
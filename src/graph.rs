@@ -0,0 +1,83 @@
+//! Random graphs, returned as adjacency lists.
+//!
+//! Every graph is undirected and has the vertices `0..n` without self-loops. The
+//! neighbors of every vertex are sorted in ascending order, and every edge appears
+//! in the lists of both of its vertices.
+//!
+//! # Examples
+//!
+//! ```
+//! use ya_rand::*;
+//!
+//! let mut rng = new_rng();
+//! let graph = graph::gnp(&mut rng, 100, 0.1);
+//! assert!(graph.len() == 100);
+//! for (v, neighbors) in graph.iter().enumerate() {
+//!     assert!(!neighbors.contains(&v));
+//!     assert!(neighbors.iter().all(|&u| graph[u].contains(&v)));
+//! }
+//! ```
+
+use crate::rng::Generator;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Returns an Erdős–Rényi G(n, p) random graph.
+///
+/// Every one of the n(n - 1) / 2 possible edges is included independently with a
+/// probability of `p`, using [`Generator::fill_bool`] for each row of the adjacency
+/// matrix.
+pub fn gnp(rng: &mut impl Generator, n: usize, p: f64) -> Vec<Vec<usize>> {
+    let mut graph = vec![Vec::new(); n];
+    let mut row = vec![false; n];
+    for i in 0..n {
+        let row = &mut row[i + 1..];
+        rng.fill_bool(row, p);
+        for (j, _) in row.iter().enumerate().filter(|(_, edge)| **edge) {
+            graph[i].push(i + 1 + j);
+            graph[i + 1 + j].push(i);
+        }
+    }
+    graph
+}
+
+/// Returns an Erdős–Rényi G(n, m) random graph.
+///
+/// The graph has exactly `m` edges, chosen uniformly among all sets of `m` of the
+/// n(n - 1) / 2 possible edges using [`Generator::combination`].
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// let mut rng = new_rng();
+/// let graph = graph::gnm(&mut rng, 100, 250);
+/// let degree_sum = graph.iter().map(|neighbors| neighbors.len()).sum::<usize>();
+/// assert!(degree_sum == 2 * 250);
+/// ```
+///
+/// # Panics
+///
+/// If `m` is greater than n(n - 1) / 2.
+pub fn gnm(rng: &mut impl Generator, n: usize, m: usize) -> Vec<Vec<usize>> {
+    let n_edges = (n as u64 * n.saturating_sub(1) as u64) / 2;
+    assert!(
+        m as u64 <= n_edges,
+        "`m` must not be greater than the amount of possible edges"
+    );
+    let mut graph = vec![Vec::new(); n];
+    // Edges are numbered row by row through the upper triangle of the adjacency matrix,
+    // and the combination is sorted, so a single pass maps them back to vertices.
+    let (mut i, mut row_start) = (0, 0);
+    for edge in rng.combination(n_edges, m) {
+        while edge >= row_start + (n - 1 - i) as u64 {
+            row_start += (n - 1 - i) as u64;
+            i += 1;
+        }
+        let j = i + 1 + (edge - row_start) as usize;
+        graph[i].push(j);
+        graph[j].push(i);
+    }
+    graph
+}
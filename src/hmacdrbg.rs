@@ -0,0 +1,211 @@
+use crate::drbg::{FrontEnd, MAX_ENTROPY_LEN, Mechanism};
+use crate::entropy::{EntropySource, OsEntropy};
+use crate::error::Error;
+use crate::rng::{Generator, SecureGenerator};
+use core::fmt;
+use hmac::digest::{Digest, Output, OutputSizeUser, core_api::BlockSizeUser, typenum::Unsigned};
+use hmac::{Mac, SimpleHmac};
+use sha2::Sha256;
+
+// See table 2 of NIST SP 800-90A.
+const RESEED_INTERVAL: u64 = 1 << 48;
+const MAX_REQUEST_LEN: usize = (1 << 19) / 8;
+
+/// A cryptographically secure random number generator, implementing
+/// the HMAC_DRBG mechanism from [NIST SP 800-90A].
+///
+/// The hash function used by the mechanism is SHA-256 by default, but any
+/// hash function implementing the traits from the [`digest`](hmac::digest) crate
+/// with an output of at most 64 bytes (e.g. SHA-512) can be used in its place.
+/// Using a hash function with a longer output fails to compile.
+///
+/// The NIST-specified instantiate, reseed, and generate functions are available
+/// through [`HmacDrbg::instantiate`], [`HmacDrbg::reseed`], and [`HmacDrbg::generate`].
+/// When used through the [`Generator`] and [`SecureGenerator`] traits, output is
+/// buffered and the generator is automatically reseeded using entropy from the
+/// operating system once the reseed interval (2<sup>48</sup> requests) is reached.
+///
+/// Unless you're required to use a NIST-approved DRBG, prefer using [`crate::SecureRng`].
+///
+/// [NIST SP 800-90A]: https://csrc.nist.gov/pubs/sp/800/90/a/r1/final
+pub struct HmacDrbg<D: Digest + BlockSizeUser = Sha256> {
+    front: FrontEnd<HmacState<D>>,
+}
+
+impl<D: Digest + BlockSizeUser> fmt::Debug for HmacDrbg<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("all `HmacDrbg` fields are private")
+    }
+}
+
+impl<D: Digest + BlockSizeUser> HmacDrbg<D> {
+    /// Shortest length of the entropy input used during instantiation and
+    /// reseeding, in bytes. This is the output length of `D`.
+    pub const MIN_ENTROPY_LEN: usize = D::OutputSize::USIZE;
+
+    /// Largest amount of bytes which can be requested from a single call to
+    /// [`HmacDrbg::generate`].
+    pub const MAX_REQUEST_LEN: usize = MAX_REQUEST_LEN;
+
+    /// Instantiates the generator using the provided `entropy` input, `nonce`,
+    /// and optional `personalization` string.
+    ///
+    /// # Panics
+    ///
+    /// If `entropy` is shorter than [`HmacDrbg::MIN_ENTROPY_LEN`].
+    pub fn instantiate(entropy: &[u8], nonce: &[u8], personalization: &[u8]) -> Self {
+        assert_entropy_len::<D>(entropy);
+        let mut state = HmacState::<D> {
+            k: Output::<D>::default(),
+            v: Output::<D>::default(),
+            reseed_counter: 1,
+        };
+        state.v.fill(0x01);
+        state.update(&[entropy, nonce, personalization]);
+        Self {
+            front: FrontEnd::new(state),
+        }
+    }
+
+    /// Reseeds the generator using the provided `entropy` input and
+    /// optional `additional_input`. Any buffered output is discarded.
+    ///
+    /// # Panics
+    ///
+    /// If `entropy` is shorter than [`HmacDrbg::MIN_ENTROPY_LEN`].
+    #[inline]
+    pub fn reseed(&mut self, entropy: &[u8], additional_input: &[u8]) {
+        assert_entropy_len::<D>(entropy);
+        self.front.reseed(entropy, additional_input);
+    }
+
+    /// Fills `dst` using the generate function of the DRBG, with optional
+    /// `additional_input`. This bypasses any buffered output.
+    ///
+    /// If the reseed interval has been reached, the generator will first be
+    /// reseeded using entropy from the operating system.
+    ///
+    /// # Panics
+    ///
+    /// If `dst` is longer than [`HmacDrbg::MAX_REQUEST_LEN`], or if the operating
+    /// system fails to provide entropy when a reseed is required.
+    #[inline]
+    pub fn generate(&mut self, dst: &mut [u8], additional_input: &[u8]) {
+        self.front.generate(dst, additional_input);
+    }
+
+    /// Fills `dst` using the generate function of the DRBG, with optional
+    /// `additional_input`. This bypasses any buffered output.
+    ///
+    /// Unlike [`HmacDrbg::generate`], failing to retrieve entropy from the operating
+    /// system when a reseed is required returns an error, and leaves `dst` untouched.
+    ///
+    /// # Panics
+    ///
+    /// If `dst` is longer than [`HmacDrbg::MAX_REQUEST_LEN`].
+    #[inline]
+    pub fn try_generate(&mut self, dst: &mut [u8], additional_input: &[u8]) -> Result<(), Error> {
+        self.front.try_generate(dst, additional_input)
+    }
+}
+
+impl<D: Digest + BlockSizeUser> SecureGenerator for HmacDrbg<D> {
+    #[inline]
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.front.fill_bytes(dst);
+    }
+}
+
+impl<D: Digest + BlockSizeUser> Generator for HmacDrbg<D> {
+    #[inline]
+    fn try_new() -> Result<Self, Error> {
+        Self::try_new_from(&mut OsEntropy)
+    }
+
+    #[inline]
+    fn try_new_from<S: EntropySource + ?Sized>(source: &mut S) -> Result<Self, Error> {
+        // Output length of the largest supported hash function,
+        // plus half of that for the nonce.
+        let mut seed = [0; MAX_ENTROPY_LEN + MAX_ENTROPY_LEN / 2];
+        let (entropy, nonce) = seed.split_at_mut(Self::MIN_ENTROPY_LEN);
+        let nonce = &mut nonce[..Self::MIN_ENTROPY_LEN / 2];
+        source.fill(entropy)?;
+        source.fill(nonce)?;
+        Ok(Self::instantiate(entropy, nonce, &[]))
+    }
+
+    #[cfg_attr(feature = "inline", inline)]
+    fn u64(&mut self) -> u64 {
+        self.front.u64()
+    }
+}
+
+/// Internal state of the HMAC_DRBG mechanism.
+struct HmacState<D: Digest + BlockSizeUser> {
+    k: Output<D>,
+    v: Output<D>,
+    reseed_counter: u64,
+}
+
+impl<D: Digest + BlockSizeUser> HmacState<D> {
+    /// Computes HMAC(K, V || `separator` || `data`), omitting the separator when `None`.
+    #[inline]
+    fn hmac(&self, separator: Option<u8>, data: &[&[u8]]) -> Output<D> {
+        // The unwrap gets optimized out since HMAC accepts keys of any length.
+        let mut mac = <SimpleHmac<D> as Mac>::new_from_slice(&self.k).unwrap();
+        mac.update(&self.v);
+        if let Some(separator) = separator {
+            mac.update(&[separator]);
+        }
+        for chunk in data {
+            mac.update(chunk);
+        }
+        mac.finalize().into_bytes()
+    }
+
+    /// The HMAC_DRBG_Update function, as specified in section 10.1.2.2,
+    /// where `provided_data` is the concatenation of all its elements.
+    fn update(&mut self, provided_data: &[&[u8]]) {
+        self.k = self.hmac(Some(0x00), provided_data);
+        self.v = self.hmac(None, &[]);
+        if provided_data.iter().any(|v| !v.is_empty()) {
+            self.k = self.hmac(Some(0x01), provided_data);
+            self.v = self.hmac(None, &[]);
+        }
+    }
+}
+
+impl<D: Digest + BlockSizeUser> Mechanism for HmacState<D> {
+    const ENTROPY_LEN: usize = D::OutputSize::USIZE;
+    const MAX_REQUEST_LEN: usize = MAX_REQUEST_LEN;
+
+    fn generate(&mut self, dst: &mut [u8], additional_input: &[u8]) -> bool {
+        if self.reseed_counter > RESEED_INTERVAL {
+            return false;
+        }
+        if !additional_input.is_empty() {
+            self.update(&[additional_input]);
+        }
+        for chunk in dst.chunks_mut(D::OutputSize::USIZE) {
+            self.v = self.hmac(None, &[]);
+            chunk.copy_from_slice(&self.v[..chunk.len()]);
+        }
+        self.update(&[additional_input]);
+        self.reseed_counter += 1;
+        true
+    }
+
+    fn reseed(&mut self, entropy: &[u8], additional_input: &[u8]) {
+        self.update(&[entropy, additional_input]);
+        self.reseed_counter = 1;
+    }
+}
+
+#[inline]
+fn assert_entropy_len<D: OutputSizeUser>(entropy: &[u8]) {
+    assert!(
+        entropy.len() >= D::output_size(),
+        "HMAC_DRBG entropy input must be at least {} bytes long",
+        D::output_size()
+    );
+}
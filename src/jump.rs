@@ -0,0 +1,89 @@
+//! Jumping ahead in the sequence of a generator with a linear state transition,
+//! such as the xoshiro family.
+//!
+//! The state transition is a linear map over GF(2), so advancing the state by `n`
+//! steps is the same as evaluating the polynomial x<sup>n</sup> mod p(x) at the
+//! transition, where p(x) is its characteristic polynomial. This is how the reference
+//! `jump` and `long_jump` functions work, except that their polynomials were
+//! precomputed for a single distance each.
+//!
+//! The characteristic polynomial of every generator is stored next to it, so only
+//! x<sup>2<sup>k</sup></sup> mod p(x) has to be computed here, using `k` squarings.
+
+/// Amount of words in a polynomial, enough for the product of two
+/// polynomials of degree below 512.
+const POLY_LEN: usize = 17;
+
+type Poly = [u64; POLY_LEN];
+
+#[inline]
+fn bit(poly: &[u64], i: usize) -> bool {
+    (poly[i / 64] >> (i % 64)) & 1 == 1
+}
+
+#[inline]
+fn flip(poly: &mut [u64], i: usize) {
+    poly[i / 64] ^= 1 << (i % 64);
+}
+
+/// XORs `src` multiplied by x<sup>`shift`</sup> into `dst`.
+fn xor_shifted(dst: &mut Poly, src: &Poly, shift: usize) {
+    let (words, bits) = (shift / 64, shift % 64);
+    for i in (words..POLY_LEN).rev() {
+        let mut val = src[i - words] << bits;
+        if bits != 0 && i > words {
+            val |= src[i - words - 1] >> (64 - bits);
+        }
+        dst[i] ^= val;
+    }
+}
+
+/// Squares `r` modulo `p`, which has degree `degree`.
+fn square_mod(r: &Poly, p: &Poly, degree: usize) -> Poly {
+    let mut sq = [0; POLY_LEN];
+    for i in 0..degree {
+        if bit(r, i) {
+            flip(&mut sq, 2 * i);
+        }
+    }
+    for i in (degree..2 * degree).rev() {
+        if bit(&sq, i) {
+            xor_shifted(&mut sq, p, i - degree);
+        }
+    }
+    sq
+}
+
+/// Returns x<sup>2<sup>`k`</sup></sup> mod p(x), the jump polynomial for advancing a
+/// `64 * N` bit state by 2<sup>`k`</sup> steps.
+///
+/// `characteristic` holds the coefficients of p(x) below its leading x<sup>64N</sup>
+/// term, which must be primitive.
+pub fn pow2_poly<const N: usize>(k: u32, characteristic: &[u64; N]) -> [u64; N] {
+    let degree = 64 * N;
+    let mut p = [0; POLY_LEN];
+    p[..N].copy_from_slice(characteristic);
+    flip(&mut p, degree);
+    // Since p(x) is primitive, x^(2^degree) = x mod p(x).
+    let mut r = [0; POLY_LEN];
+    flip(&mut r, 1);
+    for _ in 0..k as usize % degree {
+        r = square_mod(&r, &p, degree);
+    }
+    let mut ret = [0; N];
+    ret.copy_from_slice(&r[..N]);
+    ret
+}
+
+/// Advances `state` by the amount of applications of `step` encoded in the jump
+/// polynomial `poly`.
+pub fn jump<const N: usize>(state: &mut [u64; N], poly: &[u64; N], step: impl Fn(&mut [u64; N])) {
+    let mut acc = [0; N];
+    for i in 0..64 * N {
+        if bit(poly, i) {
+            acc.iter_mut().zip(state.iter()).for_each(|(a, s)| *a ^= s);
+        }
+        step(state);
+    }
+    *state = acc;
+}
@@ -0,0 +1,84 @@
+use crate::error::{Error, ErrorKind};
+use crate::rng::Generator;
+use core::fmt;
+
+/// A wrapper which partitions the output of a generator into interleaved streams.
+///
+/// Stream `stream` out of `streams` yields values `stream`, `stream + streams`,
+/// `stream + 2 * streams`, and so on, from the output of the wrapped generator. When every
+/// worker of a parallel computation wraps an identically seeded generator using its own
+/// stream index, the workers never share any values, and the results are reproducible
+/// regardless of how the work is scheduled.
+///
+/// Unlike [`Xoshiro256pp::jump_by_pow2`](crate::Xoshiro256pp::jump_by_pow2), this works
+/// with any generator, including the Romu family. The cost is that every value requires
+/// `streams` values from the wrapped generator, so it's best suited to small numbers of
+/// streams.
+///
+/// [`Generator::try_new`] fails with [`ErrorKind::Unsupported`], since the streams have
+/// to share a seed.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// let mut base = RomuTrio::new_with_seed(69);
+/// let expected = base.array_u64::<6>();
+/// let mut even = Leapfrog::new_with_rng(RomuTrio::new_with_seed(69), 0, 2);
+/// let mut odd = Leapfrog::new_with_rng(RomuTrio::new_with_seed(69), 1, 2);
+/// assert!(even.array_u64::<3>() == [expected[0], expected[2], expected[4]]);
+/// assert!(odd.array_u64::<3>() == [expected[1], expected[3], expected[5]]);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Leapfrog<G> {
+    rng: G,
+    streams: usize,
+}
+
+impl<G> fmt::Debug for Leapfrog<G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("all `Leapfrog` fields are private")
+    }
+}
+
+impl<G: Generator> Leapfrog<G> {
+    /// Wraps `rng`, yielding stream number `stream` out of `streams`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stream` isn't less than `streams`.
+    #[inline]
+    pub fn new_with_rng(mut rng: G, stream: usize, streams: usize) -> Self {
+        assert!(stream < streams, "`stream` must be less than `streams`");
+        (0..stream).for_each(|_| _ = rng.u64());
+        Self { rng, streams }
+    }
+
+    /// Returns a reference to the wrapped generator.
+    #[inline]
+    pub const fn inner(&self) -> &G {
+        &self.rng
+    }
+
+    /// Returns the wrapped generator.
+    #[inline]
+    pub fn into_inner(self) -> G {
+        self.rng
+    }
+}
+
+impl<G: Generator> Generator for Leapfrog<G> {
+    #[inline]
+    fn try_new() -> Result<Self, Error> {
+        Err(Error::new(ErrorKind::Unsupported))
+    }
+
+    #[inline]
+    fn u64(&mut self) -> u64 {
+        let ret = self.rng.u64();
+        // Skip over the values belonging to the other streams.
+        (1..self.streams).for_each(|_| _ = self.rng.u64());
+        ret
+    }
+}
@@ -42,7 +42,9 @@ suitable for use in secure contexts.
 "How do I access the thread-local RNG?" There isn't one, and unless Rust improves the performance and
 ergonomics of the TLS implementation, there probably won't ever be. Create a local instance when and
 where you need one and use it while you need it. If you need an RNG to stick around for a while, passing
-it between functions or storing it in structs is a perfectly valid solution.
+it between functions or storing it in structs is a perfectly valid solution. The one exception is
+`secure_global` (requires the **std** and **secure** features), a lock-protected [`SecureRng`] for
+code which only occasionally needs a secure value.
 
 ```
 use ya_rand::*;
@@ -92,15 +94,100 @@ assert!(s.len() == Base16::MIN_LEN);
 ## Features
 
 * **std** -
-    Enabled by default, but can be disabled for use in `no_std` environments. Enables normal/exponential
-    distributions, error type conversions for getrandom, and the **alloc** feature.
+    Enabled by default, but can be disabled for use in `no_std` environments. Enables
+    normal/exponential/Gumbel distributions, [`MultivariateNormal`], [`rotation_matrix_2d`],
+    [`rotation_matrix_3d`], [`random_orthogonal`], Poisson-disk sampling, weighted sampling,
+    random `SystemTime` values, randomized delays and [`Backoff`], `secure_global`, `mark_forked`,
+    `observe_vm_generation`, and `SecureRngPrefetch` (when **secure** is also enabled), the
+    [`sampling`] and [`stats`] modules, and the **alloc** feature.
 * **alloc** -
     Enabled by default. Normally enabled through **std**, but can be enabled on it's own for use in
     `no_std` environments which provide allocation primitives. Enables random generation of secure
-    `String` values when using [`SecureRng`].
+    `String` values, [`SecureToken`] and [`SecureBytes`] values when using [`SecureRng`], as well as
+    arbitrary UTF-8 `String` values using [`Generator::utf8_string`], the [`quasi`] module, and
+    [`Generator`] implementations for boxed [`DynGenerator`] trait objects.
 * **secure** -
     Enabled by default. Provides [`SecureRng`], which implements [SecureGenerator]. The backing generator
-    is ChaCha with 8 rounds and a 64-bit counter.
+    is ChaCha with 8 rounds and a 64-bit counter, which can be periodically reseeded according to a
+    [`ReseedPolicy`].
+* **blake3** -
+    Provides [`Blake3Rng`], an alternative to [`SecureRng`] which is backed by the extendable
+    output function of BLAKE3. Also enables the **secure** feature.
+* **bytemuck** -
+    Provides [`SecureGeneratorExt::fill_pod`], a safe alternative to
+    [`SecureGeneratorExt::fill_raw`] for any type implementing [`bytemuck::Pod`].
+* **proptest** -
+    Extends the [`testing`] module, allowing the generators in this crate to be used
+    with the [`proptest`] framework. Also enables the **std** feature.
+* **ctr-drbg** -
+    Provides [`CtrDrbg`], an implementation of the AES-256 CTR_DRBG mechanism from NIST SP 800-90A,
+    for users who are required to use a NIST-approved generator. Also enables the **secure** feature.
+* **heapless** -
+    Provides [`SecureGeneratorExt::text_heapless`], which generates random `heapless::String`
+    values for use in `no_std` environments without allocation primitives.
+* **rand06-compat** / **rand09-compat** -
+    Implements `RngCore` from version 0.6 and 0.9 of `rand_core` (respectively) for all provided
+    generators, as well as `CryptoRng` for [`SecureRng`]. This allows generators from this crate to
+    be passed directly to libraries built on either version of `rand`. Also provides [`RandAdapter`],
+    which does the same for a mutable reference to any [`Generator`], and `FromRandCore` /
+    `FromRandCore06`, which go the other way and implement [`Generator`] for any `RngCore`.
+* **shiro-xoshiro512pp** / **shiro-romutrio** / **shiro-romuquad** -
+    Changes the generator used by [`ShiroRng`] (and therefore [`new_rng`]) from [`Xoshiro256pp`]
+    to the named generator. These features are mutually exclusive, and are intended to be enabled
+    by binary crates which want to switch every call site at once.
+* **hmac-drbg** -
+    Provides [`HmacDrbg`], an implementation of the HMAC_DRBG mechanism from NIST SP 800-90A,
+    for users who are required to use a NIST-approved generator. Also enables the **secure** feature.
+* **hkdf** -
+    Provides [`SecureRng::derive`], which seeds a generator from a master key and a context label
+    using HKDF-SHA256. Also enables the **secure** feature.
+* **self-test** -
+    Runs [`SecureRng::self_test`] the first time a [`SecureRng`] is created, after which creating
+    one fails with [`ErrorKind::SelfTest`] if the test failed. Constructors which can't return an
+    error, such as [`new_rng_secure`], panic instead. Also enables the **secure** feature.
+* **regex** -
+    Provides [`RegexStrings`], which generates random strings matching a regular expression.
+    Useful for creating test data. Also enables the **alloc** feature.
+* **tracing** -
+    Emits [`tracing`] events when entropy is requested from the operating system, when an entropy
+    source fails or is disabled by its health tests, and when a [`SecureRng`] is created or
+    reseeded. All events use the `ya_rand` target, and never contain any generated values.
+* **time** -
+    Provides [`Generator::offset_date_time`], which generates random `time::OffsetDateTime` values
+    within a range. Also enables the **std** feature.
+* **num-bigint** -
+    Provides [`Generator::biguint_bits`] and [`Generator::biguint_below`], which generate
+    random `num_bigint::BigUint` values. When combined with the **secure** feature, also provides
+    [`SecureGeneratorExt::prime_bits`] for generating probable primes. Also enables the **alloc**
+    feature.
+* **mlock** -
+    Provides `LockedSecureRng` on unix targets, a [`SecureRng`] whose state is kept in memory which
+    is locked into RAM using `mlock` and excluded from core dumps where supported, so keystream
+    material can't be swapped to disk. Also enables the **secure** feature.
+* **slug** -
+    Provides [`Generator::slug`] and [`Generator::slug_with`], which generate human friendly names
+    such as `brave-otter-7291` from a small embedded word list. Also enables the **alloc** feature.
+* **bitcast-floats** -
+    Makes [`Generator::f64`] and [`Generator::f32`] construct their values by writing random bits into
+    the mantissa of a float in [1.0, 2.0) and subtracting 1.0, avoiding an integer to float conversion.
+    Faster on some targets, but provides one less bit of resolution (52 bits for `f64`, 23 for `f32`),
+    so the values produced from a given seed differ from the default conversion.
+* **rayon** -
+    Provides [`SecureRng::fill_bytes_par`], which fills large buffers using multiple threads while
+    producing the same output as [`SecureRng::fill_bytes`]. Also enables the **secure** and
+    **std** features.
+* **deterministic-tests** -
+    Makes [`new_rng`] and [`try_new_rng`] return generators derived from a fixed seed instead of
+    operating system entropy, so entire test suites can be reproduced without touching every call
+    site. The seed is read from the `YA_RAND_SEED` environment variable (when **std** is enabled),
+    falling back to `DETERMINISTIC_SEED`, and every call returns the next stream of
+    [`SeedableGenerator::new_stream`] for it. Results are only reproducible when the calls happen
+    in the same order, so tests which create generators from multiple threads (including the
+    default parallel test runner) should be run with `--test-threads=1`. The secure generators
+    and [`ShiroRng::new`] are never affected. Intended to be enabled as a dev-dependency feature.
+* **defmt** -
+    Implements `defmt::Format` for [`SecureRng`] and the non-cryptographic generators, for logging on
+    embedded targets. Just like their `Debug` implementations, the internal state is never printed.
 * **inline** -
     Marks all [`Generator::u64`] implementations with `#[inline]`. Should generally increase
     runtime performance at the cost of binary size and compile time.
@@ -180,39 +267,179 @@ rust can trivially remove the failure branch when compiling binaries for those s
 #![deny(missing_docs)]
 #![no_std]
 
-#[cfg(all(feature = "alloc", feature = "secure"))]
+#[cfg(feature = "alloc")]
 extern crate alloc;
 extern crate std;
 
-#[cfg(all(feature = "alloc", feature = "secure"))]
+/// Emits a `tracing` event when the **tracing** feature is enabled, and expands to nothing otherwise.
+macro_rules! event {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!(target: "ya_rand", $($arg)+);
+    };
+}
+
+#[cfg(feature = "std")]
+mod backoff;
+#[cfg(feature = "blake3")]
+mod blake3rng;
+mod caching;
+pub mod color;
+#[cfg(any(feature = "rand06-compat", feature = "rand09-compat"))]
+mod compat;
+#[cfg(feature = "ctr-drbg")]
+mod ctrdrbg;
+#[cfg(any(feature = "ctr-drbg", feature = "hmac-drbg"))]
+mod drbg;
+#[cfg(feature = "secure")]
 pub mod encoding;
+pub mod entropy;
+mod error;
+#[cfg(all(feature = "std", feature = "secure"))]
+mod global;
+#[cfg(feature = "alloc")]
+pub mod graph;
+#[cfg(feature = "hmac-drbg")]
+mod hmacdrbg;
+mod jump;
+mod leapfrog;
+#[cfg(all(feature = "mlock", unix))]
+mod locked;
+mod mnemonic;
+#[cfg(feature = "std")]
+mod multivariate;
+pub mod net;
+mod permutation;
+#[cfg(feature = "std")]
+mod poisson;
+#[cfg(all(feature = "secure", feature = "std"))]
+mod prefetch;
+#[cfg(feature = "alloc")]
+pub mod quasi;
+#[cfg(feature = "regex")]
+mod regex;
+#[cfg(feature = "std")]
+mod reservoir;
 mod rng;
 mod romuquad;
 mod romutrio;
+#[cfg(feature = "std")]
+pub mod sampling;
 #[cfg(feature = "secure")]
 mod secure;
+mod shuffle;
+#[cfg(all(
+    feature = "secure",
+    any(feature = "alloc", feature = "heapless"),
+    target_arch = "x86_64",
+    target_feature = "ssse3"
+))]
+mod simd;
+#[cfg(feature = "slug")]
+mod slug;
+#[cfg(feature = "std")]
+pub mod stats;
+pub mod testing;
+#[cfg(all(feature = "alloc", feature = "secure"))]
+mod token;
 mod util;
+mod variant;
 mod xoshiro256pp;
 mod xoshiro512pp;
 
+#[cfg(feature = "std")]
+pub use backoff::{Backoff, Jitter};
+#[cfg(feature = "blake3")]
+pub use blake3rng::Blake3Rng;
+pub use caching::CachingRng;
+#[cfg(feature = "rand09-compat")]
+pub use compat::FromRandCore;
+#[cfg(feature = "rand06-compat")]
+pub use compat::FromRandCore06;
+#[cfg(any(feature = "rand06-compat", feature = "rand09-compat"))]
+pub use compat::RandAdapter;
+#[cfg(feature = "ctr-drbg")]
+pub use ctrdrbg::CtrDrbg;
+pub use error::{Error, ErrorKind};
+#[cfg(all(feature = "std", feature = "secure"))]
+pub use global::{mark_forked, observe_vm_generation, secure_global};
+#[cfg(feature = "hmac-drbg")]
+pub use hmacdrbg::HmacDrbg;
+pub use leapfrog::Leapfrog;
+#[cfg(all(feature = "mlock", unix))]
+pub use locked::LockedSecureRng;
+pub use mnemonic::Mnemonic;
+#[cfg(feature = "std")]
+pub use multivariate::{
+    MultivariateNormal, random_orthogonal, rotation_matrix_2d, rotation_matrix_3d,
+};
+pub use permutation::LazyPermutation;
+#[cfg(feature = "alloc")]
+pub use permutation::latin_square_biased;
+#[cfg(all(feature = "secure", feature = "std"))]
+pub use prefetch::SecureRngPrefetch;
+#[cfg(feature = "regex")]
+pub use regex::RegexStrings;
+#[cfg(feature = "std")]
+pub use reservoir::WeightedReservoir;
+pub use rng::{DynGenerator, Generator, GeneratorIter, SeedableGenerator};
 #[cfg(feature = "secure")]
-pub use rng::SecureGenerator;
-pub use rng::{Generator, SeedableGenerator};
+pub use rng::{SecureGenerator, SecureGeneratorExt};
 pub use romuquad::RomuQuad;
 pub use romutrio::RomuTrio;
 #[cfg(feature = "secure")]
-pub use secure::SecureRng;
+pub use secure::{ReseedPolicy, SecureRng};
+pub use shuffle::Swappable;
+#[cfg(all(feature = "alloc", feature = "secure"))]
+pub use token::{SecureBytes, SecureToken};
+pub use variant::RandomVariant;
 pub use xoshiro256pp::Xoshiro256pp;
 pub use xoshiro512pp::Xoshiro512pp;
 
+#[cfg(any(
+    all(feature = "shiro-xoshiro512pp", feature = "shiro-romutrio"),
+    all(feature = "shiro-xoshiro512pp", feature = "shiro-romuquad"),
+    all(feature = "shiro-romutrio", feature = "shiro-romuquad"),
+))]
+compile_error!("only one of the `shiro-*` features can be enabled at a time");
+
 /// The recommended generator for all non-cryptographic purposes.
+///
+/// This is [`Xoshiro256pp`], unless one of the `shiro-*` features is enabled.
+#[cfg(not(any(
+    feature = "shiro-xoshiro512pp",
+    feature = "shiro-romutrio",
+    feature = "shiro-romuquad",
+)))]
 pub type ShiroRng = Xoshiro256pp;
 
+/// The recommended generator for all non-cryptographic purposes.
+///
+/// This is [`Xoshiro512pp`], since the **shiro-xoshiro512pp** feature is enabled.
+#[cfg(feature = "shiro-xoshiro512pp")]
+pub type ShiroRng = Xoshiro512pp;
+
+/// The recommended generator for all non-cryptographic purposes.
+///
+/// This is [`RomuTrio`], since the **shiro-romutrio** feature is enabled.
+#[cfg(feature = "shiro-romutrio")]
+pub type ShiroRng = RomuTrio;
+
+/// The recommended generator for all non-cryptographic purposes.
+///
+/// This is [`RomuQuad`], since the **shiro-romuquad** feature is enabled.
+#[cfg(feature = "shiro-romuquad")]
+pub type ShiroRng = RomuQuad;
+
 /// The recommended way to create new PRNG instances.
 ///
-/// Identical to calling [`ShiroRng::new`] or [`Xoshiro256pp::new`].
+/// Identical to calling [`ShiroRng::new`], unless the **deterministic-tests**
+/// feature is enabled.
 #[inline]
 pub fn new_rng() -> ShiroRng {
+    #[cfg(feature = "deterministic-tests")]
+    return deterministic_rng();
+    #[cfg(not(feature = "deterministic-tests"))]
     ShiroRng::new()
 }
 
@@ -225,6 +452,62 @@ pub fn new_rng_secure() -> SecureRng {
     SecureRng::new()
 }
 
+/// The recommended way to create new PRNG instances, without panicking on failure.
+///
+/// Identical to calling [`ShiroRng::try_new`], unless the **deterministic-tests**
+/// feature is enabled, in which case this never fails.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// fn roll() -> Result<u64, Error> {
+///     let mut rng = try_new_rng()?;
+///     Ok(rng.bound(6) + 1)
+/// }
+/// assert!((1..=6).contains(&roll().unwrap()));
+/// ```
+#[inline]
+pub fn try_new_rng() -> Result<ShiroRng, Error> {
+    #[cfg(feature = "deterministic-tests")]
+    return Ok(deterministic_rng());
+    #[cfg(not(feature = "deterministic-tests"))]
+    ShiroRng::try_new()
+}
+
+/// Seed used by [`new_rng`] when the **deterministic-tests** feature is enabled,
+/// and the `YA_RAND_SEED` environment variable isn't set.
+#[cfg(feature = "deterministic-tests")]
+pub const DETERMINISTIC_SEED: u64 = 0x5EED_5EED_5EED_5EED;
+
+/// Returns the next of a sequence of generators, which are all derived from the
+/// seed in `YA_RAND_SEED`, or [`DETERMINISTIC_SEED`] if it isn't set.
+#[cfg(feature = "deterministic-tests")]
+fn deterministic_rng() -> ShiroRng {
+    use core::sync::atomic::{AtomicU64, Ordering};
+    static CALLS: AtomicU64 = AtomicU64::new(0);
+    #[cfg(feature = "std")]
+    let seed = match std::env::var("YA_RAND_SEED") {
+        Ok(seed) => seed
+            .parse()
+            .expect("`YA_RAND_SEED` must be a decimal `u64` value"),
+        Err(_) => DETERMINISTIC_SEED,
+    };
+    #[cfg(not(feature = "std"))]
+    let seed = DETERMINISTIC_SEED;
+    ShiroRng::new_stream(seed, CALLS.fetch_add(1, Ordering::Relaxed))
+}
+
+/// The recommended way to create new CRNG instances, without panicking on failure.
+///
+/// Identical to calling [`SecureRng::try_new`].
+#[cfg(feature = "secure")]
+#[inline]
+pub fn try_new_rng_secure() -> Result<SecureRng, Error> {
+    SecureRng::try_new()
+}
+
 #[cfg(test)]
 mod tests {
     use super::encoding::*;
@@ -282,6 +565,39 @@ mod tests {
         assert!(vals.len() == 62);
     }
 
+    #[test]
+    pub fn ascii_strings() {
+        let mut rng = new_rng();
+        let s = rng.ascii_alphanumeric_str(ITERATIONS);
+        assert!(s.len() == ITERATIONS && s.chars().all(|c| c.is_ascii_alphanumeric()));
+        let distinct = s.chars().collect::<BTreeSet<_>>();
+        assert!(distinct.len() == 62);
+        // Every character should show up roughly equally often.
+        let expected = ITERATIONS / 62;
+        for c in distinct {
+            let count = s.chars().filter(|&v| v == c).count();
+            assert!(count.abs_diff(expected) < expected / 2);
+        }
+        let s = rng.ascii_alphabetic_str(ITERATIONS);
+        assert!(s.chars().all(|c| c.is_ascii_alphabetic()));
+        assert!(s.chars().collect::<BTreeSet<_>>().len() == 52);
+        let s = rng.ascii_uppercase_str(ITERATIONS);
+        assert!(s.chars().all(|c| c.is_ascii_uppercase()));
+        let s = rng.ascii_lowercase_str(ITERATIONS);
+        assert!(s.chars().all(|c| c.is_ascii_lowercase()));
+        let s = rng.ascii_digit_str(ITERATIONS);
+        assert!(s.chars().all(|c| c.is_ascii_digit()));
+        assert!(s.chars().collect::<BTreeSet<_>>().len() == 10);
+
+        // Multiple characters are extracted from every `u64`.
+        let mut rng = testing::CountingRng::new_with_rng(new_rng());
+        rng.ascii_alphanumeric_str(1000);
+        assert!((100..120).contains(&rng.draws()));
+        let mut buf = [0; 8];
+        rng.fill_from_charset(b"x", &mut buf);
+        assert!(&buf == b"xxxxxxxx");
+    }
+
     #[test]
     pub fn ascii_digit() {
         let mut rng = new_rng();
@@ -341,6 +657,1658 @@ mod tests {
         // checks but extra testing is fine by me.
         let all_values_are_ascii = distinct_chars.iter().all(|c| c.is_ascii());
         assert!(all_values_are_ascii);
+
+        // Every character should show up roughly equally often.
+        let expected = ITERATIONS / E::CHARSET.len();
+        for c in E::CHARSET {
+            let count = s.bytes().filter(|b| b == c).count();
+            assert!(count.abs_diff(expected) < expected / 2);
+        }
+    }
+
+    #[test]
+    fn text_power_of_two_draws() {
+        let mut rng = testing::CountingRng::new_with_rng(new_rng_secure());
+        // Base64 uses 6 bits per character, so each `u64` provides 10 characters.
+        let s = rng.text::<Base64>(100);
+        assert!(s.len() == 100);
+        assert!(rng.draws() == 10 && rng.bytes() == 0);
+        rng.reset();
+        rng.text::<Base16>(33);
+        assert!(rng.draws() == 3);
+    }
+
+    #[test]
+    fn fill_uninit() {
+        let mut rng = new_rng_secure();
+        // Not a multiple of 8, so the final chunk is partial.
+        let mut data = [core::mem::MaybeUninit::uninit(); 1021];
+        let data = rng.fill_uninit(&mut data);
+        assert!(data.len() == 1021);
+        assert!(data[1013..].iter().any(|&v| v != 0));
+    }
+
+    #[test]
+    fn secure_rng_layout() {
+        // Instances never share a cache line, and the hot fields start on one.
+        assert!(align_of::<SecureRng>() == 64);
+        let rngs = [new_rng_secure(), new_rng_secure()];
+        for rng in &rngs {
+            assert!((rng as *const SecureRng).addr().is_multiple_of(64));
+        }
+        // Only the index, buffer, and ChaCha state come before the cold fields, which
+        // are 104 bytes, plus another 32 bytes for the reseed age and timestamp with `std`.
+        let hot = size_of::<usize>()
+            + size_of::<[u64; chachacha::BUF_LEN_U64]>()
+            + size_of::<chachacha::ChaCha8Djb>();
+        let cold = size_of::<u64>() * 7 + chachacha::SEED_LEN_U8;
+        #[cfg(feature = "std")]
+        let cold =
+            cold + size_of::<Option<std::time::Duration>>() + size_of::<std::time::Instant>();
+        assert!(size_of::<secure::Cold>() == cold);
+        let cold_offset = core::mem::offset_of!(SecureRng, cold);
+        let padding = align_of::<chachacha::ChaCha8Djb>() + align_of::<secure::Cold>();
+        assert!(cold_offset >= hot && cold_offset < hot + padding);
+        assert!(size_of::<SecureRng>() == (cold_offset + cold).next_multiple_of(64));
+    }
+
+    #[test]
+    fn secure_fill_bytes_aligned() {
+        let mut rng = new_rng_secure();
+        let mut aligned = [0_u64; 64];
+        // SAFETY: `u64` is valid as nothing more than a collection of bytes.
+        rng.fill_bytes(unsafe { util::as_raw_bytes_mut(&mut aligned) });
+        let mut again = [0_u64; 64];
+        // SAFETY: Same as above.
+        rng.fill_bytes(unsafe { util::as_raw_bytes_mut(&mut again) });
+        let vals = aligned.iter().chain(&again).collect::<BTreeSet<_>>();
+        assert!(vals.len() == 128);
+        // Unaligned and partial destinations continue from where the fast path left off.
+        let mut unaligned = [0_u8; 521];
+        rng.fill_bytes(&mut unaligned[1..]);
+        let words = unaligned[1..].chunks_exact(8);
+        assert!(
+            words
+                .map(|w| u64::from_ne_bytes(w.try_into().unwrap()))
+                .all(|v| !vals.contains(&v))
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn fill_bytes_par() {
+        use alloc::vec;
+        let seed: &[u8] = &[42; 48];
+        let mut rng1 = SecureRng::try_new_from(&mut &*seed).unwrap();
+        let mut rng2 = SecureRng::try_new_from(&mut &*seed).unwrap();
+        let (mut buf1, mut buf2) = (vec![0; (1 << 20) + 13], vec![0; (1 << 20) + 13]);
+        rng1.fill_bytes_par(&mut buf1);
+        rng2.fill_bytes(&mut buf2);
+        assert!(buf1 == buf2);
+        // Both generators continue from the same position.
+        assert!(rng1.u64() == rng2.u64());
+        rng1.fill_bytes_par(&mut buf1[..100]);
+        rng2.fill_bytes(&mut buf2[..100]);
+        assert!(buf1 == buf2);
+    }
+
+    #[test]
+    fn secure_word_pos() {
+        let mut rng = SecureRng::new_with_key([1; 32]);
+        assert!(rng.word_pos() == 0);
+        let vals = rng.array_u64::<100>();
+        assert!(rng.word_pos() == 100);
+        for pos in [0, 7, 8, 31, 32, 33, 99, 37] {
+            rng.set_word_pos(pos);
+            assert!(rng.u64() == vals[pos as usize]);
+            assert!(rng.word_pos() == pos + 1);
+        }
+        // Seeking past the end of the counter wraps around to the start.
+        rng.set_word_pos(5 + (1 << 67));
+        assert!(rng.u64() == vals[5]);
+        // Reading the whole buffer leaves the position at the next refill.
+        rng.set_word_pos(0);
+        (0..32).for_each(|_| _ = rng.u64());
+        assert!(rng.word_pos() == 32);
+        assert!(rng.u64() == vals[32]);
+        assert!(SecureRng::new_with_key([2; 32]).u64() != vals[0]);
+    }
+
+    #[test]
+    fn secure_bytes_generated() {
+        let mut rng = SecureRng::new_with_key([3; 32]);
+        assert!(rng.bytes_generated() == 0);
+        (0..100).for_each(|_| _ = rng.u64());
+        assert!(rng.bytes_generated() == 800);
+        rng.fill_bytes(&mut [0; 1021]);
+        assert!(rng.bytes_generated() == 1821);
+        rng.set_word_pos(3);
+        assert!(rng.bytes_generated() == 1821);
+        rng.u32();
+        assert!(rng.bytes_generated() == 1829);
+        // Reseeding discards the buffered values, which were never handed out.
+        let mut rng = rng.with_reseed_policy(ReseedPolicy::NEVER.with_max_bytes(0));
+        rng.fill_bytes(&mut [0; 7]);
+        assert!(rng.bytes_generated() == 1836);
+        rng.u64();
+        assert!(rng.bytes_generated() == 1844);
+    }
+
+    #[cfg(feature = "hkdf")]
+    #[test]
+    fn secure_derive() {
+        // Test case 3 from RFC 5869.
+        let mut okm = [0; 42];
+        util::hkdf_sha256(&[0x0b; 22], &[], &mut okm);
+        let expected = [
+            0x8d, 0xa4, 0xe7, 0x75, 0xa5, 0x63, 0xc1, 0x8f, 0x71, 0x5f, 0x80, 0x2a, 0x06, 0x3c,
+            0x5a, 0x31, 0xb8, 0xa1, 0x1f, 0x5c, 0x5e, 0xe1, 0x87, 0x9e, 0xc3, 0x45, 0x4e, 0x5f,
+            0x3c, 0x73, 0x8d, 0x2d, 0x9d, 0x20, 0x13, 0x95, 0xfa, 0xa4, 0xb6, 0x1a, 0x96, 0xc8,
+        ];
+        assert!(okm == expected);
+        let mut seed = [0; 48];
+        util::hkdf_sha256(b"master", b"session-tokens", &mut seed);
+        assert!(seed[..8] == [0x5a, 0x1d, 0xf4, 0x76, 0xa3, 0xc4, 0x5a, 0x4f]);
+        assert!(seed[40..] == [0x2d, 0xf6, 0xd7, 0x64, 0x0d, 0x0f, 0x14, 0xeb]);
+        let mut expected = SecureRng::try_new_from(&mut &seed[..]).unwrap();
+        let mut rng = SecureRng::derive(b"master", "session-tokens");
+        assert!((0..100).all(|_| rng.u64() == expected.u64()));
+        let mut other = SecureRng::derive(b"master", "csrf");
+        assert!(rng.u64() != other.u64());
+    }
+
+    #[test]
+    fn secure_metrics() {
+        let mut rng = SecureRng::new_with_key([5; 32]);
+        let buffered = chachacha::BUF_LEN_U64 as u64 * 8 / 64;
+        assert!(rng.blocks_computed() == buffered && rng.reseeds() == 0);
+        (0..chachacha::BUF_LEN_U64).for_each(|_| _ = rng.u64());
+        assert!(rng.blocks_computed() == buffered);
+        rng.u64();
+        assert!(rng.blocks_computed() == 2 * buffered);
+        rng.fill_bytes(&mut [0; 129]);
+        assert!(rng.blocks_computed() == 2 * buffered + 3);
+        rng.set_word_pos(0);
+        assert!(rng.blocks_computed() == 3 * buffered + 3);
+        let mut rng = rng.with_reseed_policy(ReseedPolicy::NEVER.with_max_bytes(100));
+        rng.fill_bytes(&mut [0; 10]);
+        assert!(rng.reseeds() == 1);
+        rng.fill_bytes(&mut [0; 10]);
+        assert!(rng.reseeds() == 1);
+        rng.fill_bytes(&mut [0; 100]);
+        rng.fill_bytes(&mut [0; 1]);
+        assert!(rng.reseeds() == 2);
+        assert!(rng.bytes_generated() > 0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn secure_rng_prefetch() {
+        let seed = [3; 48];
+        let mut expected = SecureRng::try_new_from(&mut &seed[..]).unwrap();
+        let mut rng = SecureRngPrefetch::try_new_from(&mut &seed[..]).unwrap();
+        // Crosses many buffer boundaries, including partial reads.
+        assert!((0..ITERATIONS).all(|_| rng.u64() == expected.u64()));
+        let mut bytes = [0; 4997];
+        rng.fill_bytes(&mut bytes);
+        for chunk in bytes.chunks(8) {
+            assert!(chunk == &expected.u64().to_le_bytes()[..chunk.len()]);
+        }
+        assert!(rng.u64() == expected.u64());
+        assert!(alloc::format!("{rng:?}") == "all `SecureRngPrefetch` fields are private");
+        drop(rng);
+        let mut broken = entropy::HealthTested::new(&seed[..], 8);
+        assert!(SecureRngPrefetch::try_new_from(&mut broken).is_err());
+    }
+
+    #[cfg(all(feature = "mlock", unix))]
+    #[test]
+    fn locked_secure_rng() {
+        let seed = [7; 48];
+        let mut expected = SecureRng::try_new_from(&mut &seed[..]).unwrap();
+        // Locking is allowed to fail in restricted environments.
+        let Ok(mut rng) = LockedSecureRng::try_new_from(&mut &seed[..]) else {
+            return;
+        };
+        assert!((0..ITERATIONS).all(|_| rng.u64() == expected.u64()));
+        let (mut left, mut right) = ([0; 333], [0; 333]);
+        rng.fill_bytes(&mut left);
+        expected.fill_bytes(&mut right);
+        assert!(left == right);
+        rng.inner_mut().set_word_pos(0);
+        assert!(rng.inner().word_pos() == 0);
+        let mut broken = entropy::HealthTested::new(&seed[..], 8);
+        let err = LockedSecureRng::try_new_from(&mut broken).unwrap_err();
+        assert!(err.kind() == ErrorKind::HealthTest);
+    }
+
+    #[test]
+    fn reseed_policy() {
+        let seed: &[u8] = &[7; 48];
+        let mut rng1 = SecureRng::try_new_from(&mut &*seed).unwrap();
+        let mut rng2 = SecureRng::try_new_from(&mut &*seed)
+            .unwrap()
+            .with_reseed_policy(ReseedPolicy::NEVER.with_max_bytes(1024));
+        // Both generators are identical until the limit is reached.
+        assert!((0..64).all(|_| rng1.u64() == rng2.u64()));
+        assert!((0..256).any(|_| rng1.u64() != rng2.u64()));
+
+        let policy = ReseedPolicy::NEVER.with_max_age(std::time::Duration::ZERO);
+        let mut rng1 = SecureRng::try_new_from(&mut &*seed).unwrap();
+        let mut rng2 = SecureRng::try_new_from(&mut &*seed)
+            .unwrap()
+            .with_reseed_policy(policy);
+        let (mut buf1, mut buf2) = ([0; 32], [0; 32]);
+        rng1.fill_bytes(&mut buf1);
+        rng2.fill_bytes(&mut buf2);
+        assert!(buf1 != buf2);
+        assert!(ReseedPolicy::default() == ReseedPolicy::NEVER);
+
+        let mut rng1 = SecureRng::try_new_from(&mut &*seed).unwrap();
+        let mut rng2 = SecureRng::try_new_from(&mut &*seed).unwrap();
+        rng1.try_reseed().unwrap();
+        rng2.reseed();
+        assert!(rng1.reseeds() == 1 && rng2.reseeds() == 1);
+        assert!(rng1.u64() != rng2.u64());
+    }
+
+    #[test]
+    fn secure_token() {
+        let mut rng = new_rng_secure();
+        let token = rng.token::<Base64>(ITERATIONS);
+        assert!(token.as_str().len() == ITERATIONS);
+
+        let same = SecureToken::from(alloc::string::String::from(token.as_str()));
+        assert!(token == same);
+        let mut different = alloc::string::String::from(token.as_str());
+        different.pop();
+        different.push('!');
+        assert!(token != SecureToken::from(different));
+        let shorter = SecureToken::from(alloc::string::String::from(&token.as_str()[1..]));
+        assert!(token != shorter);
+
+        let debug = alloc::format!("{:?}", token);
+        let display = alloc::format!("{}", token);
+        assert!(!debug.contains(token.as_str()));
+        assert!(!display.contains(token.as_str()));
+
+        let bytes = rng.token_bytes(ITERATIONS);
+        assert!(bytes.as_bytes().len() == ITERATIONS);
+        assert!(bytes.as_bytes().iter().any(|&b| b != 0));
+        assert!(bytes == SecureBytes::from(bytes.as_bytes().to_vec()));
+        assert!(bytes != SecureBytes::from(bytes.as_bytes()[1..].to_vec()));
+        assert!(alloc::format!("{:?}", bytes) == "SecureBytes(<redacted>)");
+
+        let mut scratch = rng.secure_array::<100>();
+        util::wipe_slice(&mut scratch[10..]);
+        assert!(scratch[10..].iter().all(|&b| b == 0));
+        assert!(scratch[..10].iter().any(|&b| b != 0));
+    }
+
+    #[test]
+    fn array() {
+        let mut rng = new_rng();
+        let mut vals = BTreeSet::new();
+        for _ in 0..ITERATIONS {
+            // Odd length to make sure the trailing chunk gets filled.
+            let arr = rng.array::<13>();
+            vals.extend(arr);
+        }
+        assert!(vals.len() == 256);
+
+        let arr = rng.array_f64::<ITERATIONS>();
+        assert!(arr.iter().all(|v| (0.0..1.0).contains(v)));
+        let arr = rng.array_u64::<ITERATIONS>();
+        let distinct = arr.iter().collect::<BTreeSet<_>>();
+        assert!(distinct.len() == ITERATIONS);
+    }
+
+    #[test]
+    fn utf8_string() {
+        let mut rng = new_rng();
+        let mut widths = BTreeSet::new();
+        for budget in 0..ITERATIONS {
+            let s = rng.utf8_string(budget);
+            assert!(s.len() == budget);
+            widths.extend(s.chars().map(char::len_utf8));
+        }
+        assert!(widths.len() == 4);
+    }
+
+    #[cfg(feature = "rand09-compat")]
+    #[test]
+    fn rand09_compat() {
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = new_rng();
+        for _ in 0..ITERATIONS {
+            let val = rng.random_range(-69..=69);
+            assert!((-69..=69).contains(&val));
+        }
+        let mut secure_rng = new_rng_secure();
+        let mut data = [0_u8; 1738];
+        secure_rng.fill(&mut data[..]);
+        assert!(data.into_iter().any(|v| v != 0));
+
+        fn requires_crypto<R: rand::CryptoRng>(rng: &mut R) -> [u8; 32] {
+            rng.random()
+        }
+        let mut rng = testing::CountingRng::<SecureRng>::new();
+        let key = requires_crypto(&mut RandAdapter(&mut rng));
+        assert!(key.into_iter().any(|v| v != 0));
+        assert!(rng.draws() != 0);
+        let mut adapter = RandAdapter(&mut rng);
+        for _ in 0..ITERATIONS {
+            let val = adapter.random_range(-69..=69);
+            assert!((-69..=69).contains(&val));
+        }
+
+        let mut wrapped = FromRandCore(rand::rngs::StdRng::from_os_rng());
+        let vals = (0..ITERATIONS)
+            .map(|_| wrapped.u64())
+            .collect::<BTreeSet<_>>();
+        assert!(vals.len() == ITERATIONS);
+        let s = wrapped.text::<Base62>(ITERATIONS);
+        let distinct_bytes = s.bytes().collect::<BTreeSet<_>>();
+        assert!(distinct_bytes.len() == Base62::CHARSET.len());
+        assert!(FromRandCore::<rand::rngs::StdRng>::try_new().is_err());
+    }
+
+    #[cfg(feature = "blake3")]
+    #[test]
+    fn blake3() {
+        let mut rng = Blake3Rng::new();
+        let mut vals = BTreeSet::new();
+        for _ in 0..ITERATIONS {
+            vals.insert(rng.u64());
+        }
+        assert!(vals.len() == ITERATIONS);
+
+        let s = rng.text::<Base62>(ITERATIONS);
+        let distinct_bytes = s.bytes().collect::<BTreeSet<_>>();
+        assert!(distinct_bytes.len() == Base62::CHARSET.len());
+    }
+
+    #[cfg(feature = "ctr-drbg")]
+    #[test]
+    fn ctr_drbg() {
+        // First AES-256 (no derivation function, no prediction resistance)
+        // test vector from the NIST CAVP CTR_DRBG test suite.
+        const ENTROPY: [u8; CtrDrbg::SEED_LEN] = [
+            0xE4, 0xBC, 0x23, 0xC5, 0x08, 0x9A, 0x19, 0xD8, 0x6F, 0x41, 0x19, 0xCB, 0x3F, 0xA0,
+            0x8C, 0x0A, 0x49, 0x91, 0xE0, 0xA1, 0xDE, 0xF1, 0x7E, 0x10, 0x1E, 0x4C, 0x14, 0xD9,
+            0xC3, 0x23, 0x46, 0x0A, 0x7C, 0x2F, 0xB5, 0x8E, 0x0B, 0x08, 0x6C, 0x6C, 0x57, 0xB5,
+            0x5F, 0x56, 0xCA, 0xE2, 0x5B, 0xAD,
+        ];
+        const ENTROPY_RESEED: [u8; CtrDrbg::SEED_LEN] = [
+            0xFD, 0x85, 0xA8, 0x36, 0xBB, 0xA8, 0x50, 0x19, 0x88, 0x1E, 0x8C, 0x6B, 0xAD, 0x23,
+            0xC9, 0x06, 0x1A, 0xDC, 0x75, 0x47, 0x76, 0x59, 0xAC, 0xAE, 0xA8, 0xE4, 0xA0, 0x1D,
+            0xFE, 0x07, 0xA1, 0x83, 0x2D, 0xAD, 0x1C, 0x13, 0x6F, 0x59, 0xD7, 0x0F, 0x86, 0x53,
+            0xA5, 0xDC, 0x11, 0x86, 0x63, 0xD6,
+        ];
+        const RETURNED_BITS: [u8; 64] = [
+            0xB2, 0xCB, 0x89, 0x05, 0xC0, 0x5E, 0x59, 0x50, 0xCA, 0x31, 0x89, 0x50, 0x96, 0xBE,
+            0x29, 0xEA, 0x3D, 0x5A, 0x3B, 0x82, 0xB2, 0x69, 0x49, 0x55, 0x54, 0xEB, 0x80, 0xFE,
+            0x07, 0xDE, 0x43, 0xE1, 0x93, 0xB9, 0xE7, 0xC3, 0xEC, 0xE7, 0x3B, 0x80, 0xE0, 0x62,
+            0xB1, 0xC1, 0xF6, 0x82, 0x02, 0xFB, 0xB1, 0xC5, 0x2A, 0x04, 0x0E, 0xA2, 0x47, 0x88,
+            0x64, 0x29, 0x52, 0x82, 0x23, 0x4A, 0xAA, 0xDA,
+        ];
+        let mut drbg = CtrDrbg::instantiate(&ENTROPY, &[]);
+        drbg.reseed(&ENTROPY_RESEED, &[]);
+        let mut out = [0; RETURNED_BITS.len()];
+        drbg.generate(&mut out, &[]);
+        drbg.try_generate(&mut out, &[]).unwrap();
+        assert!(out == RETURNED_BITS);
+
+        let mut rng = CtrDrbg::new();
+        let mut data = alloc::vec![0; CtrDrbg::MAX_REQUEST_LEN * 3 + 1];
+        rng.fill_bytes(&mut data);
+        assert!(data.iter().collect::<BTreeSet<_>>().len() == 256);
+        let vals = (0..ITERATIONS).map(|_| rng.u64()).collect::<BTreeSet<_>>();
+        assert!(vals.len() == ITERATIONS);
+    }
+
+    #[cfg(feature = "hmac-drbg")]
+    #[test]
+    fn hmac_drbg() {
+        // Test vectors from the NIST CAVP HMAC_DRBG (SHA-256, no reseed) test suite.
+        fn check(entropy: &[u8], nonce: &[u8], additional_input: [&[u8]; 2], expected: &[u8]) {
+            let mut drbg = HmacDrbg::<sha2::Sha256>::instantiate(entropy, nonce, &[]);
+            let mut out = alloc::vec![0; expected.len()];
+            drbg.generate(&mut out, additional_input[0]);
+            drbg.try_generate(&mut out, additional_input[1]).unwrap();
+            assert!(out == expected);
+        }
+        const ENTROPY_0: [u8; 32] = [
+            0xCA, 0x85, 0x19, 0x11, 0x34, 0x93, 0x84, 0xBF, 0xFE, 0x89, 0xDE, 0x1C, 0xBD, 0xC4,
+            0x6E, 0x68, 0x31, 0xE4, 0x4D, 0x34, 0xA4, 0xFB, 0x93, 0x5E, 0xE2, 0x85, 0xDD, 0x14,
+            0xB7, 0x1A, 0x74, 0x88,
+        ];
+        const NONCE_0: [u8; 16] = [
+            0x65, 0x9B, 0xA9, 0x6C, 0x60, 0x1D, 0xC6, 0x9F, 0xC9, 0x02, 0x94, 0x08, 0x05, 0xEC,
+            0x0C, 0xA8,
+        ];
+        const RETURNED_BITS_0: [u8; 128] = [
+            0xE5, 0x28, 0xE9, 0xAB, 0xF2, 0xDE, 0xCE, 0x54, 0xD4, 0x7C, 0x7E, 0x75, 0xE5, 0xFE,
+            0x30, 0x21, 0x49, 0xF8, 0x17, 0xEA, 0x9F, 0xB4, 0xBE, 0xE6, 0xF4, 0x19, 0x96, 0x97,
+            0xD0, 0x4D, 0x5B, 0x89, 0xD5, 0x4F, 0xBB, 0x97, 0x8A, 0x15, 0xB5, 0xC4, 0x43, 0xC9,
+            0xEC, 0x21, 0x03, 0x6D, 0x24, 0x60, 0xB6, 0xF7, 0x3E, 0xBA, 0xD0, 0xDC, 0x2A, 0xBA,
+            0x6E, 0x62, 0x4A, 0xBF, 0x07, 0x74, 0x5B, 0xC1, 0x07, 0x69, 0x4B, 0xB7, 0x54, 0x7B,
+            0xB0, 0x99, 0x5F, 0x70, 0xDE, 0x25, 0xD6, 0xB2, 0x9E, 0x2D, 0x30, 0x11, 0xBB, 0x19,
+            0xD2, 0x76, 0x76, 0xC0, 0x71, 0x62, 0xC8, 0xB5, 0xCC, 0xDE, 0x06, 0x68, 0x96, 0x1D,
+            0xF8, 0x68, 0x03, 0x48, 0x2C, 0xB3, 0x7E, 0xD6, 0xD5, 0xC0, 0xBB, 0x8D, 0x50, 0xCF,
+            0x1F, 0x50, 0xD4, 0x76, 0xAA, 0x04, 0x58, 0xBD, 0xAB, 0xA8, 0x06, 0xF4, 0x8B, 0xE9,
+            0xDC, 0xB8,
+        ];
+        check(&ENTROPY_0, &NONCE_0, [&[], &[]], &RETURNED_BITS_0);
+
+        const ENTROPY_1: [u8; 32] = [
+            0x13, 0xC7, 0x52, 0xB9, 0xE7, 0x45, 0xCE, 0x77, 0xBB, 0xC7, 0xC0, 0xDB, 0xDA, 0x98,
+            0x23, 0x13, 0xD3, 0xFE, 0x66, 0xF9, 0x03, 0xE8, 0x3E, 0xBD, 0x8D, 0xBE, 0x4F, 0xF0,
+            0xC1, 0x13, 0x80, 0xE9,
+        ];
+        const NONCE_1: [u8; 16] = [
+            0xF1, 0xA5, 0x33, 0x09, 0x5D, 0x61, 0x74, 0x16, 0x4B, 0xD7, 0xC8, 0x25, 0x32, 0x46,
+            0x4A, 0xE7,
+        ];
+        const ADDITIONAL_INPUT_1: [u8; 32] = [
+            0x4F, 0x53, 0xDB, 0x89, 0xB9, 0xBA, 0x7F, 0xC0, 0x07, 0x67, 0xBC, 0x75, 0x1F, 0xB8,
+            0xF3, 0xC1, 0x03, 0xFE, 0x0F, 0x76, 0xAC, 0xD6, 0xD5, 0xC7, 0x89, 0x1A, 0xB1, 0x5B,
+            0x2B, 0x7C, 0xF6, 0x7C,
+        ];
+        const ADDITIONAL_INPUT_2: [u8; 32] = [
+            0x58, 0x2C, 0x2A, 0x7D, 0x34, 0x67, 0x90, 0x88, 0xCC, 0xA6, 0xBD, 0x28, 0x72, 0x3C,
+            0x99, 0xAA, 0xC0, 0x7D, 0xB4, 0x6C, 0x33, 0x2D, 0xC0, 0x15, 0x3D, 0x16, 0x73, 0x25,
+            0x69, 0x03, 0xB4, 0x46,
+        ];
+        const RETURNED_BITS_1: [u8; 128] = [
+            0x63, 0x11, 0xF4, 0xC0, 0xC4, 0xCD, 0x1F, 0x86, 0xBD, 0x48, 0x34, 0x9A, 0xBB, 0x9E,
+            0xB9, 0x30, 0xD4, 0xF6, 0x3D, 0xF5, 0xE5, 0xF7, 0x21, 0x7D, 0x1D, 0x1B, 0x91, 0xA7,
+            0x1D, 0x8A, 0x69, 0x38, 0xB0, 0xAD, 0x2B, 0x3E, 0x89, 0x7B, 0xD7, 0xE3, 0xD8, 0x70,
+            0x3D, 0xB1, 0x25, 0xFA, 0xB3, 0x0E, 0x03, 0x46, 0x4F, 0xAD, 0x41, 0xE5, 0xDD, 0xF5,
+            0xBF, 0x9A, 0xEE, 0xB5, 0x16, 0x1B, 0x24, 0x44, 0x68, 0xCF, 0xB2, 0x6A, 0x9D, 0x95,
+            0x69, 0x31, 0xA5, 0x41, 0x2C, 0x97, 0xD6, 0x41, 0x88, 0xB0, 0xDA, 0x1B, 0xD9, 0x07,
+            0x81, 0x9C, 0x68, 0x6F, 0x39, 0xAF, 0x82, 0xE9, 0x1C, 0xFE, 0xEF, 0x0C, 0xBF, 0xFB,
+            0x5D, 0x1E, 0x22, 0x9E, 0x38, 0x3B, 0xED, 0x26, 0xD0, 0x64, 0x12, 0x98, 0x86, 0x40,
+            0x70, 0x68, 0x15, 0xA6, 0xE8, 0x20, 0x79, 0x68, 0x76, 0xF4, 0x16, 0x65, 0x3E, 0x46,
+            0x49, 0x61,
+        ];
+        check(
+            &ENTROPY_1,
+            &NONCE_1,
+            [&ADDITIONAL_INPUT_1, &ADDITIONAL_INPUT_2],
+            &RETURNED_BITS_1,
+        );
+
+        let mut rng = HmacDrbg::<sha2::Sha256>::new();
+        let vals = (0..ITERATIONS).map(|_| rng.u64()).collect::<BTreeSet<_>>();
+        assert!(vals.len() == ITERATIONS);
+
+        // The largest supported output length.
+        let mut rng = HmacDrbg::<sha2::Sha512>::new();
+        let vals = (0..ITERATIONS).map(|_| rng.u64()).collect::<BTreeSet<_>>();
+        assert!(vals.len() == ITERATIONS);
+        rng.reseed(&[0x42; HmacDrbg::<sha2::Sha512>::MIN_ENTROPY_LEN], &[]);
+    }
+
+    #[test]
+    fn entropy_pool() {
+        use entropy::*;
+        let a: &[u8] = &[0b1100; 5];
+        let b: &[u8] = &[0b1010; 3];
+        let mut buf = [0xFF; 4];
+        EntropyPool::new(a).with_bytes(b).fill(&mut buf).unwrap();
+        assert!(buf == [0b1010, 0b0110, 0b0110, 0b1100]);
+
+        let mut pool = EntropyPool::new(a);
+        let rng1: Xoshiro256pp = pool.seed().unwrap();
+        let rng2: Xoshiro256pp = pool.seed().unwrap();
+        assert!(rng1 == rng2);
+
+        let mut pool = EntropyPool::default().with_bytes(a);
+        let rng3: Xoshiro256pp = pool.seed().unwrap();
+        assert!(rng1 != rng3);
+
+        // An all-zero state would only ever output zero.
+        let mut rng4: Xoshiro256pp = EntropyPool::new(&[][..]).seed().unwrap();
+        let mut rng5: RomuTrio = EntropyPool::new(&[0; 24][..]).seed().unwrap();
+        assert!((0..4).any(|_| rng4.u64() != 0));
+        assert!((0..4).any(|_| rng5.u64() != 0));
+    }
+
+    #[test]
+    fn jitter_entropy() {
+        use entropy::*;
+        let mut jitter = JitterEntropy::new().unwrap();
+        let mut buf = [0; 64];
+        jitter.fill(&mut buf).unwrap();
+        let vals = buf.chunks(8).collect::<BTreeSet<_>>();
+        assert!(vals.len() == buf.len() / 8);
+
+        let mut pool = EntropyPool::new(OsEntropy).with(jitter);
+        let rng1: Xoshiro256pp = pool.seed().unwrap();
+        let rng2: Xoshiro256pp = pool.seed().unwrap();
+        assert!(rng1 != rng2);
+    }
+
+    #[test]
+    fn test_doubles() {
+        use testing::*;
+        let mut rng = MockRng::new_with_values([u64::MAX, 0, 1 << 63]);
+        assert!(rng.bits(8) == u8::MAX as u64);
+        assert!(!rng.bool());
+        assert!(rng.f64() == 0.5);
+        assert!(rng.remaining() == 0);
+
+        let mut rng = StepRng::new_with_step(u64::MAX - 1, 2);
+        let vals = [rng.u64(), rng.u64(), rng.u64()];
+        assert!(vals == [u64::MAX - 1, 0, 2]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn recording_rng() {
+        use alloc::vec::Vec;
+        use testing::*;
+        let mut rng = RecordingRng::new_with_tape(Xoshiro256pp::new_with_seed(69), Vec::new());
+        let vals = (0..ITERATIONS).map(|_| rng.u64()).collect::<Vec<_>>();
+        let (_, tape) = rng.into_parts();
+        assert!(tape == vals);
+        let mut expected = Xoshiro256pp::new_with_seed(69);
+        assert!(tape.iter().all(|&v| v == expected.u64()));
+
+        let rng = RecordingRng::<Xoshiro256pp, Vec<u64>>::new();
+        assert!(rng.tape().is_empty());
+
+        let mut rng = ReplayRng::new_with_tape(&tape);
+        assert!(vals.iter().all(|&v| v == rng.u64()));
+        assert!(rng.remaining() == 0);
+        assert!(rng.try_u64().is_none());
+        assert!(ReplayRng::try_new().is_err());
+    }
+
+    #[test]
+    fn counting_rng() {
+        use testing::*;
+        let mut rng = CountingRng::<Xoshiro256pp>::new_with_seed(1);
+        for _ in 0..ITERATIONS {
+            rng.f64();
+        }
+        assert!(rng.draws() == ITERATIONS as u64);
+        assert!(rng.bytes() == 0);
+        assert!(rng.into_inner() != Xoshiro256pp::new_with_seed(1));
+
+        #[cfg(feature = "secure")]
+        {
+            let mut rng = CountingRng::<SecureRng>::new();
+            let _ = rng.secure_array::<100>();
+            assert!(rng.bytes() == 100);
+            assert!(rng.draws() == 0);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn goodness_of_fit() {
+        use stats::*;
+        // With two degrees of freedom, the p-value is exactly exp(-statistic / 2).
+        let result = chi_square(&[15, 5, 10], &[10.0; 3]);
+        assert!(result.statistic == 5.0);
+        assert!((result.p_value - (-2.5_f64).exp()).abs() < 1e-12);
+        // With one degree of freedom, the p-value is erfc(sqrt(statistic / 2)).
+        let p_value = chi_square(&[0, 0], &[1.0, 1.0]).p_value;
+        assert!((p_value - 0.157_299_207).abs() < 1e-8);
+
+        let mut rng = new_rng();
+        let mut observed = [0; 10];
+        for _ in 0..ITERATIONS {
+            observed[rng.bound(10) as usize] += 1;
+        }
+        let result = chi_square(&observed, &[ITERATIONS as f64 / 10.0; 10]);
+        assert!(result.passes(1e-9));
+        let skewed = (0..ITERATIONS).map(|_| rng.f64() * rng.f64());
+        assert!(!ks_test(skewed, |x| x.clamp(0.0, 1.0)).passes(1e-9));
+        let exponentials = (0..ITERATIONS).map(|_| rng.f64_exponential());
+        assert!(ks_test(exponentials, |x| 1.0 - (-x).exp()).passes(1e-9));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn histogram() {
+        use stats::*;
+        let mut histogram = Histogram::new(0.0, 4.0, 4);
+        histogram.extend([0.0, 0.5, 1.0, 3.999, 4.0, -1.0]);
+        histogram.extend([2_u64, 2]);
+        assert!(histogram.counts() == [2, 1, 2, 1]);
+        assert!(histogram.outliers() == 2);
+        assert!(histogram.len() == 8);
+        assert!((histogram.mean() - 1.562375).abs() < 1e-12);
+        let lines = histogram.render(10);
+        let lines = lines.lines().collect::<alloc::vec::Vec<_>>();
+        assert!(lines.len() == 5);
+        assert!(lines[0].ends_with("|##########"));
+        assert!(lines[1].ends_with("|#####"));
+
+        let mut rng = new_rng();
+        let mut histogram = Histogram::new(0.0, 1.0, 16);
+        histogram.extend((0..ITERATIONS).map(|_| rng.f64()));
+        assert!(histogram.outliers() == 0);
+        assert!((histogram.variance() - 1.0 / 12.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn in_range() {
+        let mut rng = new_rng();
+        for _ in 0..ITERATIONS {
+            let val = rng.in_range(10..20);
+            assert!((10..20).contains(&val));
+            let val = rng.in_range(..=3);
+            assert!(val <= 3);
+            let val = rng.in_range_i64(-5..=5);
+            assert!((-5..=5).contains(&val));
+            let val = rng.in_range_i64(i64::MIN..i64::MIN + 2);
+            assert!(val == i64::MIN || val == i64::MIN + 1);
+            let val = rng.in_range_f64(-2.0..-1.0);
+            assert!((-2.0..-1.0).contains(&val));
+        }
+        assert!(rng.in_range(7..=7) == 7);
+        assert!(rng.in_range_i64(..) != rng.in_range_i64(..));
+        assert!(rng.in_range_f64(0.5..=0.5) == 0.5);
+        let vals = (0..ITERATIONS)
+            .map(|_| rng.in_range_i64(-3..3))
+            .collect::<BTreeSet<_>>();
+        assert!(vals.len() == 6);
+    }
+
+    #[test]
+    #[should_panic]
+    fn in_range_empty() {
+        new_rng().in_range(5..5);
+    }
+
+    #[test]
+    fn checked_bound_range() {
+        let mut rng = new_rng();
+        for _ in 0..ITERATIONS {
+            assert!(rng.checked_bound(7).unwrap() < 7);
+            let val = rng.checked_range(-3, 3).unwrap();
+            assert!((-3..3).contains(&val));
+            let val = rng.checked_range(i64::MIN, i64::MAX).unwrap();
+            assert!(val != i64::MAX);
+            let val = rng.checked_range_inclusive(i64::MAX - 1, i64::MAX).unwrap();
+            assert!(val >= i64::MAX - 1);
+        }
+        assert!(rng.checked_bound(1).unwrap() == 0);
+        assert!(rng.checked_range_inclusive(-1, -1).unwrap() == -1);
+        assert!(rng.checked_range_inclusive(i64::MIN, i64::MAX).is_ok());
+        let invalid = ErrorKind::InvalidParameter;
+        assert!(rng.checked_bound(0).unwrap_err().kind() == invalid);
+        assert!(rng.checked_range(1, 1).unwrap_err().kind() == invalid);
+        assert!(rng.checked_range(2, 1).unwrap_err().kind() == invalid);
+        assert!(rng.checked_range_inclusive(2, 1).unwrap_err().kind() == invalid);
+    }
+
+    #[test]
+    fn fill_bool() {
+        let mut rng = new_rng();
+        let mut dst = [false; ITERATIONS];
+        rng.fill_bool(&mut dst, 0.25);
+        let ones = dst.iter().filter(|&&b| b).count();
+        let expected = ITERATIONS / 4;
+        assert!(ones.abs_diff(expected) < expected / 10);
+        rng.fill_bool(&mut dst, 1.0);
+        assert!(dst.iter().all(|&b| b));
+        rng.fill_bool(&mut dst, 0.0);
+        assert!(dst.iter().all(|&b| !b));
+        rng.fill_bool(&mut dst, f64::NAN);
+        assert!(dst.iter().all(|&b| !b));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn redacted_debug() {
+        use std::format;
+        let rng = Xoshiro256pp::new_with_seed(0);
+        assert!(format!("{rng:?}") == "all `Xoshiro256pp` fields are private");
+        assert!(rng.expose_state() == Xoshiro256pp::new_with_seed(0).expose_state());
+        assert!(RomuTrio::new_with_seed(1).expose_state().len() == 3);
+        assert!(RomuQuad::new_with_seed(1).expose_state().len() == 4);
+        assert!(Xoshiro512pp::new_with_seed(1).expose_state().len() == 8);
+    }
+
+    #[test]
+    fn jump_by_pow2() {
+        // Jump polynomials from the reference implementation.
+        const JUMP: [u64; 4] = [
+            0x180EC6D33CFD0ABA,
+            0xD5A61266F0C9392C,
+            0xA9582618E03FC9AA,
+            0x39ABDC4529B1661C,
+        ];
+        const LONG_JUMP: [u64; 4] = [
+            0x76E15D3EFEFDCBBF,
+            0xC5004E441C522FB3,
+            0x77710069854EE241,
+            0x39109BB02ACBE635,
+        ];
+        let reference = |poly: [u64; 4]| {
+            let mut rng = Xoshiro256pp::new_with_seed(1);
+            let mut acc = [0; 4];
+            for i in 0..256 {
+                if (poly[i / 64] >> (i % 64)) & 1 == 1 {
+                    let state = rng.expose_state();
+                    acc.iter_mut().zip(state).for_each(|(a, s)| *a ^= s);
+                }
+                rng.u64();
+            }
+            acc
+        };
+        for (k, poly) in [(128, JUMP), (192, LONG_JUMP)] {
+            let mut rng = Xoshiro256pp::new_with_seed(1);
+            rng.jump_by_pow2(k);
+            assert!(rng.expose_state() == reference(poly));
+        }
+
+        for k in 0..6 {
+            let mut rng1 = Xoshiro256pp::new_with_seed(k as u64);
+            let mut rng2 = rng1.clone();
+            rng1.jump_by_pow2(k);
+            (0..1 << k).for_each(|_| _ = rng2.u64());
+            assert!(rng1 == rng2);
+            let mut rng1 = Xoshiro512pp::new_with_seed(k as u64);
+            let mut rng2 = rng1.clone();
+            rng1.jump_by_pow2(k);
+            (0..1 << k).for_each(|_| _ = rng2.u64());
+            assert!(rng1 == rng2);
+        }
+        // The embedded reference polynomials must agree with the characteristic
+        // polynomials, which are used for every other distance.
+        for k in [128, 192] {
+            let mut rng1 = Xoshiro256pp::new_with_seed(k as u64);
+            let mut rng2 = rng1.clone();
+            rng1.jump_by_pow2(k);
+            (0..2).for_each(|_| rng2.jump_by_pow2(k - 1));
+            assert!(rng1 == rng2);
+        }
+        for k in [256, 384] {
+            let mut rng1 = Xoshiro512pp::new_with_seed(k as u64);
+            let mut rng2 = rng1.clone();
+            rng1.jump_by_pow2(k);
+            (0..2).for_each(|_| rng2.jump_by_pow2(k - 1));
+            assert!(rng1 == rng2);
+        }
+        // The period is 2^256 - 1, so jumping by 2^256 is the same as a single step.
+        let mut rng1 = Xoshiro256pp::new_with_seed(69);
+        let mut rng2 = rng1.clone();
+        rng1.jump_by_pow2(256);
+        rng2.u64();
+        assert!(rng1 == rng2);
+    }
+
+    #[test]
+    fn const_new_with_seed() {
+        static XOSHIRO256PP: Xoshiro256pp = Xoshiro256pp::new_with_seed(420);
+        const XOSHIRO512PP: Xoshiro512pp = Xoshiro512pp::new_with_seed(420);
+        const ROMUTRIO: RomuTrio = RomuTrio::new_with_seed(420);
+        const ROMUQUAD: RomuQuad = RomuQuad::new_with_seed(420);
+        assert!(XOSHIRO256PP == <Xoshiro256pp as SeedableGenerator>::new_with_seed(420));
+        assert!(XOSHIRO512PP == <Xoshiro512pp as SeedableGenerator>::new_with_seed(420));
+        assert!(ROMUTRIO == <RomuTrio as SeedableGenerator>::new_with_seed(420));
+        assert!(ROMUQUAD == <RomuQuad as SeedableGenerator>::new_with_seed(420));
+        assert!(ROMUQUAD != RomuQuad::default());
+    }
+
+    #[test]
+    #[should_panic(expected = "can't be created using entropy from the operating system")]
+    fn new_unsupported() {
+        testing::ReplayRng::new();
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn error() {
+        use std::string::ToString;
+        assert!(try_new_rng().is_ok());
+        #[cfg(feature = "secure")]
+        assert!(try_new_rng_secure().is_ok());
+        let err = testing::ReplayRng::try_new().unwrap_err();
+        assert!(err.kind() == ErrorKind::Unsupported);
+        assert!(err.raw_os_error().is_none());
+        assert!(err == ErrorKind::Unsupported.into());
+        assert!(err.to_string() == "operation is not supported");
+        let err = Error::new(ErrorKind::InvalidParameter);
+        assert!(err.to_string() == "argument is out of range");
+        let err = Error::new(ErrorKind::SelfTest);
+        assert!(err.to_string() == "known-answer self-test failed");
+        let err = Error::new(ErrorKind::MemoryLock);
+        assert!(err.to_string() == "locking memory failed");
+        assert!(entropy::JitterEntropy::HEALTH_TEST_FAILURE.kind() == ErrorKind::HealthTest);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn choose_multiple_weighted() {
+        let mut rng = new_rng();
+        let weights = [1.0, 2.0, 0.0, 4.0, 8.0];
+        let mut first = [0; 5];
+        let mut chosen = [0; 5];
+        for _ in 0..ITERATIONS {
+            let picks = rng.choose_multiple_weighted(0..weights.len(), 2, |&i| weights[i]);
+            assert!(picks.len() == 2 && picks[0] != picks[1]);
+            first[picks[0]] += 1;
+            picks.iter().for_each(|&i| chosen[i] += 1);
+        }
+        assert!(first[2] == 0 && chosen[2] == 0);
+        // The first pick is a plain weighted choice.
+        let expected = weights.map(|w| w / 15.0 * ITERATIONS as f64);
+        let observed = [first[0], first[1], first[3], first[4]];
+        let expected = [expected[0], expected[1], expected[3], expected[4]];
+        assert!(stats::chi_square(&observed, &expected).passes(1e-9));
+
+        let all = rng.choose_multiple_weighted(&weights, 10, |w| **w);
+        assert!(all.len() == 4);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn weighted_reservoir() {
+        const LEN: usize = 20;
+        let mut rng = new_rng();
+        let mut first = [0; LEN];
+        for _ in 0..ITERATIONS {
+            let mut reservoir = WeightedReservoir::new(3);
+            for i in 0..LEN {
+                reservoir.push(&mut rng, i, (i + 1) as f64);
+            }
+            reservoir.push(&mut rng, LEN, 0.0);
+            assert!(reservoir.len() == 3);
+            let sample = reservoir.into_sorted_vec();
+            assert!(sample.iter().collect::<BTreeSet<_>>().len() == 3);
+            assert!(!sample.contains(&LEN));
+            first[sample[0]] += 1;
+        }
+        // The first item of the sample is a plain weighted choice.
+        let total = (LEN * (LEN + 1) / 2) as f64;
+        let expected =
+            core::array::from_fn::<_, LEN, _>(|i| (i + 1) as f64 / total * ITERATIONS as f64);
+        assert!(stats::chi_square(&first, &expected).passes(1e-9));
+
+        let mut reservoir = WeightedReservoir::new(0);
+        reservoir.push(&mut rng, 1, 1.0);
+        assert!(reservoir.is_empty());
+    }
+
+    #[test]
+    fn shuffle_2d() {
+        const ROWS: usize = 7;
+        const COLUMNS: usize = 5;
+        let mut rng = new_rng();
+        let matrix = core::array::from_fn::<_, { ROWS * COLUMNS }, _>(|i| i);
+        let mut row_orders = BTreeSet::new();
+        let mut column_orders = BTreeSet::new();
+        for _ in 0..ITERATIONS {
+            let mut shuffled = matrix;
+            rng.shuffle_rows(&mut shuffled, COLUMNS);
+            assert!(
+                shuffled
+                    .chunks(COLUMNS)
+                    .all(|row| row[0] % COLUMNS == 0 && row.windows(2).all(|w| w[1] == w[0] + 1))
+            );
+            row_orders.insert(shuffled.map(|v| v / COLUMNS));
+
+            let mut shuffled = matrix;
+            rng.shuffle_columns(&mut shuffled, COLUMNS);
+            let first = &shuffled[..COLUMNS];
+            assert!(
+                shuffled
+                    .chunks(COLUMNS)
+                    .enumerate()
+                    .all(|(r, row)| { row.iter().zip(first).all(|(x, y)| *x == y + r * COLUMNS) })
+            );
+            column_orders.insert([
+                shuffled[0],
+                shuffled[1],
+                shuffled[2],
+                shuffled[3],
+                shuffled[4],
+            ]);
+        }
+        // All 5! column orders should show up, and plenty of the 7! row orders.
+        assert!(column_orders.len() == 120);
+        assert!(row_orders.len() > 2000);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn derangement() {
+        let mut rng = new_rng();
+        assert!(rng.derangement(0) == Some(alloc::vec![]));
+        assert!(rng.derangement(1).is_none());
+        assert!(rng.derangement(2) == Some(alloc::vec![1, 0]));
+        // There are exactly 44 derangements of 5 elements.
+        let vals = (0..ITERATIONS)
+            .map(|_| rng.derangement(5).unwrap())
+            .inspect(|v| assert!(v.iter().enumerate().all(|(i, &x)| i != x)))
+            .collect::<BTreeSet<_>>();
+        assert!(vals.len() == 44);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn latin_square() {
+        let mut rng = new_rng();
+        assert!(latin_square_biased(&mut rng, 0).is_empty());
+        for n in 1..=16 {
+            let square = latin_square_biased(&mut rng, n);
+            assert!(square.len() == n * n);
+            for i in 0..n {
+                let row = square[i * n..][..n].iter().collect::<BTreeSet<_>>();
+                let column = square.iter().skip(i).step_by(n).collect::<BTreeSet<_>>();
+                assert!(row.len() == n && column.len() == n);
+                assert!(row.iter().all(|&&v| v < n));
+            }
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn shuffle_indexed() {
+        use alloc::collections::VecDeque;
+        let mut first = Xoshiro256pp::new_with_seed(42);
+        let mut second = first.clone();
+        let mut data = [0_u32; 1000];
+        data.iter_mut().enumerate().for_each(|(i, v)| *v = i as u32);
+        // Pushing to the front makes the contents wrap around the end of the ring buffer.
+        let mut queue = data[400..].iter().copied().collect::<VecDeque<_>>();
+        data[..400].iter().rev().for_each(|&v| queue.push_front(v));
+        assert!(!queue.as_slices().1.is_empty());
+        first.shuffle(&mut data);
+        second.shuffle_indexed(&mut queue);
+        assert!(queue.iter().eq(data.iter()));
+        let mut vec = alloc::vec![1, 2, 3];
+        first.shuffle_indexed(&mut vec);
+        first.shuffle_indexed(&mut vec[..]);
+        vec.sort();
+        assert!(vec == [1, 2, 3]);
+        let mut empty = VecDeque::<u8>::new();
+        first.shuffle_indexed(&mut empty);
+        assert!(Swappable::is_empty(&empty));
+    }
+
+    #[test]
+    fn lazy_permutation() {
+        let mut rng = new_rng();
+        for len in [0, 1, 2, 3, 4, 5, 63, 64, 65, 1000, ITERATIONS as u64] {
+            let permutation = LazyPermutation::new(&mut rng, len);
+            assert!(permutation.len() == len);
+            let vals = permutation.iter().collect::<BTreeSet<_>>();
+            assert!(vals.len() as u64 == len);
+            assert!(vals.iter().all(|&v| v < len));
+        }
+        let permutation = LazyPermutation::new(&mut rng, u64::MAX);
+        assert!(permutation.get(u64::MAX - 1) < u64::MAX);
+        let first = LazyPermutation::new(&mut rng, 1 << 40);
+        let second = LazyPermutation::new(&mut rng, 1 << 40);
+        assert!(first.iter().take(100).ne(second.iter().take(100)));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn combination() {
+        let mut rng = new_rng();
+        assert!(rng.combination(0, 0).is_empty());
+        assert!(rng.combination(5, 5) == [0, 1, 2, 3, 4]);
+        let big = rng.combination(u64::MAX, 1000);
+        assert!(big.len() == 1000 && big.windows(2).all(|w| w[0] < w[1]));
+        // There are exactly 10 ways to choose 2 of 5 elements, and
+        // every element should be chosen equally often.
+        let mut counts = [0; 5];
+        let mut combinations = BTreeSet::new();
+        for _ in 0..ITERATIONS {
+            let c = rng.combination(5, 2);
+            assert!(c[0] < c[1]);
+            c.iter().for_each(|&i| counts[i as usize] += 1);
+            combinations.insert(c);
+        }
+        assert!(combinations.len() == 10);
+        let expected = [2.0 * ITERATIONS as f64 / 5.0; 5];
+        assert!(stats::chi_square(&counts, &expected).passes(1e-9));
+
+        let masks = (0..ITERATIONS)
+            .map(|_| rng.subset_mask(4))
+            .collect::<BTreeSet<_>>();
+        assert!(masks.len() == 16 && masks.iter().all(|&m| m < 16));
+        assert!((0..ITERATIONS).all(|_| rng.subset_mask(0) == 0));
+    }
+
+    #[cfg(feature = "slug")]
+    #[test]
+    fn slug() {
+        let mut rng = new_rng();
+        let names = (0..ITERATIONS).map(|_| rng.slug()).collect::<BTreeSet<_>>();
+        assert!(names.len() > ITERATIONS / 2);
+        for name in &names {
+            let parts = name.split('-').collect::<alloc::vec::Vec<_>>();
+            assert!(parts.len() == 3);
+            assert!(slug::ADJECTIVES.contains(&parts[0]));
+            assert!(slug::NOUNS.contains(&parts[1]));
+            assert!(parts[2].len() == 4 && parts[2].bytes().all(|c| c.is_ascii_digit()));
+        }
+        let name = rng.slug_with(4, " :: ", 0);
+        let parts = name.split(" :: ").collect::<alloc::vec::Vec<_>>();
+        assert!(parts[..3].iter().all(|w| slug::ADJECTIVES.contains(w)));
+        assert!(slug::NOUNS.contains(&parts[3]));
+        assert!(slug::NOUNS.contains(&rng.slug_with(1, "-", 0).as_str()));
+        assert!(rng.slug_with(0, "-", 19).len() == 19);
+        let words = [slug::ADJECTIVES, slug::NOUNS].concat();
+        assert!(words.iter().collect::<BTreeSet<_>>().len() == words.len());
+        assert!(
+            words
+                .iter()
+                .all(|w| w.bytes().all(|c| c.is_ascii_lowercase()))
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn unique_u64s() {
+        let mut rng = new_rng();
+        assert!(rng.unique_u64s(0, 0).is_empty());
+        assert!(rng.unique_u64s_set(0, 0).is_empty());
+        let all = rng.unique_u64s(5, 5);
+        assert!(all.iter().collect::<BTreeSet<_>>().len() == 5);
+        assert!(rng.unique_u64s_set(5, 5).iter().all(|&v| v < 5));
+        let big = rng.unique_u64s(1000, u64::MAX);
+        assert!(big.iter().collect::<BTreeSet<_>>().len() == 1000);
+        assert!(!big.is_sorted());
+        assert!(rng.unique_u64s_set(1000, u64::MAX).len() == 1000);
+        // Every ordering of 3 values should show up, and every
+        // value should be chosen equally often.
+        let mut counts = [0; 4];
+        let mut orders = BTreeSet::new();
+        for _ in 0..ITERATIONS {
+            let u = rng.unique_u64s(3, 4);
+            u.iter().for_each(|&i| counts[i as usize] += 1);
+            orders.insert(u);
+            rng.unique_u64s_set(2, 4)
+                .into_iter()
+                .for_each(|i| counts[i as usize] += 1);
+        }
+        assert!(orders.len() == 24);
+        let expected = [5.0 * ITERATIONS as f64 / 4.0; 4];
+        assert!(stats::chi_square(&counts, &expected).passes(1e-9));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn composition() {
+        let mut rng = new_rng();
+        assert!(rng.composition(0, 0).is_empty());
+        assert!(rng.composition(0, 3) == [0, 0, 0]);
+        assert!(rng.composition(7, 1) == [7]);
+        assert!(rng.composition_positive(3, 3) == [1, 1, 1]);
+        // There are exactly C(4 + 2, 2) = 15 weak compositions of 4 into 3 parts,
+        // and C(3, 2) = 3 compositions of 4 into 3 positive parts.
+        let weak = (0..ITERATIONS)
+            .map(|_| rng.composition(4, 3))
+            .inspect(|v| assert!(v.iter().sum::<u64>() == 4))
+            .collect::<BTreeSet<_>>();
+        assert!(weak.len() == 15);
+        let positive = (0..ITERATIONS)
+            .map(|_| rng.composition_positive(4, 3))
+            .inspect(|v| assert!(v.iter().all(|&x| x > 0)))
+            .collect::<BTreeSet<_>>();
+        assert!(positive.len() == 3);
+        let huge = rng.composition(u64::MAX - 9, 10);
+        assert!(huge.iter().sum::<u64>() == u64::MAX - 9);
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn regex_strings() {
+        let mut rng = new_rng();
+        let pattern = RegexStrings::from_regex(r"^(?:cat|dog)s?-\d{2,4}[a-c[x-z]]+$").unwrap();
+        let mut seen = BTreeSet::new();
+        for _ in 0..ITERATIONS {
+            let s = pattern.sample(&mut rng);
+            let (animal, rest) = s.split_once('-').unwrap();
+            assert!(["cat", "cats", "dog", "dogs"].contains(&animal));
+            let digits = rest.chars().take_while(|c| c.is_numeric()).count();
+            assert!((2..=4).contains(&digits));
+            let letters = &rest[rest.char_indices().nth(digits).unwrap().0..];
+            assert!(!letters.is_empty() && letters.len() <= 9);
+            assert!(letters.bytes().all(|b| b"abcxyz".contains(&b)));
+            seen.insert(animal.len());
+        }
+        assert!(seen.len() == 2);
+
+        let pattern = RegexStrings::from_regex(".").unwrap().max_repeat(0);
+        let vals = (0..ITERATIONS)
+            .map(|_| pattern.sample(&mut rng))
+            .inspect(|s| assert!(s.chars().count() == 1 && s != "\n"))
+            .collect::<BTreeSet<_>>();
+        // Roughly 70 collisions are expected among the ~1.1 million possible characters.
+        assert!(vals.len() > ITERATIONS - 500);
+        assert!(vals.iter().any(|s| s.len() == 4));
+        let pattern = RegexStrings::from_regex("(?-u:[a-c])x*")
+            .unwrap()
+            .max_repeat(0);
+        assert!(["a", "b", "c"].contains(&pattern.sample(&mut rng).as_str()));
+        assert!(RegexStrings::from_regex("(").is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn system_time() {
+        use std::time::{Duration, SystemTime};
+        let mut rng = new_rng();
+        let start = SystemTime::UNIX_EPOCH - Duration::from_secs(1000);
+        let end = start + Duration::from_nanos(3);
+        let vals = (0..ITERATIONS)
+            .map(|_| rng.system_time(start, end))
+            .collect::<BTreeSet<_>>();
+        assert!(vals.len() == 3 && vals.iter().all(|&t| start <= t && t < end));
+        // Spans which don't fit into 64 bits of nanoseconds.
+        let end = start + Duration::from_secs(1 << 40);
+        let vals = (0..ITERATIONS)
+            .map(|_| rng.system_time(start, end))
+            .inspect(|&t| assert!(start <= t && t < end))
+            .collect::<BTreeSet<_>>();
+        assert!(vals.len() == ITERATIONS);
+        let time = rng.system_time_in_last(Duration::from_secs(60));
+        assert!(SystemTime::now().duration_since(time).unwrap() < Duration::from_secs(120));
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn offset_date_time() {
+        use time::{Duration, OffsetDateTime, UtcOffset};
+        let mut rng = new_rng();
+        let offset = UtcOffset::from_hms(-5, 0, 0).unwrap();
+        let start = OffsetDateTime::UNIX_EPOCH.to_offset(offset);
+        let end = start + Duration::weeks(52 * 1000);
+        for _ in 0..ITERATIONS {
+            let time = rng.offset_date_time(start, end);
+            assert!(start <= time && time < end);
+            assert!(time.offset() == offset);
+        }
+        let time = rng.offset_date_time_in_last(Duration::days(1));
+        assert!(OffsetDateTime::now_utc() - time < Duration::days(2));
+    }
+
+    #[cfg(feature = "num-bigint")]
+    #[test]
+    fn biguint() {
+        use num_bigint::BigUint;
+        let mut rng = new_rng();
+        assert!(rng.biguint_bits(0) == BigUint::ZERO);
+        for bits in [1, 31, 32, 33, 64, 65, 1000] {
+            let vals = (0..ITERATIONS / 10)
+                .map(|_| rng.biguint_bits(bits))
+                .inspect(|v| assert!(v.bits() <= bits))
+                .collect::<BTreeSet<_>>();
+            assert!(vals.iter().any(|v| v.bits() == bits));
+        }
+        let max = BigUint::from(3_u32) << 100;
+        let vals = (0..ITERATIONS)
+            .map(|_| rng.biguint_below(&max))
+            .inspect(|v| assert!(v < &max))
+            .collect::<BTreeSet<_>>();
+        assert!(vals.len() == ITERATIONS);
+        let max = BigUint::from(6_u32);
+        let vals = (0..ITERATIONS)
+            .map(|_| rng.biguint_below(&max))
+            .collect::<BTreeSet<_>>();
+        assert!(vals.len() == 6);
+    }
+
+    #[cfg(all(feature = "num-bigint", feature = "secure"))]
+    #[test]
+    fn prime_bits() {
+        use num_bigint::BigUint;
+        let mut rng = new_rng_secure();
+        let small = (0..ITERATIONS / 10)
+            .map(|_| rng.prime_bits(2))
+            .collect::<BTreeSet<_>>();
+        assert!(small == BTreeSet::from([BigUint::from(2_u32), BigUint::from(3_u32)]));
+        for bits in [3, 8, 20, 64, 127, 512] {
+            let p = rng.prime_bits(bits);
+            assert!(p.bits() == bits);
+            // Fermat test with a handful of bases.
+            let p_minus_one = &p - 1_u32;
+            for base in [2_u32, 3, 5, 7] {
+                let base = BigUint::from(base);
+                if base < p {
+                    assert!(base.modpow(&p_minus_one, &p) == BigUint::from(1_u32));
+                }
+            }
+        }
+        for _ in 0..ITERATIONS / 10 {
+            let p = rng.prime_bits(16);
+            let p = u32::try_from(&p).unwrap();
+            assert!((2..p).take_while(|d| d * d <= p).all(|d| p % d != 0));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn multivariate_normal() {
+        const SAMPLES: usize = ITERATIONS * 16;
+        let mut rng = new_rng();
+        let mean = [1.0, -2.0, 0.5];
+        #[rustfmt::skip]
+        let covariance = [
+            4.0, 1.2, -0.6,
+            1.2, 1.0, 0.3,
+            -0.6, 0.3, 2.0,
+        ];
+        let mvn = MultivariateNormal::new(&mean, &covariance).unwrap();
+        assert!(mvn.dim() == 3);
+        assert!(mvn.mean() == mean);
+        let mut sums = [0.0; 3];
+        let mut products = [0.0; 9];
+        let mut sample = [0.0; 3];
+        for _ in 0..SAMPLES {
+            mvn.sample(&mut rng, &mut sample);
+            for i in 0..3 {
+                sums[i] += sample[i];
+                for j in 0..3 {
+                    products[i * 3 + j] += (sample[i] - mean[i]) * (sample[j] - mean[j]);
+                }
+            }
+        }
+        for i in 0..3 {
+            assert!((sums[i] / SAMPLES as f64 - mean[i]).abs() < 0.05);
+        }
+        for (product, expected) in products.into_iter().zip(covariance) {
+            assert!((product / SAMPLES as f64 - expected).abs() < 0.1);
+        }
+        assert!(MultivariateNormal::new(&[0.0; 2], &[1.0, 2.0, 2.0, 1.0]).is_none());
+        assert!(MultivariateNormal::new(&[0.0; 2], &[1.0, 0.0, 0.0, 0.0]).is_none());
+        assert!(MultivariateNormal::new(&[0.0; 2], &[f64::NAN, 0.0, 0.0, 1.0]).is_none());
+        assert!(MultivariateNormal::new(&[], &[]).is_some());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn orthogonal_matrices() {
+        const N: usize = 6;
+        const EPSILON: f64 = 1e-10;
+        let mut rng = new_rng();
+        let mut angles = stats::Histogram::new(-core::f64::consts::PI, core::f64::consts::PI, 8);
+        for _ in 0..ITERATIONS {
+            let [[a, b], [c, d]] = rotation_matrix_2d(&mut rng);
+            assert!((a * d - b * c - 1.0).abs() < EPSILON);
+            angles.add(c.atan2(a));
+        }
+        let expected = [ITERATIONS as f64 / 8.0; 8];
+        assert!(stats::chi_square(angles.counts(), &expected).passes(1e-9));
+
+        let mut z_axis = stats::Histogram::new(-1.0, 1.0, 8);
+        for _ in 0..ITERATIONS {
+            let m = rotation_matrix_3d(&mut rng);
+            let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+                - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+                + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+            assert!((det - 1.0).abs() < EPSILON);
+            // The image of a fixed axis is uniform on the sphere, so
+            // each coordinate is uniform on [-1, 1].
+            z_axis.add(m[2][2]);
+        }
+        assert!(stats::chi_square(z_axis.counts(), &expected).passes(1e-9));
+
+        let mut q = [0.0; N * N];
+        let mut positive_first = 0;
+        for _ in 0..ITERATIONS / 10 {
+            random_orthogonal(&mut rng, N, &mut q);
+            for i in 0..N {
+                for j in 0..N {
+                    let dot = (0..N).map(|k| q[i * N + k] * q[j * N + k]).sum::<f64>();
+                    let expected = if i == j { 1.0 } else { 0.0 };
+                    assert!((dot - expected).abs() < EPSILON);
+                }
+            }
+            if q[0] > 0.0 {
+                positive_first += 1;
+            }
+        }
+        assert!(positive_first > ITERATIONS / 25 && positive_first < ITERATIONS * 3 / 50);
+        random_orthogonal(&mut rng, 0, &mut []);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn quasi_sequences() {
+        use quasi::{Halton, Sobol};
+        const K: u32 = 10;
+        const POINTS: usize = 1 << K;
+        let mut rng = new_rng();
+        for mut sobol in [
+            Sobol::new(Sobol::MAX_DIMS),
+            Sobol::new_scrambled(Sobol::MAX_DIMS, &mut rng),
+        ] {
+            let mut points = alloc::vec![[0.0; Sobol::MAX_DIMS]; POINTS];
+            points.iter_mut().for_each(|p| sobol.next_point(p));
+            assert!(sobol.index() == POINTS as u64);
+            // Every dimension has exactly one point in each interval of width 2^-K.
+            for d in 0..Sobol::MAX_DIMS {
+                let cells = points
+                    .iter()
+                    .map(|p| (p[d] * POINTS as f64) as usize)
+                    .collect::<BTreeSet<_>>();
+                assert!(cells.len() == POINTS);
+            }
+            // The first two dimensions form a (0, K, 2)-net.
+            for i in 0..=K {
+                let cells = points
+                    .iter()
+                    .map(|p| {
+                        (
+                            (p[0] * (1 << i) as f64) as usize,
+                            (p[1] * (1 << (K - i)) as f64) as usize,
+                        )
+                    })
+                    .collect::<BTreeSet<_>>();
+                assert!(cells.len() == POINTS);
+            }
+            let mut skipped = sobol.clone();
+            let mut expected = [0.0; Sobol::MAX_DIMS];
+            let mut actual = [0.0; Sobol::MAX_DIMS];
+            for _ in 0..37 {
+                sobol.next_point(&mut expected);
+            }
+            skipped.skip(36);
+            skipped.next_point(&mut actual);
+            assert!(expected == actual);
+        }
+        for mut halton in [
+            Halton::new(Halton::MAX_DIMS),
+            Halton::new_scrambled(Halton::MAX_DIMS, &mut rng),
+        ] {
+            let mut points = alloc::vec![[0.0; Halton::MAX_DIMS]; 2 * 3 * 5 * 7 * 11];
+            points.iter_mut().for_each(|p| halton.next_point(p));
+            // The first b^k points are distinct multiples of b^-k.
+            for (d, &base) in [2, 3, 5, 7, 11].iter().enumerate() {
+                let mut len = 1;
+                while len * base <= points.len() {
+                    len *= base;
+                }
+                let cells = points[..len]
+                    .iter()
+                    .inspect(|p| assert!((0.0..1.0).contains(&p[d])))
+                    .map(|p| (p[d] * len as f64).round() as usize)
+                    .collect::<BTreeSet<_>>();
+                assert!(cells.len() == len);
+            }
+        }
+    }
+
+    #[test]
+    fn stratified() {
+        let mut rng = new_rng();
+        for n in [0, 1, 2, 7, 64, 1000] {
+            let mut samples = alloc::vec![0.0; n];
+            rng.stratified_1d(n, &mut samples);
+            for (i, v) in samples.into_iter().enumerate() {
+                assert!((0.0..1.0).contains(&v));
+                assert!((v * n as f64) as usize == i);
+            }
+        }
+        for (nx, ny) in [(0, 0), (0, 5), (5, 0), (1, 1), (3, 7), (32, 32)] {
+            let mut points = alloc::vec![[0.0; 2]; nx * ny];
+            rng.jittered_2d(nx, ny, &mut points);
+            for (i, [x, y]) in points.into_iter().enumerate() {
+                assert!((0.0..1.0).contains(&x) && (0.0..1.0).contains(&y));
+                assert!((x * nx as f64) as usize == i % nx);
+                assert!((y * ny as f64) as usize == i / nx);
+            }
+        }
+        // Averaging over every cell should be extremely accurate.
+        let mut samples = alloc::vec![0.0; ITERATIONS];
+        rng.stratified_1d(ITERATIONS, &mut samples);
+        let mean = samples.iter().sum::<f64>() / ITERATIONS as f64;
+        assert!((mean - 0.5).abs() < 1e-4);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn poisson_disk() {
+        const WIDTH: f64 = 40.0;
+        const HEIGHT: f64 = 30.0;
+        const MIN_DIST: f64 = 1.0;
+        let mut rng = new_rng();
+        let points = rng.poisson_disk(WIDTH, HEIGHT, MIN_DIST);
+        for (i, &[x1, y1]) in points.iter().enumerate() {
+            assert!((0.0..WIDTH).contains(&x1) && (0.0..HEIGHT).contains(&y1));
+            for &[x2, y2] in &points[i + 1..] {
+                assert!((x1 - x2).hypot(y1 - y2) >= MIN_DIST);
+            }
+        }
+        // A maximal set has no gaps which could fit another point, so
+        // it's far denser than the sparsest possible valid packing.
+        let area_per_point = WIDTH * HEIGHT / points.len() as f64;
+        assert!(area_per_point < 2.0 * MIN_DIST * MIN_DIST);
+        let mut gaps = 0;
+        for _ in 0..ITERATIONS {
+            let (x, y) = (rng.f64() * WIDTH, rng.f64() * HEIGHT);
+            if points
+                .iter()
+                .all(|&[px, py]| (px - x).hypot(py - y) >= MIN_DIST)
+            {
+                gaps += 1;
+            }
+        }
+        assert!(gaps < ITERATIONS / 100);
+        assert!(rng.poisson_disk(0.0, HEIGHT, MIN_DIST).is_empty());
+        assert!(rng.poisson_disk(0.1, 0.1, MIN_DIST).len() == 1);
+        // Points right at the far edge used to be placed in a cell past the end of the grid.
+        let width = 7.0 * (0.1 / core::f64::consts::SQRT_2);
+        let mut rng = testing::StepRng::new_with_step(u64::MAX, 0);
+        assert!(rng.poisson_disk(width, width, 0.1).len() == 1);
+    }
+
+    #[test]
+    fn by_ref_iter() {
+        let mut rng1 = Xoshiro256pp::new_with_seed(42);
+        let mut rng2 = Xoshiro256pp::new_with_seed(42);
+        let vals = rng1
+            .by_ref_iter()
+            .take(ITERATIONS)
+            .collect::<alloc::vec::Vec<_>>();
+        assert!(vals.into_iter().all(|v| v == rng2.u64()));
+        assert!(rng1 == rng2);
+        let bytes = rng1
+            .by_ref_iter()
+            .flat_map(u64::to_le_bytes)
+            .take(16)
+            .collect::<alloc::vec::Vec<_>>();
+        assert!(bytes[..8] == rng2.u64().to_le_bytes());
+        assert!(bytes[8..] == rng2.u64().to_le_bytes());
+        assert!(rng1.by_ref_iter().size_hint() == (usize::MAX, None));
+    }
+
+    #[cfg(all(feature = "std", feature = "secure"))]
+    #[test]
+    fn secure_global() {
+        use std::{thread, vec::Vec};
+        let tokens = thread::scope(|s| {
+            let handles = (0..8)
+                .map(|_| {
+                    s.spawn(|| {
+                        (0..ITERATIONS / 8)
+                            .map(|_| super::secure_global().text::<Base62>(0))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().unwrap())
+                .collect::<BTreeSet<_>>()
+        });
+        assert!(tokens.len() == ITERATIONS / 8 * 8);
+        // A poisoned lock is recovered from.
+        let _ = thread::spawn(|| {
+            let _guard = super::secure_global();
+            panic!("poisoning the lock");
+        })
+        .join();
+        // Each guard has to be dropped before the lock can be taken again.
+        let x = super::secure_global().u64();
+        let y = super::secure_global().u64();
+        assert!(x != y);
+        // Reseeding after a fork keeps the generator usable.
+        super::mark_forked();
+        let z = super::secure_global().u64();
+        assert!(z != x && z != y);
+        super::observe_vm_generation(1);
+        assert!(!super::observe_vm_generation(1));
+        assert!(super::observe_vm_generation(2));
+        assert!(!super::observe_vm_generation(2));
+    }
+
+    #[test]
+    fn caching_rng() {
+        let mut reference = new_rng();
+        let mut rng =
+            CachingRng::new_with_rng(testing::CountingRng::new_with_rng(reference.clone()));
+        for _ in 0..ITERATIONS / 2 {
+            let val = reference.u64();
+            assert!(rng.u32() == (val >> 32) as u32 && rng.u32() == val as u32);
+        }
+        assert!(rng.inner().draws() == (ITERATIONS / 2) as u64);
+        assert!(rng.bits(0) == 0);
+        assert!(rng.bits(33) < 1 << 33);
+
+        // The cached bits are handed out from the top of each value.
+        let mut rng =
+            CachingRng::new_with_rng(testing::StepRng::new_with_step(0x0123_4567_89AB_CDEF, 0));
+        assert!(rng.u8() == 0x01 && rng.u16() == 0x2345 && rng.u32() == 0x6789_ABCD);
+        // Only 8 bits are left, so a fresh value is drawn.
+        assert!(rng.u16() == 0x0123);
+        assert!(rng.u64() == 0x0123_4567_89AB_CDEF);
+        let ones = (0..64).filter(|_| rng.bool()).count();
+        assert!(ones == 0x0123_4567_89AB_CDEF_u64.count_ones() as usize);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn dyn_generator() {
+        use alloc::boxed::Box;
+        let mut rngs: [Box<dyn DynGenerator + Send>; 2] = [
+            Box::new(testing::StepRng::new_with_step(0x0102_0304_0506_0708, 0)),
+            Box::new(new_rng()),
+        ];
+        let mut buf = [0; 11];
+        rngs[0].dyn_fill(&mut buf);
+        assert!(buf == [8, 7, 6, 5, 4, 3, 2, 1, 8, 7, 6]);
+        assert!(rngs[0].bound(1 << 56) == 0x01_0203_0405_0607);
+        let vals = rngs[1]
+            .by_ref_iter()
+            .take(ITERATIONS)
+            .collect::<BTreeSet<_>>();
+        assert!(vals.len() == ITERATIONS);
+        assert!(Box::<dyn DynGenerator>::try_new().is_err());
+    }
+
+    #[test]
+    fn new_stream() {
+        let vals = (0..100)
+            .flat_map(|id| Xoshiro256pp::new_stream(69, id).array_u64::<10>())
+            .collect::<BTreeSet<_>>();
+        assert!(vals.len() == 1000);
+        assert!(RomuTrio::new_stream(1, 2) == RomuTrio::new_stream(1, 2));
+        assert!(RomuTrio::new_stream(1, 2) != RomuTrio::new_stream(2, 1));
+        assert!(RomuTrio::new_stream(0, 0) != RomuTrio::new_with_seed(0));
+        // Wrappers are derived through the wrapped generator.
+        let rng = CachingRng::<Xoshiro512pp>::new_stream(3, 4);
+        assert!(rng.into_inner() == Xoshiro512pp::new_stream(3, 4));
+    }
+
+    #[test]
+    fn split_fold_in() {
+        use alloc::vec::Vec;
+        let (mut left, mut right) = RomuTrio::new_with_seed(1).split();
+        let (mut again, _) = RomuTrio::new_with_seed(1).split();
+        assert!(left.u64() == again.u64());
+        assert!(left.u64() != right.u64());
+        let parent = Xoshiro256pp::new_with_seed(2);
+        let children = (0..100).map(|i| parent.fold_in(i)).collect::<Vec<_>>();
+        assert!(parent == Xoshiro256pp::new_with_seed(2));
+        assert!(
+            children
+                .iter()
+                .rev()
+                .zip((0..100).rev())
+                .all(|(c, i)| *c == parent.fold_in(i))
+        );
+        let vals = children
+            .into_iter()
+            .map(|mut c| c.u64())
+            .collect::<BTreeSet<_>>();
+        assert!(vals.len() == 100);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn mnemonic() {
+        use alloc::borrow::ToOwned;
+        use alloc::string::{String, ToString};
+        use alloc::vec::Vec;
+
+        let seed = 0x0123_4567_89ab_cdef;
+        let phrase = Mnemonic::from_u64(seed).to_string();
+        assert!(phrase.split('-').count() == 9);
+        assert!(phrase.parse::<Mnemonic<8>>().unwrap().to_u64() == seed);
+        let spaced = phrase.replace('-', "  ").to_uppercase();
+        assert!(spaced.parse::<Mnemonic<8>>().unwrap().to_u64() == seed);
+        assert!(phrase.parse::<Mnemonic<16>>().is_err());
+        assert!(
+            alloc::format!("{phrase}-army")
+                .parse::<Mnemonic<8>>()
+                .is_err()
+        );
+        assert!(alloc::format!("x{phrase}").parse::<Mnemonic<8>>().is_err());
+        // Replacing any single word with any other word is always detected.
+        let mnemonic = Mnemonic::new([7; 16]);
+        let words: Vec<_> = mnemonic.to_string().split('-').map(String::from).collect();
+        let others: Vec<_> = (0..=255)
+            .map(|b| Mnemonic::new([b]).to_string())
+            .map(|s| s.split('-').next().unwrap().to_owned())
+            .collect();
+        for i in 0..words.len() {
+            for other in others.iter().filter(|&w| *w != words[i]) {
+                let mut changed = words.clone();
+                changed[i] = other.clone();
+                assert!(changed.join("-").parse::<Mnemonic<16>>().is_err());
+            }
+        }
+        let wide = Mnemonic::from_u128(u128::MAX - 1);
+        assert!(wide.to_string().parse::<Mnemonic<16>>().unwrap() == wide);
+        assert!(wide.to_u128() == u128::MAX - 1);
+    }
+
+    #[test]
+    fn leapfrog() {
+        const STREAMS: usize = 5;
+        let expected = RomuQuad::new_with_seed(420).array_u64::<{ STREAMS * 20 }>();
+        let mut streams: [_; STREAMS] = core::array::from_fn(|i| {
+            Leapfrog::new_with_rng(RomuQuad::new_with_seed(420), i, STREAMS)
+        });
+        for chunk in expected.chunks_exact(STREAMS) {
+            for (stream, &val) in streams.iter_mut().zip(chunk) {
+                assert!(stream.u64() == val);
+            }
+        }
+        assert!(Leapfrog::<RomuQuad>::try_new().is_err());
+        let mut single = Leapfrog::new_with_rng(RomuQuad::new_with_seed(420), 0, 1);
+        assert!(single.u64() == expected[0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "`stream` must be less than `streams`")]
+    fn leapfrog_invalid_stream() {
+        Leapfrog::new_with_rng(RomuQuad::new_with_seed(0), 3, 3);
     }
 
     #[test]
@@ -356,6 +2324,26 @@ mod tests {
         assert!(low == EXPECTED_LOW);
     }
 
+    #[test]
+    fn bitcast_floats() {
+        // Both conversions agree on everything but the lowest bit of resolution.
+        let mut rng = new_rng();
+        for x in [0, 1, 1 << 11, 1 << 63, u64::MAX]
+            .into_iter()
+            .chain(rng.by_ref_iter().take(ITERATIONS))
+        {
+            let (precise, fast) = (util::u64_to_f64(x), util::u64_to_f64_bitcast(x));
+            assert!((0.0..1.0).contains(&fast));
+            assert!(fast <= precise && precise - fast <= f64::EPSILON / 2.0);
+            let precise = (x >> 40) as f32 / (1 << 24) as f32;
+            let fast = util::u64_to_f32_bitcast(x);
+            assert!((0.0..1.0).contains(&fast));
+            assert!(fast <= precise && precise - fast <= f32::EPSILON / 2.0);
+        }
+        assert!(util::u64_to_f64_bitcast(u64::MAX) == 1.0 - f64::EPSILON);
+        assert!(util::u64_to_f32_bitcast(u64::MAX) == 1.0 - f32::EPSILON);
+    }
+
     #[test]
     fn f64() {
         let mut rng = new_rng();
@@ -409,4 +2397,455 @@ mod tests {
             assert!(val.abs() < 1.0);
         }
     }
+
+    #[test]
+    fn random_variant() {
+        random_variant! {
+            #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+            enum Shape {
+                Circle,
+                Square,
+                Triangle(bool),
+            }
+        }
+
+        random_variant! {
+            enum Level {
+                Low = 3,
+                High,
+            }
+        }
+
+        let mut rng = new_rng();
+        let mut counts = [0_usize; 4];
+        for _ in 0..ITERATIONS {
+            match rng.variant() {
+                Shape::Circle => counts[0] += 1,
+                Shape::Square => counts[1] += 1,
+                Shape::Triangle(false) => counts[2] += 1,
+                Shape::Triangle(true) => counts[3] += 1,
+            }
+        }
+        let third = ITERATIONS / 3;
+        assert!(counts[..2].iter().all(|&c| c.abs_diff(third) < third / 10));
+        assert!(counts[2].abs_diff(counts[3]) < third / 10);
+        assert!(Level::High as isize == 4);
+        assert!(matches!(rng.variant(), Level::Low | Level::High));
+    }
+
+    #[test]
+    fn choose_from_iter() {
+        let mut rng = new_rng();
+        let mut counts = [0_usize; 5];
+        for _ in 0..ITERATIONS {
+            let chosen = rng.choose_from_iter((0..50).filter(|i| i % 10 == 0));
+            counts[chosen.unwrap() / 10] += 1;
+        }
+        let fifth = ITERATIONS / 5;
+        assert!(counts.iter().all(|&c| c.abs_diff(fifth) < fifth / 10));
+        assert!(rng.choose_from_iter([7]) == Some(7));
+        assert!(rng.choose_from_iter(core::iter::empty::<u8>()).is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn choose_by_weight() {
+        let mut rng = new_rng();
+        let items = [("a", 1.0), ("b", 0.0), ("c", 3.0), ("d", 4.0)];
+        let mut counts = [0; 4];
+        for _ in 0..ITERATIONS {
+            let (name, _) = rng.choose_by_weight(&items, |(_, w)| *w).unwrap();
+            counts[items.iter().position(|(n, _)| n == name).unwrap()] += 1;
+        }
+        assert!(counts[1] == 0);
+        let observed = [counts[0], counts[2], counts[3]];
+        let expected = [1.0, 3.0, 4.0].map(|w| w * ITERATIONS as f64 / 8.0);
+        assert!(stats::chi_square(&observed, &expected).passes(1e-9));
+        assert!(rng.choose_by_weight([5], |_| 0.5) == Some(5));
+        assert!(rng.choose_by_weight([5, 6], |_| 0.0).is_none());
+        assert!(
+            rng.choose_by_weight(core::iter::empty::<u8>(), |_| 1.0)
+                .is_none()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn choose_except() {
+        let mut rng = new_rng();
+        let items = [0_usize, 1, 2, 3, 4, 5];
+        let (mut few, mut most, mut index) = ([0; 6], [0; 6], [0; 6]);
+        for _ in 0..ITERATIONS {
+            few[*rng.choose_except(&items, |&i| i == 2).unwrap()] += 1;
+            most[*rng.choose_except(&items, |&i| i < 4).unwrap()] += 1;
+            index[rng.choose_index_except(6, 4).unwrap()] += 1;
+        }
+        assert!(few[2] == 0 && index[4] == 0);
+        assert!(most[..4].iter().all(|&c| c == 0));
+        let fifth = [ITERATIONS as f64 / 5.0; 5];
+        for counts in [few, index] {
+            let allowed = counts
+                .iter()
+                .copied()
+                .filter(|&c| c != 0)
+                .collect::<alloc::vec::Vec<_>>();
+            assert!(stats::chi_square(&allowed, &fifth).passes(1e-9));
+        }
+        let half = [ITERATIONS as f64 / 2.0; 2];
+        assert!(stats::chi_square(&most[4..], &half).passes(1e-9));
+        assert!(rng.choose_except(&items, |_| true).is_none());
+        assert!(rng.choose_except(&[] as &[u8], |_| false).is_none());
+        assert!(rng.choose_index_except(0, 0).is_none());
+        assert!(rng.choose_index_except(1, 0).is_none());
+        assert!(rng.choose_index_except(2, 0) == Some(1));
+        assert!(rng.choose_index_except(1, 5) == Some(0));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn choose_from_map() {
+        use alloc::collections::BTreeMap;
+        use std::collections::HashMap;
+        let mut rng = new_rng();
+        let map = (0..5_u32).map(|i| (i, i * 10)).collect::<HashMap<_, _>>();
+        let mut counts = [0; 5];
+        for _ in 0..ITERATIONS {
+            let (&key, &value) = rng.choose_from_map(&map).unwrap();
+            assert!(value == key * 10);
+            counts[key as usize] += 1;
+        }
+        let expected = [ITERATIONS as f64 / 5.0; 5];
+        assert!(stats::chi_square(&counts, &expected).passes(1e-9));
+        let mut tree = map.into_iter().collect::<BTreeMap<_, _>>();
+        assert!(tree.contains_key(rng.choose_key(&tree).unwrap()));
+        *rng.choose_value(&mut tree).unwrap() = 1;
+        assert!(tree.values().filter(|&&v| v == 1).count() == 1);
+        assert!(rng.choose_value(BTreeMap::from([(1, 'x')])) == Some('x'));
+        assert!(rng.choose_key(&BTreeMap::<u8, u8>::new()).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn choose_by_weight_negative() {
+        new_rng().choose_by_weight([1, 2], |&i| 1.0 - i as f64);
+    }
+
+    #[test]
+    fn mac_address() {
+        let mut rng = new_rng();
+        let mut seen = 0;
+        for _ in 0..ITERATIONS {
+            let mac = net::mac_address(&mut rng);
+            assert!(mac[0] & 0b11 == 0b10);
+            seen |= u64::from_le_bytes([mac[0], mac[1], mac[2], mac[3], mac[4], mac[5], 0, 0]);
+            let mac = net::oui_suffix(&mut rng, [0xff, 0x01, 0x80]);
+            assert!(mac[..3] == [0xff, 0x01, 0x80]);
+        }
+        // Every bit other than the two fixed ones takes both values.
+        assert!(seen == 0xffff_ffff_fffe);
+    }
+
+    #[test]
+    fn colors() {
+        assert!(color::hsl_to_rgb(0.0, 1.0, 0.5) == [255, 0, 0]);
+        assert!(color::hsl_to_rgb(1.0 / 3.0, 1.0, 0.5) == [0, 255, 0]);
+        assert!(color::hsl_to_rgb(0.5, 1.0, 0.25) == [0, 128, 128]);
+        assert!(color::hsl_to_rgb(0.75, 0.0, 0.5) == [128, 128, 128]);
+        assert!(color::hsl_to_rgb(0.9, 0.3, 1.0) == [255, 255, 255]);
+        let mut rng = new_rng();
+        for _ in 0..ITERATIONS {
+            let [r, g, b] = color::hsl(&mut rng, 0.0..=0.0, 0.2..0.8);
+            assert!(r == g && g == b && (51..=204).contains(&r));
+            let [r, g, b] = color::hsl(&mut rng, 1.0..=1.0, 0.5..=0.5);
+            assert!(r.max(g).max(b) == 255 && r.min(g).min(b) == 0);
+        }
+        let alphas: BTreeSet<_> = (0..ITERATIONS).map(|_| color::rgba(&mut rng)[3]).collect();
+        assert!(alphas.len() == 256);
+    }
+
+    #[test]
+    fn bits_with_weight() {
+        let mut rng = new_rng();
+        for width in 0..=64 {
+            for ones in 0..=width {
+                let bits = rng.bits_with_weight(width, ones);
+                assert!(bits.count_ones() == ones);
+                assert!(width == 64 || bits < 1 << width);
+            }
+        }
+        assert!(rng.bits_with_weight(100, 64) == u64::MAX);
+        // All 10 patterns of 2 ones within 5 bits are equally likely.
+        let mut counts = [0_usize; 32];
+        (0..ITERATIONS).for_each(|_| counts[rng.bits_with_weight(5, 2) as usize] += 1);
+        let tenth = ITERATIONS / 10;
+        for (i, &count) in counts.iter().enumerate() {
+            match i.count_ones() == 2 {
+                true => assert!(count.abs_diff(tenth) < tenth / 5),
+                false => assert!(count == 0),
+            }
+        }
+        // Same for 3 ones, which places the zeros instead.
+        let mut counts = [0_usize; 32];
+        (0..ITERATIONS).for_each(|_| counts[rng.bits_with_weight(5, 3) as usize] += 1);
+        for (i, &count) in counts.iter().enumerate() {
+            match i.count_ones() == 3 {
+                true => assert!(count.abs_diff(tenth) < tenth / 5),
+                false => assert!(count == 0),
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "`ones` must not be greater than `width`")]
+    fn bits_with_weight_too_many() {
+        new_rng().bits_with_weight(5, 6);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn erdos_renyi() {
+        let mut rng = new_rng();
+        let check = |graph: &[alloc::vec::Vec<usize>]| {
+            for (v, neighbors) in graph.iter().enumerate() {
+                assert!(neighbors.is_sorted());
+                assert!(neighbors.windows(2).all(|w| w[0] != w[1]));
+                assert!(neighbors.iter().all(|&u| u != v && graph[u].contains(&v)));
+            }
+            graph.iter().map(|neighbors| neighbors.len()).sum::<usize>() / 2
+        };
+        let n = 200;
+        let possible = n * (n - 1) / 2;
+        let edges = check(&graph::gnp(&mut rng, n, 0.25));
+        assert!(edges.abs_diff(possible / 4) < possible / 40);
+        assert!(check(&graph::gnp(&mut rng, n, 0.0)) == 0);
+        assert!(check(&graph::gnp(&mut rng, n, 1.0)) == possible);
+        assert!(check(&graph::gnm(&mut rng, n, 1234)) == 1234);
+        assert!(check(&graph::gnm(&mut rng, n, possible)) == possible);
+        assert!(check(&graph::gnm(&mut rng, n, 0)) == 0);
+        assert!(graph::gnm(&mut rng, 0, 0).is_empty() && graph::gnp(&mut rng, 0, 0.5).is_empty());
+        assert!(graph::gnm(&mut rng, 1, 0).len() == 1);
+        // Every edge of a small graph is equally likely.
+        let mut counts = [[0_usize; 4]; 4];
+        for _ in 0..ITERATIONS {
+            let graph = graph::gnm(&mut rng, 4, 1);
+            let u = graph
+                .iter()
+                .position(|neighbors| !neighbors.is_empty())
+                .unwrap();
+            counts[u][graph[u][0]] += 1;
+        }
+        let sixth = ITERATIONS / 6;
+        for (i, row) in counts.iter().enumerate() {
+            for (j, &count) in row.iter().enumerate() {
+                match i < j {
+                    true => assert!(count.abs_diff(sixth) < sixth / 10),
+                    false => assert!(count == 0),
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn sorted_uniforms() {
+        let mut rng = new_rng();
+        let mut samples = [0.0; 1000];
+        rng.sorted_uniforms(samples.len(), &mut samples);
+        assert!(samples.is_sorted());
+        assert!(samples.iter().all(|v| (0.0..1.0).contains(v)));
+        // The k-th of n sorted uniforms has a mean of k / (n + 1).
+        const N: usize = 4;
+        let mut means = [0.0; N];
+        let mut buf = [0.0; N];
+        for _ in 0..ITERATIONS {
+            rng.sorted_uniforms(N, &mut buf);
+            means.iter_mut().zip(buf).for_each(|(m, v)| *m += v);
+        }
+        for (k, mean) in means.into_iter().enumerate() {
+            let expected = (k + 1) as f64 / (N + 1) as f64;
+            assert!((mean / ITERATIONS as f64 - expected).abs() < 0.01);
+        }
+        rng.sorted_uniforms(0, &mut []);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn jitter() {
+        use std::time::Duration;
+
+        let mut rng = new_rng();
+        let (min, max) = (Duration::from_nanos(10), Duration::from_nanos(13));
+        let vals: BTreeSet<_> = (0..ITERATIONS)
+            .map(|_| rng.jitter_between(min, max))
+            .collect();
+        assert!(vals.into_iter().eq((10..=13).map(Duration::from_nanos)));
+        assert!(rng.jitter_between(max, max) == max);
+        let base = Duration::from_secs(100);
+        for _ in 0..ITERATIONS {
+            let val = rng.jitter(base, 0.25);
+            assert!(Duration::from_secs(75) <= val && val <= Duration::from_secs(125));
+        }
+        assert!(rng.jitter(base, 0.0) == base);
+        let huge = Duration::new(u64::MAX, 999_999_999);
+        assert!(rng.jitter_between(Duration::ZERO, huge) <= huge);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic(expected = "`min` must not be greater than `max`")]
+    fn jitter_between_reversed() {
+        use std::time::Duration;
+
+        new_rng().jitter_between(Duration::from_secs(2), Duration::from_secs(1));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn backoff() {
+        use std::time::Duration;
+
+        let mut rng = new_rng();
+        let ms = Duration::from_millis;
+        let mut backoff = Backoff::new(ms(10), ms(100)).with_jitter(Jitter::None);
+        let delays: alloc::vec::Vec<_> = (0..6).map(|_| backoff.next_delay(&mut rng)).collect();
+        assert!(delays == [ms(10), ms(20), ms(40), ms(80), ms(100), ms(100)]);
+        assert!(backoff.attempts() == 6);
+        backoff.reset();
+        assert!(backoff.attempts() == 0 && backoff.next_delay(&mut rng) == ms(10));
+        let mut backoff = Backoff::new(ms(10), ms(100));
+        for expected in [10, 20, 40, 80, 100, 100] {
+            assert!(backoff.next_delay(&mut rng) <= ms(expected));
+        }
+        let mut backoff = Backoff::new(ms(10), ms(100)).with_jitter(Jitter::Equal);
+        for expected in [10, 20, 40, 80, 100, 100] {
+            let delay = backoff.next_delay(&mut rng);
+            assert!(ms(expected) / 2 <= delay && delay <= ms(expected));
+        }
+        // Huge multipliers and caps saturate instead of overflowing.
+        let max = Duration::MAX;
+        let mut backoff = Backoff::new(ms(1), max)
+            .with_multiplier(1e300)
+            .with_jitter(Jitter::None);
+        backoff.next_delay(&mut rng);
+        assert!(backoff.next_delay(&mut rng) == max);
+        assert!(backoff.next_delay(&mut rng) == max);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn top_k_top_p() {
+        let mut rng = new_rng();
+        // Softmax probabilities of 0.1, 0.2, 0.3, and 0.4.
+        let logits = [0.1_f32.ln(), 0.2_f32.ln(), 0.3_f32.ln(), 0.4_f32.ln()];
+        let mut scratch = [0; 4];
+        let mut counts = [0_usize; 4];
+        for _ in 0..ITERATIONS {
+            counts[sampling::top_k(&mut rng, &logits, 2, 1.0, &mut scratch)] += 1;
+        }
+        // Only the top 2 remain, renormalized to 3/7 and 4/7.
+        assert!(counts[..2] == [0, 0]);
+        let expected = ITERATIONS * 3 / 7;
+        assert!(counts[2].abs_diff(expected) < expected / 10);
+        let mut counts = [0_usize; 4];
+        for _ in 0..ITERATIONS {
+            counts[sampling::top_p(&mut rng, &logits, 0.65, 1.0, &mut scratch)] += 1;
+        }
+        // 0.4 + 0.3 is the smallest prefix reaching 0.65.
+        assert!(counts[..2] == [0, 0]);
+        assert!(counts[2].abs_diff(expected) < expected / 10);
+        // A `p` of 1.0 and a `k` of everything keeps all of the logits.
+        let mut counts = [0_usize; 4];
+        for _ in 0..ITERATIONS {
+            counts[sampling::top_p(&mut rng, &logits, 1.0, 1.0, &mut scratch)] += 1;
+            counts[sampling::top_k(&mut rng, &logits, 10, 1.0, &mut scratch)] += 1;
+        }
+        for (i, &count) in counts.iter().enumerate() {
+            let expected = 2 * ITERATIONS * (i + 1) / 10;
+            assert!(count.abs_diff(expected) < expected / 5);
+        }
+        // Tiny temperatures are greedy, and masked logits are never chosen.
+        assert!(sampling::top_k(&mut rng, &logits, 4, 1e-6, &mut scratch) == 3);
+        let masked = [f32::NEG_INFINITY, 0.0, f32::NEG_INFINITY];
+        for _ in 0..1000 {
+            assert!(sampling::top_k(&mut rng, &masked, 3, 100.0, &mut [0; 3]) == 1);
+            assert!(sampling::top_p(&mut rng, &masked, 1.0, 100.0, &mut [0; 3]) == 1);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn gumbel() {
+        let mut rng = new_rng();
+        // The standard Gumbel distribution has a mean of the Euler-Mascheroni constant.
+        let mean = (0..ITERATIONS).map(|_| rng.f64_gumbel()).sum::<f64>() / ITERATIONS as f64;
+        assert!((mean - 0.5772156649).abs() < 0.05);
+        let scores = [1.0_f64.ln(), 3.0_f64.ln(), f64::NEG_INFINITY, 4.0_f64.ln()];
+        let mut counts = [0_usize; 4];
+        for _ in 0..ITERATIONS {
+            counts[sampling::argmax_with_gumbel(&mut rng, &scores).unwrap()] += 1;
+        }
+        assert!(counts[2] == 0);
+        for (i, weight) in [(0, 1), (1, 3), (3, 4)] {
+            let expected = ITERATIONS * weight / 8;
+            assert!(counts[i].abs_diff(expected) < expected / 5);
+        }
+        assert!(sampling::argmax_with_gumbel(&mut rng, &[f64::NEG_INFINITY]) == Some(0));
+    }
+
+    #[test]
+    fn health_tested() {
+        use entropy::*;
+
+        // Cutoffs from the exact binomial distribution, for a window of 512 samples.
+        let cutoffs = [
+            (31, 325),
+            (16, 190),
+            (11, 114),
+            (9, 71),
+            (7, 46),
+            (6, 31),
+            (6, 22),
+            (5, 16),
+        ];
+        for (h, &(rct, apt)) in (1..=8).zip(cutoffs.iter()) {
+            assert!(entropy::rct_cutoff(h) == rct && entropy::apt_cutoff(h) == apt);
+        }
+        let mut healthy = HealthTested::new(OsEntropy, 8);
+        for _ in 0..1000 {
+            healthy.fill(&mut [0; 64]).unwrap();
+        }
+        assert!(!healthy.has_failed());
+        // Four repetitions are fine at 8 bits per byte, but five aren't.
+        let mut rct = HealthTested::new(&[1_u8, 1, 1, 1, 2, 3, 4, 5][..], 8);
+        rct.fill(&mut [0; 8]).unwrap();
+        let mut rct = HealthTested::new(&[1_u8, 1, 1, 1, 1, 3, 4, 5][..], 8);
+        let mut buf = [0xff; 8];
+        assert!(rct.fill(&mut buf).unwrap_err().kind() == ErrorKind::HealthTest);
+        assert!(buf == [0; 8] && rct.has_failed());
+        // Every other byte being the same passes the repetition count test,
+        // but not the adaptive proportion test.
+        let pattern: [u8; 16] = core::array::from_fn(|i| [7, i as u8][i % 2]);
+        let mut apt = HealthTested::new(&pattern[..], 8);
+        assert!(apt.fill(&mut [0; 16]).is_ok());
+        assert!(apt.fill(&mut [0; 16]).is_err());
+        assert!(apt.fill(&mut [0; 1]).is_err());
+    }
+
+    #[test]
+    fn secure_self_test() {
+        SecureRng::self_test().unwrap();
+        // The embedded vector is what the generator itself produces from the same seed.
+        let mut rng = SecureRng::try_new_from(&mut &secure::KAT_SEED[..]).unwrap();
+        assert!(secure::KAT_WORDS.iter().all(|&w| rng.u64() == w));
+    }
+
+    #[cfg(all(feature = "deterministic-tests", feature = "std"))]
+    #[test]
+    fn deterministic_tests() {
+        // Other tests create generators concurrently, so only the membership
+        // in the sequence of streams can be checked.
+        let val = new_rng().u64();
+        let seed = std::env::var("YA_RAND_SEED").map_or(DETERMINISTIC_SEED, |s| s.parse().unwrap());
+        assert!((0..1 << 16).any(|stream| ShiroRng::new_stream(seed, stream).u64() == val));
+    }
 }
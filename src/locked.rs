@@ -0,0 +1,157 @@
+use crate::entropy::{EntropySource, OsEntropy};
+use crate::error::{Error, ErrorKind};
+use crate::rng::{Generator, SecureGenerator};
+use crate::secure::SecureRng;
+use crate::util;
+use core::fmt;
+use core::ptr::{self, NonNull};
+use core::slice;
+
+/// A [`SecureRng`] whose state lives in memory which is locked into RAM and
+/// excluded from core dumps.
+///
+/// The state is placed in its own anonymous mapping, which is locked using `mlock` so
+/// it can never be written to swap. On Linux and Android the mapping is also marked
+/// with `MADV_DONTDUMP`, and on FreeBSD with `MADV_NOCORE`, keeping the key and the
+/// buffered keystream out of core dumps. When dropped, the state is overwritten with
+/// zeros before the memory is unlocked and unmapped.
+///
+/// The generator is seeded directly inside of the locked memory, so its seed and
+/// buffered keystream are never copied through the stack of the thread creating it.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// // Locking can fail when the process exceeds `RLIMIT_MEMLOCK`.
+/// if let Ok(mut rng) = LockedSecureRng::try_new() {
+///     let key: [u8; 32] = rng.secure_array();
+///     assert!(key.into_iter().any(|v| v != 0));
+/// }
+/// ```
+pub struct LockedSecureRng {
+    rng: NonNull<SecureRng>,
+}
+
+// SAFETY: The mapping is exclusively owned, so this is equivalent to a `Box<SecureRng>`.
+unsafe impl Send for LockedSecureRng {}
+// SAFETY: Same as above.
+unsafe impl Sync for LockedSecureRng {}
+
+impl fmt::Debug for LockedSecureRng {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("all `LockedSecureRng` fields are private")
+    }
+}
+
+impl LockedSecureRng {
+    const LEN: usize = size_of::<SecureRng>();
+
+    /// Returns a reference to the wrapped generator.
+    #[inline]
+    pub fn inner(&self) -> &SecureRng {
+        // SAFETY: `rng` points to an initialized value which we exclusively own.
+        unsafe { self.rng.as_ref() }
+    }
+
+    /// Returns a mutable reference to the wrapped generator, allowing
+    /// methods such as [`SecureRng::set_word_pos`] to be used.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut SecureRng {
+        // SAFETY: Same as above.
+        unsafe { self.rng.as_mut() }
+    }
+
+    /// Creates a locked mapping which is large enough to hold a `SecureRng`.
+    fn map() -> Result<NonNull<SecureRng>, Error> {
+        const FAILURE: Error = Error::new(ErrorKind::MemoryLock);
+        // SAFETY: Creating a fresh anonymous mapping doesn't affect any existing memory.
+        let addr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                Self::LEN,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANON,
+                -1,
+                0,
+            )
+        };
+        if addr == libc::MAP_FAILED {
+            event!(error, "failed to map memory for LockedSecureRng");
+            return Err(FAILURE);
+        }
+        // SAFETY: `addr` is a valid mapping of `LEN` bytes, which is unmapped
+        // again if it can't be locked.
+        unsafe {
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            let advised = libc::madvise(addr, Self::LEN, libc::MADV_DONTDUMP) == 0;
+            #[cfg(target_os = "freebsd")]
+            let advised = libc::madvise(addr, Self::LEN, libc::MADV_NOCORE) == 0;
+            #[cfg(not(any(target_os = "linux", target_os = "android", target_os = "freebsd")))]
+            let advised = true;
+            if !advised || libc::mlock(addr, Self::LEN) != 0 {
+                event!(error, "failed to lock memory for LockedSecureRng");
+                libc::munmap(addr, Self::LEN);
+                return Err(FAILURE);
+            }
+        }
+        // Mappings are page aligned, which satisfies the alignment of `SecureRng`.
+        Ok(NonNull::new(addr.cast()).unwrap())
+    }
+}
+
+impl Drop for LockedSecureRng {
+    fn drop(&mut self) {
+        let addr = self.rng.as_ptr();
+        // SAFETY: `addr` points to an initialized value which is never used again,
+        // inside of a mapping of `LEN` bytes which we exclusively own.
+        unsafe {
+            ptr::drop_in_place(addr);
+            let bytes = addr.cast::<u8>();
+            // Padding within the generator is uninitialized, so every byte
+            // is set before the mapping is treated as a slice of bytes.
+            ptr::write_bytes(bytes, 0, Self::LEN);
+            util::wipe_slice(slice::from_raw_parts_mut(bytes, Self::LEN));
+            libc::munlock(addr.cast(), Self::LEN);
+            libc::munmap(addr.cast(), Self::LEN);
+        }
+    }
+}
+
+impl SecureGenerator for LockedSecureRng {
+    #[inline]
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.inner_mut().fill_bytes(dst);
+    }
+}
+
+impl Generator for LockedSecureRng {
+    #[inline]
+    fn try_new() -> Result<Self, Error> {
+        Self::try_new_from(&mut OsEntropy)
+    }
+
+    /// Fails with [`ErrorKind::MemoryLock`] if the memory couldn't be mapped
+    /// or locked, such as when the process would exceed `RLIMIT_MEMLOCK`.
+    fn try_new_from<S: EntropySource + ?Sized>(source: &mut S) -> Result<Self, Error> {
+        let rng = Self::map()?;
+        // SAFETY: `rng` is valid for writes and suitably aligned.
+        match unsafe { SecureRng::try_init_in_place(rng.as_ptr(), source) } {
+            Ok(()) => Ok(Self { rng }),
+            Err(err) => {
+                // SAFETY: `rng` is a mapping of `LEN` bytes which was never handed out.
+                unsafe {
+                    libc::munlock(rng.as_ptr().cast(), Self::LEN);
+                    libc::munmap(rng.as_ptr().cast(), Self::LEN);
+                }
+                Err(err)
+            }
+        }
+    }
+
+    #[inline]
+    fn u64(&mut self) -> u64 {
+        self.inner_mut().u64()
+    }
+}
@@ -0,0 +1,164 @@
+use crate::error::{Error, ErrorKind};
+use core::fmt;
+use core::str::FromStr;
+
+/// A seed of `N` bytes which can be written as, and parsed from, a short phrase of words.
+///
+/// Meant for seeds which need to be shared by humans, such as the seed of a game world
+/// or a reproducibility token in a bug report. Every byte of the seed is written as one
+/// word, followed by an extra checksum word, with all words separated by hyphens. A seed
+/// from [`Mnemonic::from_u64`] therefore takes nine words.
+///
+/// Parsing is case-insensitive and accepts hyphens, underscores, and whitespace as
+/// separators. Phrases with an unknown word, the wrong amount of words, or a checksum
+/// which doesn't match the rest of the words are rejected with [`ErrorKind::InvalidSeed`].
+/// The checksum catches every phrase where a single word was replaced by another.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// let seed = new_rng().u64();
+/// let phrase = Mnemonic::from_u64(seed).to_string();
+/// assert!(phrase.split('-').count() == 9);
+/// let parsed: Mnemonic<8> = phrase.to_uppercase().replace('-', " ").parse().unwrap();
+/// assert!(parsed.to_u64() == seed);
+/// let mut rng = Xoshiro256pp::new_with_seed(parsed.to_u64());
+///
+/// let err = "army-atom-aunt".parse::<Mnemonic<8>>().unwrap_err();
+/// assert!(err.kind() == ErrorKind::InvalidSeed);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Mnemonic<const N: usize> {
+    bytes: [u8; N],
+}
+
+impl<const N: usize> Mnemonic<N> {
+    /// Creates a mnemonic for the seed `bytes`.
+    #[inline]
+    pub const fn new(bytes: [u8; N]) -> Self {
+        Self { bytes }
+    }
+
+    /// Returns the seed bytes of the mnemonic.
+    #[inline]
+    pub const fn to_bytes(&self) -> [u8; N] {
+        self.bytes
+    }
+}
+
+impl Mnemonic<8> {
+    /// Creates a mnemonic for a 64-bit `seed`, such as one passed to
+    /// [`crate::SeedableGenerator::new_with_seed`].
+    #[inline]
+    pub const fn from_u64(seed: u64) -> Self {
+        Self::new(seed.to_be_bytes())
+    }
+
+    /// Returns the seed of the mnemonic as a `u64`.
+    #[inline]
+    pub const fn to_u64(&self) -> u64 {
+        u64::from_be_bytes(self.bytes)
+    }
+}
+
+impl Mnemonic<16> {
+    /// Creates a mnemonic for a 128-bit `seed`.
+    #[inline]
+    pub const fn from_u128(seed: u128) -> Self {
+        Self::new(seed.to_be_bytes())
+    }
+
+    /// Returns the seed of the mnemonic as a `u128`.
+    #[inline]
+    pub const fn to_u128(&self) -> u128 {
+        u128::from_be_bytes(self.bytes)
+    }
+}
+
+impl<const N: usize> fmt::Display for Mnemonic<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for &byte in &self.bytes {
+            f.write_str(WORDS[byte as usize])?;
+            f.write_str("-")?;
+        }
+        f.write_str(WORDS[checksum(&self.bytes) as usize])
+    }
+}
+
+impl<const N: usize> FromStr for Mnemonic<N> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const INVALID: Error = Error::new(ErrorKind::InvalidSeed);
+        let mut words = s
+            .split(|c: char| c == '-' || c == '_' || c.is_whitespace())
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                WORDS
+                    .iter()
+                    .position(|w| w.eq_ignore_ascii_case(word))
+                    .map(|i| i as u8)
+                    .ok_or(INVALID)
+            });
+        let mut bytes = [0; N];
+        for byte in &mut bytes {
+            *byte = words.next().ok_or(INVALID)??;
+        }
+        match (words.next(), words.next()) {
+            (Some(check), None) if check? == checksum(&bytes) => Ok(Self::new(bytes)),
+            _ => Err(INVALID),
+        }
+    }
+}
+
+/// CRC-8 with polynomial 0x07, which detects any single corrupted byte.
+const fn checksum(bytes: &[u8]) -> u8 {
+    let mut crc = 0_u8;
+    let mut i = 0;
+    while i < bytes.len() {
+        crc ^= bytes[i];
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        i += 1;
+    }
+    crc
+}
+
+/// One word for every possible byte value, chosen to be short and easy to tell apart.
+#[rustfmt::skip]
+const WORDS: [&str; 256] = [
+    "army", "atom", "aunt", "baby", "ball", "band", "bank", "barn", "beam", "bean", "bear", "bell",
+    "belt", "bike", "bird", "blue", "boat", "body", "bold", "bone", "book", "boot", "bowl", "brave",
+    "bread", "brick", "bride", "brush", "bulb", "cabin", "cake", "calm", "camel", "camp", "candy",
+    "cargo", "castle", "cedar", "chalk", "charm", "chef", "cherry", "chess", "cider", "city",
+    "clay", "cliff", "clock", "cloud", "coach", "coast", "cobra", "cocoa", "coin", "comet", "coral",
+    "corn", "couch", "crab", "crane", "crisp", "crow", "crown", "cube", "daisy", "dance", "delta",
+    "desk", "dime", "disk", "dock", "donut", "dove", "dragon", "dream", "drum", "duck", "dune",
+    "eagle", "earth", "easel", "echo", "elbow", "elder", "elm", "ember", "emu", "engine", "fable",
+    "falcon", "fancy", "farm", "feast", "fern", "ferry", "fiber", "field", "fig", "flag", "flame",
+    "flute", "foam", "forest", "fossil", "fox", "frog", "frost", "fudge", "gala", "garden", "gecko",
+    "gem", "ghost", "giant", "ginger", "glove", "goat", "gold", "grape", "guitar", "hammer",
+    "harbor", "hawk", "hazel", "helmet", "hero", "hill", "honey", "hook", "horse", "hotel", "igloo",
+    "iron", "island", "ivory", "jacket", "jade", "jaguar", "jelly", "jewel", "judge", "juice",
+    "jungle", "kayak", "kettle", "kite", "kiwi", "knot", "koala", "ladder", "lake", "lamp", "lava",
+    "lemon", "lily", "lion", "lizard", "llama", "lotus", "lunar", "magnet", "mango", "maple",
+    "marble", "meadow", "melon", "metal", "mint", "mirror", "monkey", "moose", "motor", "mouse",
+    "muffin", "nectar", "needle", "nest", "noodle", "north", "oasis", "ocean", "olive", "onion",
+    "opal", "orbit", "otter", "oven", "owl", "paddle", "panda", "paper", "parrot", "peach", "pearl",
+    "pencil", "pepper", "piano", "pilot", "planet", "plum", "pony", "prism", "puzzle", "quail",
+    "quartz", "quilt", "rabbit", "radar", "radio", "raven", "reef", "ribbon", "river", "robin",
+    "rocket", "ruby", "saddle", "salad", "salmon", "sand", "scarf", "shark", "shell", "silver",
+    "skate", "sled", "snail", "sofa", "spider", "sponge", "squid", "stone", "straw", "sugar",
+    "summit", "swan", "table", "tiger", "toast", "tulip", "tunnel", "turtle", "valley", "violin",
+    "wagon", "walnut", "whale", "wheat", "willow", "window", "wizard", "wolf", "yacht", "yogurt",
+    "zebra", "zinc",
+];
@@ -0,0 +1,234 @@
+use crate::rng::{Generator, fill_normal};
+use alloc::vec::Vec;
+
+/// A multivariate normal distribution, defined by a mean vector and a covariance matrix.
+///
+/// The Cholesky factor of the covariance matrix is computed once at construction,
+/// so each sample only costs one normal value per dimension and a triangular
+/// matrix-vector product. Sampling writes directly into a caller-provided
+/// slice, so no allocation happens after construction.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// let mut rng = new_rng();
+/// let mean = [1.0, -2.0];
+/// #[rustfmt::skip]
+/// let covariance = [
+///     4.0, 1.2,
+///     1.2, 1.0,
+/// ];
+/// let mvn = MultivariateNormal::new(&mean, &covariance).unwrap();
+/// let mut sample = [0.0; 2];
+/// mvn.sample(&mut rng, &mut sample);
+/// assert!(sample.iter().all(|v| v.is_finite()));
+///
+/// // Not positive-definite.
+/// assert!(MultivariateNormal::new(&mean, &[1.0, 2.0, 2.0, 1.0]).is_none());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultivariateNormal {
+    mean: Vec<f64>,
+    /// Lower-triangular Cholesky factor, stored row-major.
+    factor: Vec<f64>,
+}
+
+impl MultivariateNormal {
+    /// Creates a multivariate normal distribution with the given `mean`
+    /// and row-major `covariance` matrix.
+    ///
+    /// Only the lower triangle of `covariance` is read, since it's assumed to
+    /// be symmetric. Returns `None` if `covariance` isn't positive-definite.
+    ///
+    /// # Panics
+    ///
+    /// If the length of `covariance` isn't the square of the length of `mean`.
+    pub fn new(mean: &[f64], covariance: &[f64]) -> Option<Self> {
+        let n = mean.len();
+        assert!(
+            n.checked_mul(n) == Some(covariance.len()),
+            "`covariance` must be a square matrix matching the length of `mean`"
+        );
+        let mut factor = alloc::vec![0.0; covariance.len()];
+        for i in 0..n {
+            for j in 0..=i {
+                let dot = (0..j)
+                    .map(|k| factor[i * n + k] * factor[j * n + k])
+                    .sum::<f64>();
+                let remainder = covariance[i * n + j] - dot;
+                if i == j {
+                    if remainder <= 0.0 || remainder.is_nan() {
+                        return None;
+                    }
+                    factor[i * n + i] = remainder.sqrt();
+                } else {
+                    factor[i * n + j] = remainder / factor[j * n + j];
+                }
+            }
+        }
+        Some(Self {
+            mean: mean.to_vec(),
+            factor,
+        })
+    }
+
+    /// Returns the number of dimensions of the distribution.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.mean.len()
+    }
+
+    /// Returns the mean vector of the distribution.
+    #[inline]
+    pub fn mean(&self) -> &[f64] {
+        &self.mean
+    }
+
+    /// Fills `dst` with a single sample from the distribution.
+    ///
+    /// # Panics
+    ///
+    /// If the length of `dst` doesn't match [`MultivariateNormal::dim`].
+    pub fn sample<G: Generator>(&self, rng: &mut G, dst: &mut [f64]) {
+        let n = self.dim();
+        assert!(
+            dst.len() == n,
+            "`dst` must have the same length as the distribution's dimension"
+        );
+        fill_normal(rng, dst);
+        // Working backwards means the standard normal values in `dst`
+        // which are still needed are never overwritten.
+        for i in (0..n).rev() {
+            let row = &self.factor[i * n..=i * n + i];
+            let dot = row.iter().zip(&dst[..=i]).map(|(l, z)| l * z).sum::<f64>();
+            dst[i] = self.mean[i] + dot;
+        }
+    }
+}
+
+/// Returns a uniformly distributed 2x2 rotation matrix, in row-major order.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// let mut rng = new_rng();
+/// let [[a, b], [c, d]] = rotation_matrix_2d(&mut rng);
+/// assert!((a * d - b * c - 1.0).abs() < 1e-12);
+/// ```
+#[inline]
+pub fn rotation_matrix_2d<G: Generator>(rng: &mut G) -> [[f64; 2]; 2] {
+    let theta = rng.f64() * core::f64::consts::TAU;
+    let (sin, cos) = theta.sin_cos();
+    [[cos, -sin], [sin, cos]]
+}
+
+/// Returns a uniformly distributed 3x3 rotation matrix, in row-major order.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// let mut rng = new_rng();
+/// let m = rotation_matrix_3d(&mut rng);
+/// // Every row is a unit vector.
+/// for row in m {
+///     let len = row.iter().map(|v| v * v).sum::<f64>();
+///     assert!((len - 1.0).abs() < 1e-12);
+/// }
+/// ```
+pub fn rotation_matrix_3d<G: Generator>(rng: &mut G) -> [[f64; 3]; 3] {
+    // A normalized vector of 4 independent normal values is a uniformly
+    // distributed unit quaternion, which is then converted to a matrix.
+    let mut q = [0.0; 4];
+    let mut len_squared = 0.0;
+    while len_squared == 0.0 {
+        fill_normal(rng, &mut q);
+        len_squared = q.iter().map(|v| v * v).sum::<f64>();
+    }
+    let s = 2.0 / len_squared;
+    let [w, x, y, z] = q;
+    [
+        [
+            1.0 - s * (y * y + z * z),
+            s * (x * y - w * z),
+            s * (x * z + w * y),
+        ],
+        [
+            s * (x * y + w * z),
+            1.0 - s * (x * x + z * z),
+            s * (y * z - w * x),
+        ],
+        [
+            s * (x * z - w * y),
+            s * (y * z + w * x),
+            1.0 - s * (x * x + y * y),
+        ],
+    ]
+}
+
+/// Fills `dst` with a uniformly distributed (Haar) `n`x`n` orthogonal
+/// matrix, in row-major order.
+///
+/// This is the QR decomposition of a matrix of standard normal values,
+/// with signs corrected so that `R` has a positive diagonal, computed in-place
+/// using Gram-Schmidt orthogonalization. Unlike [`rotation_matrix_3d`],
+/// the determinant of the result is equally likely to be `1.0` or `-1.0`.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// const N: usize = 5;
+/// let mut rng = new_rng();
+/// let mut q = [0.0; N * N];
+/// random_orthogonal(&mut rng, N, &mut q);
+/// // Rows are orthonormal.
+/// for i in 0..N {
+///     for j in 0..N {
+///         let dot = (0..N).map(|k| q[i * N + k] * q[j * N + k]).sum::<f64>();
+///         let expected = if i == j { 1.0 } else { 0.0 };
+///         assert!((dot - expected).abs() < 1e-12);
+///     }
+/// }
+/// ```
+///
+/// # Panics
+///
+/// If the length of `dst` isn't `n * n`.
+#[inline(never)]
+pub fn random_orthogonal<G: Generator>(rng: &mut G, n: usize, dst: &mut [f64]) {
+    assert!(
+        n.checked_mul(n) == Some(dst.len()),
+        "`dst` must have a length of `n * n`"
+    );
+    fill_normal(rng, dst);
+    // Operating on rows is equivalent to operating on columns, since the
+    // transpose of a Haar-distributed matrix is also Haar-distributed,
+    // and rows are contiguous in memory.
+    for i in 0..n {
+        let (done, rest) = dst.split_at_mut(i * n);
+        let row = &mut rest[..n];
+        loop {
+            // Orthogonalizing twice keeps rounding errors from accumulating.
+            for _ in 0..2 {
+                for prev in done.chunks_exact(n) {
+                    let dot = prev.iter().zip(&*row).map(|(p, r)| p * r).sum::<f64>();
+                    row.iter_mut().zip(prev).for_each(|(r, p)| *r -= dot * p);
+                }
+            }
+            let len = row.iter().map(|v| v * v).sum::<f64>().sqrt();
+            // Only reachable with astronomically bad luck.
+            if len > f64::EPSILON {
+                row.iter_mut().for_each(|v| *v /= len);
+                break;
+            }
+            fill_normal(rng, row);
+        }
+    }
+}
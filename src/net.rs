@@ -0,0 +1,59 @@
+//! Random network hardware addresses.
+//!
+//! # Examples
+//!
+//! ```
+//! use ya_rand::*;
+//!
+//! let mut rng = new_rng();
+//! let mac = net::mac_address(&mut rng);
+//! assert!(mac[0] & 0b10 != 0);
+//! ```
+
+use crate::rng::Generator;
+
+/// Returns a random MAC address, in transmission order.
+///
+/// The locally administered bit of the first octet is set and the multicast bit is
+/// cleared, so the address is a valid unicast address which never collides with one
+/// assigned by a manufacturer. The remaining 46 bits are uniformly distributed.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// let mut rng = new_rng();
+/// let mac = net::mac_address(&mut rng);
+/// assert!(mac[0] & 0b10 != 0);
+/// assert!(mac[0] & 0b01 == 0);
+/// ```
+#[inline]
+pub fn mac_address(rng: &mut impl Generator) -> [u8; 6] {
+    let mut mac = rng.array::<6>();
+    mac[0] = (mac[0] | 0b10) & !0b01;
+    mac
+}
+
+/// Returns a MAC address made of the organizationally unique identifier `oui`,
+/// followed by three uniformly distributed bytes.
+///
+/// Useful for simulating hardware from a specific vendor. The bits of `oui`
+/// are used as-is, so they determine whether the address is unicast and
+/// universally administered.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// let mut rng = new_rng();
+/// let mac = net::oui_suffix(&mut rng, [0x00, 0x1b, 0x63]);
+/// assert!(mac[..3] == [0x00, 0x1b, 0x63]);
+/// ```
+#[inline]
+pub fn oui_suffix(rng: &mut impl Generator, oui: [u8; 3]) -> [u8; 6] {
+    let [a, b, c] = oui;
+    let [d, e, f] = rng.array();
+    [a, b, c, d, e, f]
+}
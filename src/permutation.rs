@@ -0,0 +1,156 @@
+use crate::rng::Generator;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+const ROUNDS: usize = 8;
+
+/// A random permutation of `0..len`, which is computed lazily one index at a time.
+///
+/// Since nothing is materialized, this uses a constant amount of memory regardless of
+/// `len`, which makes it possible to visit a massive keyspace in random order.
+///
+/// Internally, indices are mapped using a balanced Feistel network with keys drawn from
+/// a generator, using cycle-walking to stay within `0..len`. The permutation is
+/// unpredictable to anyone who doesn't know the keys, but it isn't uniformly distributed
+/// over all possible permutations, and it shouldn't be relied on for cryptographic purposes.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// let mut rng = new_rng();
+/// let permutation = LazyPermutation::new(&mut rng, 5_000_000_000);
+/// // Visit the first few elements of a random order over five billion items,
+/// // without ever allocating.
+/// for index in permutation.iter().take(10) {
+///     assert!(index < 5_000_000_000);
+/// }
+///
+/// let small = LazyPermutation::new(&mut rng, 10);
+/// let mut seen = [false; 10];
+/// small.iter().for_each(|i| seen[i as usize] = true);
+/// assert!(seen.iter().all(|&v| v));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LazyPermutation {
+    len: u64,
+    half_bits: u32,
+    keys: [u64; ROUNDS],
+}
+
+impl LazyPermutation {
+    /// Creates a random permutation of `0..len`, with keys drawn from `rng`.
+    pub fn new<G: Generator>(rng: &mut G, len: u64) -> Self {
+        // The Feistel network operates on the smallest even number of bits
+        // which can represent every index, so at most 3 out of every 4
+        // values in its domain need to be skipped by cycle-walking.
+        let bits = match len {
+            0 | 1 => 0,
+            _ => u64::BITS - (len - 1).leading_zeros(),
+        };
+        let half_bits = bits.div_ceil(2);
+        let keys = rng.array_u64();
+        Self {
+            len,
+            half_bits,
+            keys,
+        }
+    }
+
+    /// Returns the length of the permutation.
+    #[inline]
+    pub const fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if the permutation has a length of zero.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the value at position `index` of the permutation.
+    ///
+    /// # Panics
+    ///
+    /// If `index` isn't less than [`LazyPermutation::len`].
+    #[inline]
+    pub fn get(&self, index: u64) -> u64 {
+        assert!(index < self.len, "index out of bounds");
+        // Since `index` is part of the cycle being walked, and it's
+        // within the range, this loop always terminates.
+        let mut x = self.feistel(index);
+        while x >= self.len {
+            x = self.feistel(x);
+        }
+        x
+    }
+
+    /// Returns an iterator over all values of the permutation, in order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = u64> + '_ {
+        (0..self.len).map(|i| self.get(i))
+    }
+
+    /// Applies the Feistel network to `x`, which must fit in `2 * half_bits` bits.
+    #[inline]
+    fn feistel(&self, x: u64) -> u64 {
+        let mask = match self.half_bits {
+            0 => 0,
+            bits => u64::MAX >> (u64::BITS - bits),
+        };
+        let mut left = x >> self.half_bits;
+        let mut right = x & mask;
+        for key in self.keys {
+            (left, right) = (right, left ^ (round(right, key) & mask));
+        }
+        (left << self.half_bits) | right
+    }
+}
+
+/// Round function of the Feistel network.
+#[inline]
+fn round(x: u64, key: u64) -> u64 {
+    // Finalizer of SplitMix64.
+    let mut z = x ^ key;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+/// Returns a random Latin square of order `n`, in row-major order, which is
+/// **not** uniformly distributed.
+///
+/// Every row and every column of the square contains each value in `0..n` exactly once.
+/// The square is created by randomly permuting the rows, columns, and values of a cyclic
+/// Latin square. This only reaches a tiny fraction of the Latin squares of order `n` when
+/// `n` is greater than 3, so it's suited to things like randomizing the order of trials,
+/// but not to sampling Latin squares for statistical analysis of the squares themselves.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// let mut rng = new_rng();
+/// let square = latin_square_biased(&mut rng, 4);
+/// for row in square.chunks(4) {
+///     let mut sorted = row.to_vec();
+///     sorted.sort();
+///     assert!(sorted == [0, 1, 2, 3]);
+/// }
+/// ```
+#[cfg(feature = "alloc")]
+pub fn latin_square_biased<G: Generator>(rng: &mut G, n: usize) -> Vec<usize> {
+    let mut symbols = (0..n).collect::<Vec<_>>();
+    rng.shuffle(&mut symbols);
+    let mut square = (0..n * n)
+        .map(|i| symbols[(i / n + i % n) % n])
+        .collect::<Vec<_>>();
+    if n != 0 {
+        rng.shuffle_rows(&mut square, n);
+        rng.shuffle_columns(&mut square, n);
+    }
+    square
+}
@@ -0,0 +1,81 @@
+use crate::rng::Generator;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::f64::consts::{SQRT_2, TAU};
+
+/// Amount of candidates generated around an active point before it's retired.
+const ATTEMPTS: usize = 30;
+
+/// Generates a set of points within [0.0, `width`) x [0.0, `height`), with no two
+/// points being closer together than `min_dist`, using Bridson's algorithm.
+pub fn bridson<G: Generator>(rng: &mut G, width: f64, height: f64, min_dist: f64) -> Vec<[f64; 2]> {
+    assert!(
+        width >= 0.0 && width.is_finite() && height >= 0.0 && height.is_finite(),
+        "`width` and `height` must be finite and non-negative"
+    );
+    assert!(
+        min_dist > 0.0 && min_dist.is_finite(),
+        "`min_dist` must be finite and positive"
+    );
+    if width == 0.0 || height == 0.0 {
+        return Vec::new();
+    }
+    // Each cell is small enough to contain at most one point.
+    let cell = min_dist / SQRT_2;
+    let cols = (width / cell).ceil() as usize;
+    let rows = (height / cell).ceil() as usize;
+    let mut grid = vec![usize::MAX; cols * rows];
+    // Rounding can place a point just below `width` or `height` in a cell past
+    // the end of the grid, so the indices are clamped.
+    let cell_of = |[x, y]: [f64; 2]| {
+        (
+            ((x / cell) as usize).min(cols - 1),
+            ((y / cell) as usize).min(rows - 1),
+        )
+    };
+
+    let mut points = Vec::new();
+    let mut active = Vec::new();
+    let first = [rng.f64() * width, rng.f64() * height];
+    let (col, row) = cell_of(first);
+    grid[row * cols + col] = 0;
+    points.push(first);
+    active.push(0);
+
+    let min_dist_squared = min_dist * min_dist;
+    while !active.is_empty() {
+        let i = rng.bound(active.len() as u64) as usize;
+        let [px, py] = points[active[i]];
+        let mut found = false;
+        for _ in 0..ATTEMPTS {
+            // Uniformly distributed within the annulus of radii `min_dist` and 2 * `min_dist`.
+            let radius = min_dist * (1.0 + 3.0 * rng.f64()).sqrt();
+            let (sin, cos) = (rng.f64() * TAU).sin_cos();
+            let candidate = [px + radius * cos, py + radius * sin];
+            let [cx, cy] = candidate;
+            if !(0.0..width).contains(&cx) || !(0.0..height).contains(&cy) {
+                continue;
+            }
+            let (col, row) = cell_of(candidate);
+            let is_far_enough = (row.saturating_sub(2)..(row + 3).min(rows))
+                .flat_map(|r| (col.saturating_sub(2)..(col + 3).min(cols)).map(move |c| (r, c)))
+                .map(|(r, c)| grid[r * cols + c])
+                .filter(|&idx| idx != usize::MAX)
+                .all(|idx| {
+                    let [qx, qy] = points[idx];
+                    (qx - cx) * (qx - cx) + (qy - cy) * (qy - cy) >= min_dist_squared
+                });
+            if is_far_enough {
+                grid[row * cols + col] = points.len();
+                active.push(points.len());
+                points.push(candidate);
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            active.swap_remove(i);
+        }
+    }
+    points
+}
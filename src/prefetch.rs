@@ -0,0 +1,178 @@
+use crate::entropy::{EntropySource, OsEntropy};
+use crate::error::{Error, ErrorKind};
+use crate::rng::{Generator, SecureGenerator};
+use crate::secure::SecureRng;
+use crate::util;
+use core::{fmt, mem};
+use std::boxed::Box;
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::thread;
+
+/// Amount of `u64` values in each of the two buffers, which is 4 KiB.
+const PREFETCH_LEN_U64: usize = 512;
+
+type Buffer = Box<[u64; PREFETCH_LEN_U64]>;
+
+/// A [`SecureRng`] which refills its output buffer on a background thread.
+///
+/// Output is handed out from one buffer, while a dedicated thread fills the next one
+/// ahead of time (double buffering). When the current buffer runs out it's swapped
+/// with the already filled one, so the cost of generating ChaCha blocks is almost
+/// never paid by the thread asking for random data. Only when values are consumed
+/// faster than the background thread can produce them does a call have to wait.
+///
+/// Calls to [`Generator::u64`] produce the same values as a [`SecureRng`] created
+/// from the same entropy, and [`SecureGenerator::fill_bytes`] uses the little-endian
+/// bytes of those values, discarding any bytes left over after the last one.
+///
+/// The background thread stops once the generator is dropped, at which point
+/// both buffers are wiped.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// let mut rng = SecureRngPrefetch::new();
+/// let session_id: [u8; 16] = rng.secure_array();
+/// assert!(session_id.into_iter().any(|v| v != 0));
+/// ```
+pub struct SecureRngPrefetch {
+    buf: Buffer,
+    index: usize,
+    filled: Receiver<Buffer>,
+    spent: SyncSender<Buffer>,
+}
+
+impl fmt::Debug for SecureRngPrefetch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("all `SecureRngPrefetch` fields are private")
+    }
+}
+
+impl SecureRngPrefetch {
+    /// Moves `rng` to a new background thread, which is used to fill every buffer.
+    ///
+    /// Allows for a [`crate::ReseedPolicy`] to be configured, which is then applied
+    /// on the background thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let policy = ReseedPolicy::NEVER.with_max_bytes(1 << 20);
+    /// let mut rng = SecureRngPrefetch::new_with_rng(SecureRng::new().with_reseed_policy(policy));
+    /// assert!(rng.bound(10) < 10);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the background thread can't be spawned.
+    pub fn new_with_rng(rng: SecureRng) -> Self {
+        Self::spawn(rng).expect("spawning the prefetch thread should never fail")
+    }
+
+    fn spawn(mut rng: SecureRng) -> Result<Self, Error> {
+        let mut buf = Box::new([0; PREFETCH_LEN_U64]);
+        fill(&mut rng, &mut buf);
+        // Capacity for a single buffer, which is the one being filled in advance.
+        let (filled_tx, filled) = mpsc::sync_channel(1);
+        let (spent, spent_rx) = mpsc::sync_channel(1);
+        thread::Builder::new()
+            .name("ya-rand-prefetch".into())
+            .spawn(move || refill_loop(rng, filled_tx, spent_rx))
+            .map_err(|_| Error::new(ErrorKind::Unsupported))?;
+        Ok(Self {
+            buf,
+            index: 0,
+            filled,
+            spent,
+        })
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn swap_buffers(&mut self) {
+        let next = self
+            .filled
+            .recv()
+            .expect("the prefetch thread should never stop while the generator is alive");
+        let spent = mem::replace(&mut self.buf, next);
+        // Only fails if the background thread is gone, which is
+        // caught by the next call to this function.
+        let _ = self.spent.send(spent);
+        self.index = 0;
+    }
+}
+
+/// Fills `buf` with output from `rng`, in the same order as calling [`SecureRng::u64`].
+fn fill(rng: &mut SecureRng, buf: &mut [u64; PREFETCH_LEN_U64]) {
+    buf.fill_with(|| rng.u64());
+}
+
+/// Overwrites `buf` with zeros, in a way that won't be optimized out by the compiler.
+fn wipe(buf: &mut [u64; PREFETCH_LEN_U64]) {
+    // SAFETY: `u64` is valid as nothing more than a collection of bytes.
+    util::wipe_slice(unsafe { util::as_raw_bytes_mut(buf) });
+}
+
+/// Body of the background thread, which runs until the generator is dropped.
+fn refill_loop(mut rng: SecureRng, filled: SyncSender<Buffer>, spent: Receiver<Buffer>) {
+    let mut buf = Box::new([0; PREFETCH_LEN_U64]);
+    loop {
+        fill(&mut rng, &mut buf);
+        if let Err(mpsc::SendError(mut buf)) = filled.send(buf) {
+            wipe(&mut buf);
+            return;
+        }
+        match spent.recv() {
+            Ok(next) => buf = next,
+            Err(_) => return,
+        }
+    }
+}
+
+impl Drop for SecureRngPrefetch {
+    fn drop(&mut self) {
+        wipe(&mut self.buf);
+        // The background thread always has exactly one buffer in flight, so waiting
+        // for it takes at most a single refill, and lets it be wiped as well.
+        if let Ok(mut next) = self.filled.recv() {
+            wipe(&mut next);
+        }
+    }
+}
+
+impl SecureGenerator for SecureRngPrefetch {
+    #[inline]
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        for chunk in dst.chunks_mut(size_of::<u64>()) {
+            let bytes = self.u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+}
+
+impl Generator for SecureRngPrefetch {
+    #[inline]
+    fn try_new() -> Result<Self, Error> {
+        Self::try_new_from(&mut OsEntropy)
+    }
+
+    /// Fails with [`ErrorKind::Unsupported`] if the background thread can't be spawned.
+    #[inline]
+    fn try_new_from<S: EntropySource + ?Sized>(source: &mut S) -> Result<Self, Error> {
+        SecureRng::try_new_from(source).and_then(Self::spawn)
+    }
+
+    #[inline]
+    fn u64(&mut self) -> u64 {
+        if self.index == PREFETCH_LEN_U64 {
+            self.swap_buffers();
+        }
+        let ret = self.buf[self.index];
+        self.index += 1;
+        ret
+    }
+}
@@ -0,0 +1,279 @@
+//! Low-discrepancy (quasi-random) sequences.
+//!
+//! Unlike the output of a [`Generator`], the points of these sequences
+//! are deliberately spread as evenly as possible, which makes quasi-Monte
+//! Carlo integration converge at close to O(1/n) instead of O(1/sqrt(n)).
+//! Each sequence can optionally be scrambled using a [`Generator`], which
+//! preserves its uniformity while allowing independent replications
+//! to be used for error estimation.
+//!
+//! # Examples
+//!
+//! ```
+//! use ya_rand::quasi::Sobol;
+//!
+//! // Approximate pi using the first 2^16 points of a 2-dimensional Sobol sequence.
+//! const POINTS: usize = 1 << 16;
+//! let mut sobol = Sobol::new(2);
+//! let mut point = [0.0; 2];
+//! let mut in_circle = 0;
+//! for _ in 0..POINTS {
+//!     sobol.next_point(&mut point);
+//!     let [x, y] = point;
+//!     if x * x + y * y <= 1.0 {
+//!         in_circle += 1;
+//!     }
+//! }
+//! let approx = 4.0 * in_circle as f64 / POINTS as f64;
+//! assert!((approx - core::f64::consts::PI).abs() < 1e-3);
+//! ```
+
+use crate::rng::Generator;
+use crate::util;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Primitive polynomials and initial direction numbers for dimensions
+/// after the first, in the format (degree, coefficients, initial values).
+///
+/// Taken from the `new-joe-kuo-6.21201` table by Stephen Joe and Frances Kuo.
+const SOBOL_PARAMS: [(u32, u32, &[u64]); 20] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+    (6, 19, &[1, 1, 1, 15, 7, 5]),
+    (6, 22, &[1, 3, 1, 15, 13, 25]),
+    (6, 25, &[1, 1, 5, 5, 19, 61]),
+    (7, 1, &[1, 3, 7, 11, 23, 15, 103]),
+    (7, 4, &[1, 3, 7, 13, 13, 15, 69]),
+];
+
+/// Bases used by each dimension of the Halton sequence.
+const HALTON_PRIMES: [u32; 32] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 127, 131,
+];
+
+/// A Sobol sequence, using Gray code ordering.
+///
+/// Scrambling applies a random digital shift to each dimension, which keeps
+/// the net properties of the sequence intact.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sobol {
+    index: u64,
+    /// Direction numbers, with [`u64::BITS`] entries per dimension.
+    directions: Vec<u64>,
+    state: Vec<u64>,
+    shift: Vec<u64>,
+}
+
+impl Sobol {
+    /// The largest amount of dimensions supported by [`Sobol`].
+    pub const MAX_DIMS: usize = SOBOL_PARAMS.len() + 1;
+
+    /// Creates an unscrambled Sobol sequence with `dims` dimensions.
+    ///
+    /// The first point is always the origin.
+    ///
+    /// # Panics
+    ///
+    /// If `dims` is zero or greater than [`Sobol::MAX_DIMS`].
+    pub fn new(dims: usize) -> Self {
+        assert!(
+            (1..=Self::MAX_DIMS).contains(&dims),
+            "`dims` must be in the interval [1, `Sobol::MAX_DIMS`]"
+        );
+        const BITS: usize = u64::BITS as usize;
+        let mut directions = vec![0; dims * BITS];
+        for (k, v) in directions[..BITS].iter_mut().enumerate() {
+            *v = 1 << (BITS - 1 - k);
+        }
+        for (d, &(degree, coefficients, initial)) in SOBOL_PARAMS.iter().take(dims - 1).enumerate()
+        {
+            let v = &mut directions[(d + 1) * BITS..(d + 2) * BITS];
+            let s = degree as usize;
+            for (k, &m) in initial.iter().enumerate() {
+                v[k] = m << (BITS - 1 - k);
+            }
+            for k in s..BITS {
+                v[k] = v[k - s] ^ (v[k - s] >> s);
+                for j in 1..s {
+                    if (coefficients >> (s - 1 - j)) & 1 == 1 {
+                        v[k] ^= v[k - j];
+                    }
+                }
+            }
+        }
+        Self {
+            index: 0,
+            directions,
+            state: vec![0; dims],
+            shift: vec![0; dims],
+        }
+    }
+
+    /// Creates a Sobol sequence with `dims` dimensions, scrambled using `rng`.
+    ///
+    /// # Panics
+    ///
+    /// If `dims` is zero or greater than [`Sobol::MAX_DIMS`].
+    pub fn new_scrambled<G: Generator>(dims: usize, rng: &mut G) -> Self {
+        let mut sobol = Self::new(dims);
+        sobol.shift.iter_mut().for_each(|v| *v = rng.u64());
+        sobol
+    }
+
+    /// Returns the amount of dimensions of each point.
+    #[inline]
+    pub fn dims(&self) -> usize {
+        self.state.len()
+    }
+
+    /// Returns the index of the next point in the sequence.
+    #[inline]
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// Skips the next `n` points of the sequence.
+    pub fn skip(&mut self, n: u64) {
+        const BITS: usize = u64::BITS as usize;
+        self.index += n;
+        let gray = self.index ^ (self.index >> 1);
+        for (d, state) in self.state.iter_mut().enumerate() {
+            let v = &self.directions[d * BITS..(d + 1) * BITS];
+            *state = (0..BITS)
+                .filter(|&k| (gray >> k) & 1 == 1)
+                .fold(0, |acc, k| acc ^ v[k]);
+        }
+    }
+
+    /// Fills `dst` with the next point of the sequence.
+    ///
+    /// # Panics
+    ///
+    /// If the length of `dst` doesn't match [`Sobol::dims`].
+    pub fn next_point(&mut self, dst: &mut [f64]) {
+        const BITS: usize = u64::BITS as usize;
+        assert!(
+            dst.len() == self.dims(),
+            "`dst` must have the same length as the sequence's dimension"
+        );
+        let bit = self.index.trailing_ones() as usize;
+        for (d, (v, state)) in dst.iter_mut().zip(&mut self.state).enumerate() {
+            *v = util::u64_to_f64(*state ^ self.shift[d]);
+            *state ^= self.directions[d * BITS + bit];
+        }
+        self.index += 1;
+    }
+}
+
+/// A Halton sequence, which uses a different prime base for each dimension.
+///
+/// Scrambling applies a random permutation to the digits of each dimension.
+/// Zero is always mapped onto itself, so that the trailing digits of every
+/// point remain zero.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Halton {
+    index: u64,
+    /// Digit permutations, with one entry per digit of each base.
+    permutations: Vec<Vec<u8>>,
+}
+
+impl Halton {
+    /// The largest amount of dimensions supported by [`Halton`].
+    pub const MAX_DIMS: usize = HALTON_PRIMES.len();
+
+    /// Creates an unscrambled Halton sequence with `dims` dimensions.
+    ///
+    /// The first point is always the origin.
+    ///
+    /// # Panics
+    ///
+    /// If `dims` is zero or greater than [`Halton::MAX_DIMS`].
+    pub fn new(dims: usize) -> Self {
+        assert!(
+            (1..=Self::MAX_DIMS).contains(&dims),
+            "`dims` must be in the interval [1, `Halton::MAX_DIMS`]"
+        );
+        let permutations = HALTON_PRIMES[..dims]
+            .iter()
+            .map(|&base| (0..base as u8).collect())
+            .collect();
+        Self {
+            index: 0,
+            permutations,
+        }
+    }
+
+    /// Creates a Halton sequence with `dims` dimensions, scrambled using `rng`.
+    ///
+    /// # Panics
+    ///
+    /// If `dims` is zero or greater than [`Halton::MAX_DIMS`].
+    pub fn new_scrambled<G: Generator>(dims: usize, rng: &mut G) -> Self {
+        let mut halton = Self::new(dims);
+        for permutation in &mut halton.permutations {
+            rng.shuffle(&mut permutation[1..]);
+        }
+        halton
+    }
+
+    /// Returns the amount of dimensions of each point.
+    #[inline]
+    pub fn dims(&self) -> usize {
+        self.permutations.len()
+    }
+
+    /// Returns the index of the next point in the sequence.
+    #[inline]
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// Skips the next `n` points of the sequence.
+    #[inline]
+    pub fn skip(&mut self, n: u64) {
+        self.index += n;
+    }
+
+    /// Fills `dst` with the next point of the sequence.
+    ///
+    /// # Panics
+    ///
+    /// If the length of `dst` doesn't match [`Halton::dims`].
+    pub fn next_point(&mut self, dst: &mut [f64]) {
+        assert!(
+            dst.len() == self.dims(),
+            "`dst` must have the same length as the sequence's dimension"
+        );
+        for (v, permutation) in dst.iter_mut().zip(&self.permutations) {
+            let base = permutation.len() as u64;
+            let inv_base = 1.0 / base as f64;
+            let mut n = self.index;
+            let mut factor = inv_base;
+            let mut result = 0.0;
+            while n != 0 {
+                let digit = permutation[(n % base) as usize];
+                result += digit as f64 * factor;
+                n /= base;
+                factor *= inv_base;
+            }
+            // Rounding can very rarely push the result up to exactly 1.0.
+            *v = result.min(1.0 - f64::EPSILON / 2.0);
+        }
+        self.index += 1;
+    }
+}
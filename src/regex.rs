@@ -0,0 +1,145 @@
+use crate::rng::Generator;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use regex_syntax::hir::{Class, Hir, HirKind};
+
+/// Default value of [`RegexStrings::max_repeat`].
+const DEFAULT_MAX_REPEAT: u32 = 8;
+
+/// Generates random strings which match a regular expression.
+///
+/// Patterns use the syntax of the [`regex`](https://docs.rs/regex) crate. Every alternation
+/// branch is equally likely to be chosen, every character of a class is equally likely to be
+/// chosen, and repetitions use a uniformly distributed count. Unbounded repetitions
+/// (like `*` and `+`) are capped at [`RegexStrings::max_repeat`] extra repetitions.
+///
+/// Anchors and word boundaries don't produce any output, so patterns that rely on them
+/// to constrain their surroundings (like `a\bb`) can produce strings which don't match.
+///
+/// Keep in mind that, just like in the `regex` crate, classes like `\d` and `\w` are
+/// Unicode-aware by default. Use `[0-9]` or `(?-u:\d)` to restrict them to ASCII.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// let mut rng = new_rng();
+/// let plates = RegexStrings::from_regex("[A-Z]{3}-[0-9]{4}").unwrap();
+/// let plate = plates.sample(&mut rng);
+/// assert!(plate.len() == 8);
+/// assert!(plate.as_bytes()[3] == b'-');
+/// ```
+#[derive(Clone, Debug)]
+pub struct RegexStrings {
+    hir: Hir,
+    max_repeat: u32,
+}
+
+impl RegexStrings {
+    /// Attempts to parse `pattern` into a string generator.
+    #[inline]
+    pub fn from_regex(pattern: &str) -> Result<Self, Box<regex_syntax::Error>> {
+        let hir = regex_syntax::parse(pattern).map_err(Box::new)?;
+        Ok(Self {
+            hir,
+            max_repeat: DEFAULT_MAX_REPEAT,
+        })
+    }
+
+    /// Sets the maximum amount of extra repetitions produced by unbounded repetition
+    /// operators. For example, `a+` produces between 1 and `1 + max_repeat` characters.
+    #[inline]
+    pub fn max_repeat(mut self, max_repeat: u32) -> Self {
+        self.max_repeat = max_repeat;
+        self
+    }
+
+    /// Returns a random string which matches the pattern.
+    ///
+    /// # Panics
+    ///
+    /// If the pattern contains a character class which can't match anything, like `[^\s\S]`.
+    pub fn sample<G: Generator>(&self, rng: &mut G) -> String {
+        let mut bytes = Vec::new();
+        self.sample_into(rng, &self.hir, &mut bytes);
+        // The parser rejects patterns which can match invalid UTF-8.
+        String::from_utf8(bytes).expect("output of a UTF-8 pattern should be valid UTF-8")
+    }
+
+    fn sample_into<G: Generator>(&self, rng: &mut G, hir: &Hir, dst: &mut Vec<u8>) {
+        match hir.kind() {
+            HirKind::Empty | HirKind::Look(_) => {}
+            HirKind::Literal(literal) => dst.extend_from_slice(&literal.0),
+            HirKind::Class(Class::Unicode(class)) => {
+                let len_of = |start: char, end: char| {
+                    let len = end as u32 - start as u32 + 1;
+                    match (start as u32) < 0xD800 && 0xDFFF < end as u32 {
+                        true => len - 0x800,
+                        false => len,
+                    }
+                };
+                let total = class
+                    .ranges()
+                    .iter()
+                    .map(|r| len_of(r.start(), r.end()) as u64)
+                    .sum::<u64>();
+                assert!(
+                    total != 0,
+                    "pattern contains a class which can't match anything"
+                );
+                let mut index = rng.bound(total) as u32;
+                for range in class.ranges() {
+                    let len = len_of(range.start(), range.end());
+                    if index < len {
+                        // The iterator skips surrogates for us.
+                        let c = (range.start()..=range.end()).nth(index as usize).unwrap();
+                        let mut buf = [0; 4];
+                        dst.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                        return;
+                    }
+                    index -= len;
+                }
+                unreachable!("index should always fall within one of the ranges");
+            }
+            HirKind::Class(Class::Bytes(class)) => {
+                let total = class
+                    .ranges()
+                    .iter()
+                    .map(|r| (r.end() - r.start()) as u64 + 1)
+                    .sum::<u64>();
+                assert!(
+                    total != 0,
+                    "pattern contains a class which can't match anything"
+                );
+                let mut index = rng.bound(total) as u8;
+                for range in class.ranges() {
+                    let len = range.end() - range.start();
+                    if index <= len {
+                        dst.push(range.start() + index);
+                        return;
+                    }
+                    index -= len + 1;
+                }
+                unreachable!("index should always fall within one of the ranges");
+            }
+            HirKind::Repetition(repetition) => {
+                let max = repetition
+                    .max
+                    .unwrap_or(repetition.min.saturating_add(self.max_repeat));
+                let count =
+                    repetition.min as u64 + rng.bound_inclusive((max - repetition.min) as u64);
+                for _ in 0..count {
+                    self.sample_into(rng, &repetition.sub, dst);
+                }
+            }
+            HirKind::Capture(capture) => self.sample_into(rng, &capture.sub, dst),
+            HirKind::Concat(subs) => subs.iter().for_each(|sub| self.sample_into(rng, sub, dst)),
+            HirKind::Alternation(subs) => {
+                let sub = rng.choose(subs).expect("alternations are never empty");
+                self.sample_into(rng, sub, dst);
+            }
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use crate::rng::Generator;
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// A weighted random sample of fixed size, taken from a stream of unknown length.
+///
+/// Every item pushed into the reservoir ends up in the sample with probability
+/// proportional to its weight, exactly as if [`Generator::choose_multiple_weighted`]
+/// had been called on the entire stream. But only `capacity` items are ever stored,
+/// which makes it suitable for downsampling logs and telemetry.
+///
+/// This uses the A-ExpJ algorithm of Efraimidis and Spirakis, which only needs
+/// random values for the (logarithmically few) items actually inserted into the sample,
+/// instead of one for every item in the stream.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// let mut rng = new_rng();
+/// let mut reservoir = WeightedReservoir::new(10);
+/// for (i, line) in (0..10_000).map(|i| format!("log line {i}")).enumerate() {
+///     // Make errors far more likely to be kept.
+///     let weight = if i % 100 == 0 { 50.0 } else { 1.0 };
+///     reservoir.push(&mut rng, line, weight);
+/// }
+/// assert!(reservoir.len() == 10);
+/// let sample = reservoir.into_sorted_vec();
+/// assert!(sample.len() == 10);
+/// ```
+#[derive(Clone, Debug)]
+pub struct WeightedReservoir<T> {
+    capacity: usize,
+    heap: BinaryHeap<Entry<T>>,
+    skip: f64,
+}
+
+impl<T> WeightedReservoir<T> {
+    /// Creates an empty reservoir which holds a sample of (at most) `capacity` items.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            heap: BinaryHeap::with_capacity(capacity),
+            skip: 0.0,
+        }
+    }
+
+    /// Offers `item` to the reservoir, with the given `weight`.
+    ///
+    /// Items with a weight of zero are never included in the sample.
+    ///
+    /// # Panics
+    ///
+    /// If `weight` is negative, infinite, or NaN.
+    pub fn push<G: Generator>(&mut self, rng: &mut G, item: T, weight: f64) {
+        assert!(
+            weight >= 0.0 && weight.is_finite(),
+            "weights must be finite and non-negative"
+        );
+        if weight == 0.0 || self.capacity == 0 {
+            return;
+        }
+        if self.heap.len() < self.capacity {
+            // Keys are stored in the log domain, see `Generator::choose_multiple_weighted`.
+            let key = -rng.f64_exponential() / weight;
+            self.heap.push(Entry { key, item });
+            if self.heap.len() == self.capacity {
+                self.skip = self.next_skip(rng);
+            }
+            return;
+        }
+        self.skip -= weight;
+        if self.skip > 0.0 {
+            return;
+        }
+        // The new key has to beat the current minimum, so it's drawn from the
+        // conditional distribution of keys which are larger than it.
+        let min_key = self.min_key();
+        let threshold = (weight * min_key).exp();
+        let r = threshold + (1.0 - threshold) * rng.f64_nonzero();
+        let key = r.ln() / weight;
+        self.heap.pop();
+        self.heap.push(Entry { key, item });
+        self.skip = self.next_skip(rng);
+    }
+
+    /// Returns the amount of weight which must be pushed before the next insertion.
+    #[inline]
+    fn next_skip<G: Generator>(&self, rng: &mut G) -> f64 {
+        rng.f64_exponential() / -self.min_key()
+    }
+
+    /// Returns the smallest key in the sample.
+    #[inline]
+    fn min_key(&self) -> f64 {
+        self.heap.peek().map(|v| v.key).unwrap_or(f64::NEG_INFINITY)
+    }
+
+    /// Returns the maximum amount of items the sample can hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the amount of items currently in the sample.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns `true` if the sample contains no items.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns an iterator over the items in the sample, in arbitrary order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.heap.iter().map(|v| &v.item)
+    }
+
+    /// Returns the items in the sample, in arbitrary order.
+    #[inline]
+    pub fn into_vec(self) -> Vec<T> {
+        self.heap.into_iter().map(|v| v.item).collect()
+    }
+
+    /// Returns the items in the sample, ordered as if they had been drawn
+    /// one at a time without replacement from the entire stream.
+    #[inline]
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        // Entries are ordered in reverse, so ascending order puts the largest keys first.
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|v| v.item)
+            .collect()
+    }
+}
+
+/// An item of the sample and its key, ordered so that
+/// `BinaryHeap` keeps the smallest key at the top.
+#[derive(Clone, Debug)]
+struct Entry<T> {
+    key: f64,
+    item: T,
+}
+
+impl<T> PartialEq for Entry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T> Eq for Entry<T> {}
+
+impl<T> PartialOrd for Entry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Entry<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.key.total_cmp(&self.key)
+    }
+}
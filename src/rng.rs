@@ -1,12 +1,34 @@
+use crate::entropy::EntropySource;
+use crate::error::{Error, ErrorKind};
+use crate::shuffle::Swappable;
 use crate::util;
+use crate::variant::RandomVariant;
+use core::iter::FusedIterator;
+use core::ops::{Bound, RangeBounds};
 use core::ptr;
 
-#[cfg(all(feature = "alloc", feature = "secure"))]
+#[cfg(feature = "std")]
 use {
-    crate::encoding::Encoder,
-    alloc::{string::String, vec, vec::Vec},
+    crate::poisson,
+    std::collections::HashSet,
+    std::time::{Duration, SystemTime},
 };
 
+#[cfg(feature = "secure")]
+use core::{mem::MaybeUninit, slice};
+
+#[cfg(all(feature = "secure", any(feature = "alloc", feature = "heapless")))]
+use crate::encoding::Encoder;
+
+#[cfg(feature = "slug")]
+use crate::slug;
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, collections::BTreeSet, string::String, vec, vec::Vec};
+
+#[cfg(all(feature = "alloc", feature = "secure"))]
+use crate::token::{SecureBytes, SecureToken};
+
 const F64_MANT: u32 = f64::MANTISSA_DIGITS;
 const F32_MANT: u32 = f32::MANTISSA_DIGITS;
 const F64_MAX_PRECISE: u64 = 1 << F64_MANT;
@@ -16,6 +38,14 @@ const F32_DIVISOR: f32 = F32_MAX_PRECISE as f32;
 pub const ALPHANUMERIC: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
 
 /// Trait for RNGs that provide cryptographically secure data.
+///
+/// This is the minimal core a secure backend needs to implement. All of the helpers
+/// built on top of it, such as [`SecureGeneratorExt::text`], are provided by
+/// [`SecureGeneratorExt`], which is implemented for every `SecureGenerator`.
+///
+/// Implementing this trait is a promise that every value produced by `self`, both through
+/// [`SecureGenerator::fill_bytes`] and the methods of [`Generator`], is unpredictable
+/// to anyone who doesn't know the internal state, even after observing prior output.
 #[cfg(feature = "secure")]
 pub trait SecureGenerator: Generator {
     /// Fills `dst` with random data, which is safe to be used in cryptographic contexts.
@@ -31,7 +61,42 @@ pub trait SecureGenerator: Generator {
     /// assert!(data.into_iter().any(|v| v != 0));
     /// ```
     fn fill_bytes(&mut self, dst: &mut [u8]);
+}
 
+/// Helpers for producing secure data, available for every [`SecureGenerator`].
+///
+/// Only relies on the guarantees of [`SecureGenerator`], so any conforming backend gets
+/// these methods without implementing anything beyond [`SecureGenerator::fill_bytes`].
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// struct MyBackend(SecureRng);
+///
+/// impl Generator for MyBackend {
+///     fn try_new() -> Result<Self, Error> {
+///         SecureRng::try_new().map(Self)
+///     }
+///
+///     fn u64(&mut self) -> u64 {
+///         self.0.u64()
+///     }
+/// }
+///
+/// impl SecureGenerator for MyBackend {
+///     fn fill_bytes(&mut self, dst: &mut [u8]) {
+///         self.0.fill_bytes(dst);
+///     }
+/// }
+///
+/// let mut rng = MyBackend::new();
+/// let key: [u8; 32] = rng.secure_array();
+/// assert!(key.into_iter().any(|v| v != 0));
+/// ```
+#[cfg(feature = "secure")]
+pub trait SecureGeneratorExt: SecureGenerator {
     /// Fills `dst` with random data, which is safe to be used in cryptographic contexts.
     ///
     /// Differs from [`SecureGenerator::fill_bytes`] in that the underlying type of `dst`
@@ -71,6 +136,85 @@ pub trait SecureGenerator: Generator {
         self.fill_bytes(dst_as_bytes);
     }
 
+    /// Fills `dst` with random data, which is safe to be used in cryptographic contexts.
+    ///
+    /// Safe alternative to [`SecureGeneratorExt::fill_raw`], relying on the
+    /// [`bytemuck::Pod`] trait to guarantee that `T` is valid as nothing
+    /// more than a collection of bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng_secure();
+    /// let mut data = [[0_u32; 4]; 69];
+    /// rng.fill_pod(&mut data);
+    /// assert!(data.into_iter().flatten().any(|v| v != 0));
+    /// ```
+    #[cfg(feature = "bytemuck")]
+    #[inline]
+    fn fill_pod<T: bytemuck::Pod>(&mut self, dst: &mut [T]) {
+        self.fill_bytes(bytemuck::cast_slice_mut(dst));
+    }
+
+    /// Fills `dst` with random data, which is safe to be used in cryptographic contexts,
+    /// and returns `dst` as an initialized slice.
+    ///
+    /// Allows for large buffers to be initialized with random data without
+    /// first needing to initialize them with some placeholder value. The output
+    /// of [`Generator::u64`] is written directly into `dst`, so no intermediate
+    /// buffer is involved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng_secure();
+    /// let mut data = Box::new_uninit_slice(1 << 16);
+    /// let data = rng.fill_uninit(&mut data);
+    /// assert!(data.len() == 1 << 16);
+    /// assert!(data.iter().any(|&v| v != 0));
+    /// ```
+    #[inline(never)]
+    fn fill_uninit<'a>(&mut self, dst: &'a mut [MaybeUninit<u8>]) -> &'a mut [u8] {
+        for chunk in dst.chunks_mut(size_of::<u64>()) {
+            let bytes = self.u64().to_le_bytes();
+            // SAFETY: `chunk` is never longer than `bytes`, and
+            // `MaybeUninit<u8>` has the same layout as `u8`.
+            unsafe {
+                ptr::copy_nonoverlapping(bytes.as_ptr(), chunk.as_mut_ptr().cast(), chunk.len());
+            }
+        }
+        // SAFETY: Every element of `dst` has been initialized in the above loop.
+        unsafe { slice::from_raw_parts_mut(dst.as_mut_ptr().cast(), dst.len()) }
+    }
+
+    /// Returns an array filled with random data, which is safe to be used in
+    /// cryptographic contexts.
+    ///
+    /// Convenient for producing fixed-size keys, salts, and nonces
+    /// in a single expression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng_secure();
+    /// let key = rng.secure_array::<32>();
+    /// let nonce: [u8; 12] = rng.secure_array();
+    /// assert!(key.into_iter().any(|v| v != 0));
+    /// assert!(nonce.into_iter().any(|v| v != 0));
+    /// ```
+    #[inline]
+    fn secure_array<const N: usize>(&mut self) -> [u8; N] {
+        let mut arr = [0; N];
+        self.fill_bytes(&mut arr);
+        arr
+    }
+
     /// Generates a random `String` with length `len`, using the provided
     /// `Encoder` to determine character set and minimum secure length. Because
     /// character sets can only contain valid ascii values, the length of the created
@@ -87,6 +231,12 @@ pub trait SecureGenerator: Generator {
     /// Originally inspired by golang's addition of [`rand.Text`] in release 1.24,
     /// but altered to be encoding/length generic and unbiased for non-trivial bases.
     ///
+    /// When SSSE3 is enabled at compile time (e.g. via `target-cpu=native`), long strings
+    /// using an encoding with 16 or 64 characters are generated using SIMD table lookups.
+    /// Scratch buffers of random bytes used along the way are zeroed before returning,
+    /// so the returned `String` is the only copy of the text. Use
+    /// [`SecureGeneratorExt::token`] for a value which is also wiped when dropped.
+    ///
     /// [`rand.Text`]:
     /// https://cs.opensource.google/go/go/+/refs/tags/go1.24.0:src/crypto/rand/text.go
     ///
@@ -112,35 +262,214 @@ pub trait SecureGenerator: Generator {
     #[cfg(feature = "alloc")]
     #[inline(never)]
     fn text<E: Encoder>(&mut self, len: usize) -> String {
-        const BYTE_VALUES: usize = 1 << u8::BITS;
         // Force all values of the vector to be initialized to a
         // non-zero value. This guarantees all the allocated memory
         // will be page-faulted and can massively improve performance
         // when encoding long strings.
         let mut bytes = vec![u8::MAX; len.max(E::MIN_LEN)];
-        if BYTE_VALUES.is_multiple_of(E::CHARSET.len()) {
-            self.fill_bytes(&mut bytes);
-            // Directly map each random u8 to a character in the set.
-            // This approach is extremely efficient, but only produces
-            // unbiased random sequences when the length of the current
-            // `CHARSET` is divisible by the amount of possible u8 values,
-            // which is why we need a fallback approach.
-            for cur in &mut bytes {
-                let random_index = *cur as usize;
-                *cur = E::CHARSET[random_index % E::CHARSET.len()];
-            }
-        } else {
-            // Alternative approach that's potentially much slower,
-            // but always produces unbiased results.
-            // The unwrap gets optimized out since rust can see that
-            // `E::CHARSET` has a non-zero length.
-            bytes.fill_with(|| *self.choose(E::CHARSET).unwrap());
-        }
+        fill_text::<E, _>(self, &mut bytes);
         // SAFETY: All provided encoders only use ascii values, and
         // custom `Encoder` implementations agree to do the same when
         // implementing the trait.
         unsafe { String::from_utf8_unchecked(bytes) }
     }
+
+    /// Identical to [`SecureGeneratorExt::text`], but the result is a fixed-capacity
+    /// `heapless::String` with a length of `N`, so no allocation is required.
+    ///
+    /// Values of `N` which are less than the minimum secure length of the
+    /// `Encoder` being used will fail to compile.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    /// use ya_rand::encoding::{Base32, Encoder};
+    ///
+    /// let mut rng = new_rng_secure();
+    /// let s = rng.text_heapless::<Base32, 32>();
+    /// assert!(s.len() == 32);
+    /// assert!(s.bytes().all(|c| Base32::CHARSET.contains(&c)));
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use ya_rand::*;
+    /// use ya_rand::encoding::Base32;
+    ///
+    /// let mut rng = new_rng_secure();
+    /// // Too short to be secure.
+    /// let s = rng.text_heapless::<Base32, 8>();
+    /// ```
+    #[cfg(feature = "heapless")]
+    #[inline(never)]
+    fn text_heapless<E: Encoder, const N: usize>(&mut self) -> heapless::String<N> {
+        const {
+            assert!(
+                N >= E::MIN_LEN,
+                "`N` must be at least the minimum secure length of `E`"
+            )
+        };
+        // Filled in place, so no copy of the text is left behind on the stack.
+        let mut bytes = heapless::Vec::from_array([u8::MAX; N]);
+        fill_text::<E, _>(self, &mut bytes);
+        // SAFETY: Same reasoning as in `text`.
+        unsafe { heapless::String::from_utf8_unchecked(bytes) }
+    }
+
+    /// Identical to [`SecureGeneratorExt::text`], but the result is wrapped
+    /// in a [`SecureToken`], which guards against the value being leaked
+    /// through logging, timing-sensitive comparisons, or lingering memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    /// use ya_rand::encoding::{Base62, Encoder};
+    ///
+    /// let mut rng = new_rng_secure();
+    /// let token = rng.token::<Base62>(0);
+    /// assert!(token.as_str().len() == Base62::MIN_LEN);
+    /// assert!(token.as_str().chars().all(|c| c.is_ascii_alphanumeric()));
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn token<E: Encoder>(&mut self, len: usize) -> SecureToken {
+        SecureToken::from(self.text::<E>(len))
+    }
+
+    /// Returns `len` random bytes wrapped in a [`SecureBytes`], which guards against
+    /// the value being leaked through logging, timing-sensitive comparisons, or
+    /// lingering memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng_secure();
+    /// let key = rng.token_bytes(32);
+    /// assert!(key.as_bytes().len() == 32);
+    /// assert!(key != rng.token_bytes(32));
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn token_bytes(&mut self, len: usize) -> SecureBytes {
+        let mut bytes = vec![0; len];
+        self.fill_bytes(&mut bytes);
+        SecureBytes::from(bytes)
+    }
+
+    /// Returns a random probable prime which is exactly `bit_count` bits long.
+    ///
+    /// Candidates are screened with trial division by small primes, then
+    /// subjected to 40 rounds of Miller-Rabin using witnesses drawn from
+    /// `self`, giving a worst-case probability of a composite slipping
+    /// through of less than 2<sup>-80</sup>.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng_secure();
+    /// let p = rng.prime_bits(256);
+    /// assert!(p.bits() == 256);
+    /// assert!(p.bit(0));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `bit_count` is less than 2.
+    #[cfg(feature = "num-bigint")]
+    #[inline(never)]
+    fn prime_bits(&mut self, bit_count: u64) -> num_bigint::BigUint {
+        assert!(bit_count >= 2, "`bit_count` must be at least 2");
+        loop {
+            let mut candidate = self.biguint_bits(bit_count);
+            candidate.set_bit(bit_count - 1, true);
+            if bit_count > 2 {
+                candidate.set_bit(0, true);
+            }
+            if is_probable_prime(self, &candidate) {
+                return candidate;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "secure")]
+impl<G: SecureGenerator> SecureGeneratorExt for G {}
+
+/// Returns `true` if `n` passes trial division and the Miller-Rabin test,
+/// with witnesses being selected using `rng`.
+#[cfg(all(feature = "secure", feature = "num-bigint"))]
+fn is_probable_prime<G: SecureGenerator>(rng: &mut G, n: &num_bigint::BigUint) -> bool {
+    use num_bigint::BigUint;
+    const SMALL_PRIMES: [u32; 54] = [
+        2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89,
+        97, 101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179, 181,
+        191, 193, 197, 199, 211, 223, 227, 229, 233, 239, 241, 251,
+    ];
+    const ROUNDS: usize = 40;
+    for p in SMALL_PRIMES {
+        if *n == BigUint::from(p) {
+            return true;
+        }
+        if n % p == BigUint::ZERO {
+            return false;
+        }
+    }
+    // At this point `n` is guaranteed to be larger than 251.
+    let one = BigUint::from(1_u32);
+    let n_minus_one = n - 1_u32;
+    let s = n_minus_one.trailing_zeros().unwrap();
+    let d = &n_minus_one >> s;
+    let witness_range = n - 3_u32;
+    'witness: for _ in 0..ROUNDS {
+        let a = rng.biguint_below(&witness_range) + 2_u32;
+        let mut x = a.modpow(&d, n);
+        if x == one || x == n_minus_one {
+            continue;
+        }
+        for _ in 1..s {
+            x = &x * &x % n;
+            if x == n_minus_one {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// Fills `dst` with characters randomly selected from the `CHARSET` of `E`.
+#[cfg(all(feature = "secure", any(feature = "alloc", feature = "heapless")))]
+#[inline]
+fn fill_text<E: Encoder, G: SecureGenerator>(rng: &mut G, dst: &mut [u8]) {
+    if E::CHARSET.len().is_power_of_two() {
+        #[cfg(all(target_arch = "x86_64", target_feature = "ssse3"))]
+        let dst = crate::simd::fill_text(E::CHARSET, rng, dst);
+        // Every group of `bits` random bits directly maps to a character in the set,
+        // so there's no bias and no modulo. Only the bits which are actually needed
+        // get consumed (6 per character for Base64 instead of a whole byte), with
+        // the leftover bits of each `u64` being discarded.
+        let bits = E::CHARSET.len().trailing_zeros();
+        let mask = (1 << bits) - 1;
+        let chars_per_u64 = (u64::BITS / bits.max(1)) as usize;
+        for chunk in dst.chunks_mut(chars_per_u64) {
+            let mut random_bits = rng.u64();
+            for cur in chunk {
+                *cur = E::CHARSET[(random_bits & mask) as usize];
+                random_bits >>= bits;
+            }
+        }
+    } else {
+        // Alternative approach that's potentially much slower,
+        // but always produces unbiased results.
+        // The unwrap gets optimized out since rust can see that
+        // `E::CHARSET` has a non-zero length.
+        dst.fill_with(|| *rng.choose(E::CHARSET).unwrap());
+    }
 }
 
 /// Trait for RNGs that can be created from a user-provided seed.
@@ -155,6 +484,9 @@ pub trait SeedableGenerator: Generator + Default {
     /// If you have a scenario where you really do need a set seed, prefer using the
     /// `Default` implementation of the desired generator.
     ///
+    /// The non-cryptographic generators in this crate also provide an inherent `const`
+    /// version of this method, for creating generators in `const` and `static` contexts.
+    ///
     /// # Examples
     ///
     /// ```
@@ -170,6 +502,85 @@ pub trait SeedableGenerator: Generator + Default {
     /// assert!(result1 == result2);
     /// ```
     fn new_with_seed(seed: u64) -> Self;
+
+    /// Creates a generator for stream number `stream_id`, derived from `seed`.
+    ///
+    /// Intended for distributed computations, where every worker needs its own
+    /// reproducible stream, and all of them should follow from a single configured seed.
+    /// The full state of the generator is derived from both values using a mixing
+    /// function, so different pairs produce unrelated generators.
+    ///
+    /// Generators which don't implement [`Generator::try_new_from`] fall back to
+    /// [`SeedableGenerator::new_with_seed`] with a mix of both values, which allows
+    /// for collisions between streams once there are billions of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let seed = 69;
+    /// let mut workers = (0..4).map(|id| ShiroRng::new_stream(seed, id)).collect::<Vec<_>>();
+    /// let mut again = ShiroRng::new_stream(seed, 2);
+    /// assert!(workers[2].u64() == again.u64());
+    /// assert!(workers[0].u64() != workers[1].u64());
+    /// ```
+    #[inline]
+    fn new_stream(seed: u64, stream_id: u64) -> Self {
+        let mut source = util::StreamSource::new(seed, stream_id);
+        match Self::try_new_from(&mut source) {
+            Ok(rng) => rng,
+            Err(_) => Self::new_with_seed(util::StreamSource::new(seed, stream_id).u64()),
+        }
+    }
+
+    /// Consumes the generator, deriving two new generators from its output.
+    ///
+    /// Along with [`SeedableGenerator::fold_in`], this allows for treating generators
+    /// like the keys of JAX: instead of passing a single generator through a computation,
+    /// every part of the computation receives its own generator derived from a parent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let (mut terrain, mut monsters) = ShiroRng::new_with_seed(69).split();
+    /// assert!(terrain.u64() != monsters.u64());
+    /// ```
+    #[inline]
+    fn split(mut self) -> (Self, Self) {
+        let left = Self::new_stream(self.u64(), self.u64());
+        let right = Self::new_stream(self.u64(), self.u64());
+        (left, right)
+    }
+
+    /// Derives a new generator from the current state of the generator and `data`,
+    /// without advancing it.
+    ///
+    /// Since the generator isn't modified, the derived generators only depend on the
+    /// structure of the data rather than the order in which they're created. This makes
+    /// per-entity procedural generation reproducible regardless of iteration order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let world = ShiroRng::new_with_seed(69);
+    /// let chunk_a = world.fold_in(12).u64();
+    /// let chunk_b = world.fold_in(34).u64();
+    /// // Visiting the chunks in a different order produces the same results.
+    /// assert!(world.fold_in(34).u64() == chunk_b);
+    /// assert!(world.fold_in(12).u64() == chunk_a);
+    /// ```
+    #[inline]
+    fn fold_in(&self, data: u64) -> Self
+    where
+        Self: Clone,
+    {
+        Self::new_stream(self.clone().u64(), data)
+    }
 }
 
 /// Base trait that all RNGs must implement.
@@ -183,11 +594,26 @@ pub trait Generator: Sized {
     ///
     /// Stick to using [`crate::new_rng`], unless you really need a generator of a
     /// different type (you probably don't), then use `new` on your desired type.
-    fn try_new() -> Result<Self, getrandom::Error>;
+    fn try_new() -> Result<Self, Error>;
 
     /// Returns a uniformly distributed `u64` in the interval [0, 2<sup>64</sup>).
     fn u64(&mut self) -> u64;
 
+    /// Creates a generator using randomness provided by `source`.
+    ///
+    /// Most users should never need this, but it allows generators to be seeded from
+    /// sources other than the OS, or from multiple sources at once using an
+    /// [`EntropyPool`](crate::entropy::EntropyPool).
+    ///
+    /// All generators provided by this crate implement this method. The default
+    /// implementation always fails with [`ErrorKind::Unsupported`], so
+    /// existing implementations of this trait continue to work unchanged.
+    #[inline]
+    fn try_new_from<S: EntropySource + ?Sized>(source: &mut S) -> Result<Self, Error> {
+        let _ = source;
+        Err(Error::new(ErrorKind::Unsupported))
+    }
+
     /// Creates a generator using randomness provided by the OS.
     ///
     /// It is recommended to use the top-level [`crate::new_rng`] instead
@@ -204,11 +630,11 @@ pub trait Generator: Sized {
     /// let mut rng2 = ShiroRng::new();
     /// // Even more explicit
     /// let mut rng3 = Xoshiro256pp::new();
+    /// let mut rng4 = Xoshiro256pp::new();
     /// // Since these are all created using OS entropy, the odds of
     /// // their initial states colliding is vanishingly small.
     /// assert!(rng1 != rng2);
-    /// assert!(rng1 != rng3);
-    /// assert!(rng2 != rng3);
+    /// assert!(rng3 != rng4);
     /// ```
     ///
     /// # Safety
@@ -219,9 +645,40 @@ pub trait Generator: Sized {
     ///
     /// Since Windows 10 this function is infallible, thanks to modern Windows versions adopting
     /// a user-space cryptographic architecture that can't fail during runtime.
+    ///
+    /// It will also panic for generators which can't be created from entropy at all,
+    /// meaning their [`Generator::try_new`] fails with [`ErrorKind::Unsupported`].
     #[inline]
     fn new() -> Self {
-        Self::try_new().expect("retrieving random data from the operating system should never fail")
+        match Self::try_new() {
+            Ok(rng) => rng,
+            Err(err) if err.kind() == ErrorKind::Unsupported => panic!(
+                "`{}` can't be created using entropy from the operating system",
+                core::any::type_name::<Self>()
+            ),
+            Err(err) => panic!("retrieving random data from the operating system failed: {err}"),
+        }
+    }
+
+    /// Returns an infinite iterator over the output of [`Generator::u64`],
+    /// which borrows the generator instead of consuming it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let vals = rng.by_ref_iter().take(10).collect::<Vec<_>>();
+    /// assert!(vals.len() == 10);
+    /// let pairs = rng.by_ref_iter().zip(0..3).count();
+    /// assert!(pairs == 3);
+    /// // Still usable afterwards.
+    /// rng.u64();
+    /// ```
+    #[inline]
+    fn by_ref_iter(&mut self) -> GeneratorIter<'_, Self> {
+        GeneratorIter(self)
     }
 
     /// Returns a uniformly distributed `usize` in the interval \[0, `usize::MAX`\].
@@ -256,6 +713,42 @@ pub trait Generator: Sized {
         self.u64() >> (u64::BITS - bit_count.min(u64::BITS))
     }
 
+    /// Returns an array of uniformly distributed `u8` values.
+    ///
+    /// Every call to [`Generator::u64`] provides the data for 8 elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let salt = rng.array::<16>();
+    /// let other: [u8; 16] = rng.array();
+    /// assert!(salt != other);
+    /// ```
+    #[inline]
+    fn array<const N: usize>(&mut self) -> [u8; N] {
+        let mut arr = [0; N];
+        for chunk in arr.chunks_mut(size_of::<u64>()) {
+            let bytes = self.u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+        arr
+    }
+
+    /// Returns an array of uniformly distributed `u64` values.
+    #[inline]
+    fn array_u64<const N: usize>(&mut self) -> [u64; N] {
+        core::array::from_fn(|_| self.u64())
+    }
+
+    /// Returns an array of uniformly distributed `f64` values in the interval [0.0, 1.0).
+    #[inline]
+    fn array_f64<const N: usize>(&mut self) -> [f64; N] {
+        core::array::from_fn(|_| self.f64())
+    }
+
     /// A simple coinflip, returning a `bool` that has a 50% chance of being true.
     ///
     /// # Examples
@@ -284,6 +777,36 @@ pub trait Generator: Sized {
         self.bits(1) == 1
     }
 
+    /// Fills `dst` with independent `bool` values, each of which has a probability
+    /// of `p` of being true.
+    ///
+    /// The probability is converted into a threshold once, so every value only costs a
+    /// single call to [`Generator::u64`] and a comparison. Values of `p` at or below `0.0`
+    /// (including NaN) never produce `true`, and values at or above `1.0` always do.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let mut dropout = [false; 1000];
+    /// rng.fill_bool(&mut dropout, 0.1);
+    /// let dropped = dropout.iter().filter(|&&b| b).count();
+    /// assert!(dropped < 200);
+    /// ```
+    #[inline]
+    fn fill_bool(&mut self, dst: &mut [bool], p: f64) {
+        if p >= 1.0 {
+            dst.fill(true);
+            return;
+        }
+        // 2^64, scaling `p` into the full range of `u64`. Float to int
+        // casts saturate, with NaN becoming 0.
+        let threshold = (p * 18446744073709551616.0) as u64;
+        dst.fill_with(|| self.u64() < threshold);
+    }
+
     /// Returns a uniformly distributed `u64` in the interval [0, `max`).
     ///
     /// Using [`Generator::bits`] when `max` happens to be a power of 2
@@ -306,20 +829,13 @@ pub trait Generator: Sized {
     #[inline]
     fn bound(&mut self, max: u64) -> u64 {
         // Lemire's nearly divisionless method: https://arxiv.org/abs/1805.10941.
-        let (mut high, mut low) = util::wide_mul(self.u64(), max);
-        match low < max {
-            false => {
-                // TODO: Use the `unlikely` hint when it get stabilized
-                // to indicate this branch is unlikely.
-            }
-            true => {
-                // The dreaded division.
-                let threshold = max.wrapping_neg() % max;
-                while low < threshold {
-                    (high, low) = util::wide_mul(self.u64(), max);
-                }
-            }
-        }
+        let (high, low) = util::wide_mul(self.u64(), max);
+        let high = match low < max {
+            false => high,
+            // Kept out of line so the common case stays small enough to inline
+            // everywhere, until the `unlikely` hint gets stabilized.
+            true => bound_rejection(self, max, high, low),
+        };
         debug_assert!(
             (max != 0 && high < max) || high == 0,
             "BUG: assertion should be unreachable"
@@ -350,7 +866,8 @@ pub trait Generator: Sized {
 
     /// Returns a uniformly distributed `i64` in the interval [`min`, `max`)
     ///
-    /// It is expected that `min` < `max`.
+    /// It is expected that `min` < `max`. See [`Generator::checked_range`]
+    /// for a version which validates its arguments.
     #[inline]
     fn range(&mut self, min: i64, max: i64) -> i64 {
         let delta = max.abs_diff(min);
@@ -359,33 +876,458 @@ pub trait Generator: Sized {
 
     /// Returns a uniformly distributed `i64` in the interval \[`min`, `max`\]
     ///
-    /// It is expected that `min` <= `max` and `max` != `i64::MAX`.
+    /// It is expected that `min` <= `max` and `max` != `i64::MAX`. See
+    /// [`Generator::checked_range_inclusive`] for a version which validates its arguments.
     #[inline]
     fn range_inclusive(&mut self, min: i64, max: i64) -> i64 {
         self.range(min, max + 1)
     }
 
-    /// Returns a uniformly distributed `f64` in the interval [0.0, 1.0).
-    #[inline]
-    fn f64(&mut self) -> f64 {
-        self.bits(F64_MANT) as f64 / F64_DIVISOR
-    }
-
-    /// Returns a uniformly distributed `f32` in the interval [0.0, 1.0).
+    /// Checked version of [`Generator::bound`], returning a uniformly
+    /// distributed `u64` in the interval [0, `max`).
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`ErrorKind::InvalidParameter`] if `max` is 0,
+    /// since the interval would be empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// assert!(rng.checked_bound(10).unwrap() < 10);
+    /// let err = rng.checked_bound(0).unwrap_err();
+    /// assert!(err.kind() == ErrorKind::InvalidParameter);
+    /// ```
     #[inline]
-    fn f32(&mut self) -> f32 {
-        self.bits(F32_MANT) as f32 / F32_DIVISOR
+    fn checked_bound(&mut self, max: u64) -> Result<u64, Error> {
+        match max {
+            0 => Err(Error::new(ErrorKind::InvalidParameter)),
+            _ => Ok(self.bound(max)),
+        }
     }
 
-    /// Returns a uniformly distributed `f64` in the interval (0.0, 1.0].
+    /// Checked version of [`Generator::range`], returning a uniformly
+    /// distributed `i64` in the interval [`min`, `max`).
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`ErrorKind::InvalidParameter`] unless `min` < `max`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let val = rng.checked_range(-5, 5).unwrap();
+    /// assert!(-5 <= val && val < 5);
+    /// assert!(rng.checked_range(5, 5).is_err());
+    /// ```
     #[inline]
-    fn f64_nonzero(&mut self) -> f64 {
-        // Interval of (0, 2^53]
-        let nonzero = self.bits(F64_MANT) + 1;
-        nonzero as f64 / F64_DIVISOR
+    fn checked_range(&mut self, min: i64, max: i64) -> Result<i64, Error> {
+        if min >= max {
+            return Err(Error::new(ErrorKind::InvalidParameter));
+        }
+        let delta = max.abs_diff(min);
+        Ok(min.wrapping_add_unsigned(self.bound(delta)))
     }
 
-    /// Returns a uniformly distributed `f32` in the interval (0.0, 1.0].
+    /// Checked version of [`Generator::range_inclusive`], returning a uniformly
+    /// distributed `i64` in the interval \[`min`, `max`\].
+    ///
+    /// Unlike [`Generator::range_inclusive`], a `max` of `i64::MAX` is supported.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`ErrorKind::InvalidParameter`] unless `min` <= `max`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let val = rng.checked_range_inclusive(0, i64::MAX).unwrap();
+    /// assert!(val >= 0);
+    /// assert!(rng.checked_range_inclusive(3, 3).unwrap() == 3);
+    /// assert!(rng.checked_range_inclusive(3, 2).is_err());
+    /// ```
+    #[inline]
+    fn checked_range_inclusive(&mut self, min: i64, max: i64) -> Result<i64, Error> {
+        if min > max {
+            return Err(Error::new(ErrorKind::InvalidParameter));
+        }
+        let offset = match max.abs_diff(min) {
+            u64::MAX => self.u64(),
+            delta => self.bound(delta + 1),
+        };
+        Ok(min.wrapping_add_unsigned(offset))
+    }
+
+    /// Returns a uniformly distributed `u64` within `range`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let val = rng.in_range(0..100);
+    /// assert!(val < 100);
+    /// let roll = rng.in_range(1..=6);
+    /// assert!(1 <= roll && roll <= 6);
+    /// let big = rng.in_range(u64::MAX - 1..);
+    /// assert!(big >= u64::MAX - 1);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `range` is empty.
+    #[inline]
+    fn in_range<R: RangeBounds<u64>>(&mut self, range: R) -> u64 {
+        let (min, max) = inclusive_bounds(range.start_bound(), range.end_bound());
+        match max - min {
+            u64::MAX => self.u64(),
+            delta => min + self.bound(delta + 1),
+        }
+    }
+
+    /// Returns a uniformly distributed `i64` within `range`.
+    ///
+    /// # Panics
+    ///
+    /// If `range` is empty.
+    #[inline]
+    fn in_range_i64<R: RangeBounds<i64>>(&mut self, range: R) -> i64 {
+        // Flipping the sign bit maps `i64` onto `u64` while preserving order.
+        const FLIP: u64 = 1 << 63;
+        let map = |bound: Bound<&i64>| bound.map(|&v| v as u64 ^ FLIP);
+        let start = map(range.start_bound());
+        let end = map(range.end_bound());
+        (self.in_range((start, end)) ^ FLIP) as i64
+    }
+
+    /// Returns a uniformly distributed `f64` within `range`, where
+    /// both ends of the range must be bounded.
+    ///
+    /// Whether each end of the range is included or excluded is respected,
+    /// so `rng.in_range_f64(-1.0..1.0)` can return -1.0 but never 1.0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let val = rng.in_range_f64(-1.0..1.0);
+    /// assert!(-1.0 <= val && val < 1.0);
+    /// let val = rng.in_range_f64(2.5..=3.5);
+    /// assert!(2.5 <= val && val <= 3.5);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `range` is empty, either end is unbounded, or either end isn't finite.
+    #[inline]
+    fn in_range_f64<R: RangeBounds<f64>>(&mut self, range: R) -> f64 {
+        let (min, min_included) = match range.start_bound() {
+            Bound::Included(&v) => (v, true),
+            Bound::Excluded(&v) => (v, false),
+            Bound::Unbounded => panic!("range must have a start bound"),
+        };
+        let (max, max_included) = match range.end_bound() {
+            Bound::Included(&v) => (v, true),
+            Bound::Excluded(&v) => (v, false),
+            Bound::Unbounded => panic!("range must have an end bound"),
+        };
+        assert!(min.is_finite() && max.is_finite(), "range must be finite");
+        match min_included && max_included {
+            true => assert!(min <= max, "range must not be empty"),
+            false => assert!(min < max, "range must not be empty"),
+        }
+        loop {
+            let x = match (min_included, max_included) {
+                (true, false) => self.f64(),
+                (false, true) => self.f64_nonzero(),
+                (true, true) => self.bits(F64_MANT) as f64 / (F64_MAX_PRECISE - 1) as f64,
+                (false, false) => (self.bits(F64_MANT) as f64 + 0.5) / F64_DIVISOR,
+            };
+            let val = min + x * (max - min);
+            // Rounding can push values onto an excluded bound,
+            // or slightly past an included one.
+            let above_min = min < val || (min_included && min == val);
+            let below_max = val < max || (max_included && val == max);
+            if above_min && below_max {
+                return val;
+            }
+        }
+    }
+
+    /// Returns a uniformly distributed `SystemTime` in the interval [`start`, `end`),
+    /// with nanosecond precision.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let start = SystemTime::UNIX_EPOCH;
+    /// let end = start + Duration::from_secs(365 * 24 * 60 * 60);
+    /// let time = rng.system_time(start, end);
+    /// assert!(start <= time && time < end);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `start` isn't earlier than `end`.
+    #[cfg(feature = "std")]
+    fn system_time(&mut self, start: SystemTime, end: SystemTime) -> SystemTime {
+        let span = end
+            .duration_since(start)
+            .ok()
+            .filter(|span| !span.is_zero())
+            .expect("`start` must be earlier than `end`");
+        let nanos = bound_u128(self, span.as_nanos());
+        start + duration_from_nanos(nanos)
+    }
+
+    /// Returns a uniformly distributed `SystemTime` within `span` of the current time,
+    /// in the interval [`now - span`, `now`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let week = Duration::from_secs(7 * 24 * 60 * 60);
+    /// let time = rng.system_time_in_last(week);
+    /// assert!(time < SystemTime::now());
+    /// assert!(SystemTime::now() - week <= time + Duration::from_secs(1));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `span` is zero, or `now - span` can't be represented.
+    #[cfg(feature = "std")]
+    #[inline]
+    fn system_time_in_last(&mut self, span: Duration) -> SystemTime {
+        let now = SystemTime::now();
+        self.system_time(now - span, now)
+    }
+
+    /// Returns a uniformly distributed `Duration` in the interval [`min`, `max`],
+    /// with nanosecond precision.
+    ///
+    /// This is the building block for randomized retry delays. With an exponentially
+    /// growing `backoff`, "full jitter" waits for `jitter_between(Duration::ZERO, backoff)`,
+    /// which spreads out competing clients the most, while "equal jitter" waits for
+    /// `jitter_between(backoff / 2, backoff)`, which guarantees at least half of the delay.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let backoff = Duration::from_millis(800);
+    /// let full = rng.jitter_between(Duration::ZERO, backoff);
+    /// assert!(full <= backoff);
+    /// let equal = rng.jitter_between(backoff / 2, backoff);
+    /// assert!(backoff / 2 <= equal && equal <= backoff);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `min` is greater than `max`.
+    #[cfg(feature = "std")]
+    fn jitter_between(&mut self, min: Duration, max: Duration) -> Duration {
+        let span = max
+            .checked_sub(min)
+            .expect("`min` must not be greater than `max`");
+        let nanos = bound_u128(self, span.as_nanos() + 1);
+        min + duration_from_nanos(nanos)
+    }
+
+    /// Returns a uniformly distributed `Duration` within `fraction` of `base`, in the
+    /// interval [`base * (1 - fraction)`, `base * (1 + fraction)`].
+    ///
+    /// Useful for spreading out periodic work, such as polling or cache expiry, so that
+    /// many clients started at the same time don't stay in lockstep. A `fraction` of
+    /// `0.1` gives delays within 10% of `base`, and a `fraction` of `1.0` gives delays
+    /// anywhere between zero and twice `base`. See [`Generator::jitter_between`] for the
+    /// usual jitter strategies of retry loops.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let interval = rng.jitter(Duration::from_secs(60), 0.1);
+    /// assert!(Duration::from_secs(54) <= interval && interval <= Duration::from_secs(66));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `fraction` isn't within the interval [0.0, 1.0], or `base * (1 + fraction)`
+    /// overflows `Duration`.
+    #[cfg(feature = "std")]
+    fn jitter(&mut self, base: Duration, fraction: f64) -> Duration {
+        assert!(
+            (0.0..=1.0).contains(&fraction),
+            "`fraction` must be within [0.0, 1.0]"
+        );
+        self.jitter_between(base.mul_f64(1.0 - fraction), base.mul_f64(1.0 + fraction))
+    }
+
+    /// Returns a uniformly distributed `OffsetDateTime` in the interval [`start`, `end`),
+    /// with nanosecond precision. The result uses the same UTC offset as `start`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use time::{Duration, OffsetDateTime};
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let start = OffsetDateTime::UNIX_EPOCH;
+    /// let end = start + Duration::days(30);
+    /// let time = rng.offset_date_time(start, end);
+    /// assert!(start <= time && time < end);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `start` isn't earlier than `end`.
+    #[cfg(feature = "time")]
+    fn offset_date_time(
+        &mut self,
+        start: time::OffsetDateTime,
+        end: time::OffsetDateTime,
+    ) -> time::OffsetDateTime {
+        let span = (end - start).whole_nanoseconds();
+        assert!(span > 0, "`start` must be earlier than `end`");
+        let nanos = bound_u128(self, span as u128);
+        let offset = duration_from_nanos(nanos);
+        // Can't overflow since the result is between `start` and `end`.
+        start + offset
+    }
+
+    /// Returns a uniformly distributed `OffsetDateTime` within `span` of the current time,
+    /// in the interval [`now - span`, `now`). The result uses UTC.
+    ///
+    /// # Panics
+    ///
+    /// If `span` isn't positive, or `now - span` can't be represented.
+    #[cfg(feature = "time")]
+    #[inline]
+    fn offset_date_time_in_last(&mut self, span: time::Duration) -> time::OffsetDateTime {
+        let now = time::OffsetDateTime::now_utc();
+        self.offset_date_time(now - span, now)
+    }
+
+    /// Returns a uniformly distributed `BigUint` in the interval [0, 2<sup>`bit_count`</sup>).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let val = rng.biguint_bits(300);
+    /// assert!(val.bits() <= 300);
+    /// ```
+    #[cfg(feature = "num-bigint")]
+    fn biguint_bits(&mut self, bit_count: u64) -> num_bigint::BigUint {
+        let len = bit_count.div_ceil(u32::BITS as u64) as usize;
+        let mut digits = Vec::with_capacity(len);
+        while digits.len() < len {
+            let x = self.u64();
+            digits.push(x as u32);
+            if digits.len() < len {
+                digits.push((x >> u32::BITS) as u32);
+            }
+        }
+        let extra_bits = (len as u64 * u32::BITS as u64 - bit_count) as u32;
+        if let Some(top) = digits.last_mut() {
+            *top >>= extra_bits;
+        }
+        num_bigint::BigUint::new(digits)
+    }
+
+    /// Returns a uniformly distributed `BigUint` in the interval [0, `max`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let max = BigUint::from(10_u32).pow(50);
+    /// let val = rng.biguint_below(&max);
+    /// assert!(val < max);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `max` is zero.
+    #[cfg(feature = "num-bigint")]
+    fn biguint_below(&mut self, max: &num_bigint::BigUint) -> num_bigint::BigUint {
+        assert!(max.bits() != 0, "`max` must not be zero");
+        // Sampling from the smallest power of 2 containing the interval
+        // means that rejection happens less than half of the time.
+        loop {
+            let x = self.biguint_bits(max.bits());
+            if &x < max {
+                return x;
+            }
+        }
+    }
+
+    /// Returns a uniformly distributed `f64` in the interval [0.0, 1.0).
+    ///
+    /// Values are multiples of 2<sup>-53</sup>, or 2<sup>-52</sup> when the
+    /// **bitcast-floats** feature is enabled.
+    #[inline]
+    fn f64(&mut self) -> f64 {
+        match cfg!(feature = "bitcast-floats") {
+            true => util::u64_to_f64_bitcast(self.u64()),
+            false => util::u64_to_f64(self.u64()),
+        }
+    }
+
+    /// Returns a uniformly distributed `f32` in the interval [0.0, 1.0).
+    ///
+    /// Values are multiples of 2<sup>-24</sup>, or 2<sup>-23</sup> when the
+    /// **bitcast-floats** feature is enabled.
+    #[inline]
+    fn f32(&mut self) -> f32 {
+        match cfg!(feature = "bitcast-floats") {
+            true => util::u64_to_f32_bitcast(self.u64()),
+            false => self.bits(F32_MANT) as f32 / F32_DIVISOR,
+        }
+    }
+
+    /// Returns a uniformly distributed `f64` in the interval (0.0, 1.0].
+    #[inline]
+    fn f64_nonzero(&mut self) -> f64 {
+        // Interval of (0, 2^53]
+        let nonzero = self.bits(F64_MANT) + 1;
+        nonzero as f64 / F64_DIVISOR
+    }
+
+    /// Returns a uniformly distributed `f32` in the interval (0.0, 1.0].
     #[inline]
     fn f32_nonzero(&mut self) -> f32 {
         // Interval of (0, 2^24]
@@ -433,6 +1375,151 @@ pub trait Generator: Sized {
         x as f32 / F32_DIVISOR
     }
 
+    /// Partitions [0.0, 1.0) into `n` equally sized cells, and fills `dst` with
+    /// one uniformly distributed `f64` from each cell, in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// const N: usize = 16;
+    /// let mut rng = new_rng();
+    /// let mut samples = [0.0; N];
+    /// rng.stratified_1d(N, &mut samples);
+    /// for (i, v) in samples.into_iter().enumerate() {
+    ///     assert!((v * N as f64) as usize == i);
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the length of `dst` isn't `n`.
+    #[inline(never)]
+    fn stratified_1d(&mut self, n: usize, dst: &mut [f64]) {
+        assert!(dst.len() == n, "`dst` must have a length of `n`");
+        let width = 1.0 / n as f64;
+        for (i, v) in dst.iter_mut().enumerate() {
+            *v = stratum_sample(self, i, width);
+        }
+    }
+
+    /// Fills `dst` with `n` independent uniformly distributed `f64` values in the interval
+    /// [0.0, 1.0), sorted in ascending order.
+    ///
+    /// The values have the same distribution as sorting `n` calls to [`Generator::f64`],
+    /// but are generated directly in order using exponential spacings, so this takes
+    /// linear time instead of the O(n log n) of sorting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// const N: usize = 1000;
+    /// let mut rng = new_rng();
+    /// let mut samples = [0.0; N];
+    /// rng.sorted_uniforms(N, &mut samples);
+    /// assert!(samples.is_sorted());
+    /// assert!(samples.iter().all(|v| (0.0..1.0).contains(v)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the length of `dst` isn't `n`.
+    #[cfg(feature = "std")]
+    fn sorted_uniforms(&mut self, n: usize, dst: &mut [f64]) {
+        assert!(dst.len() == n, "`dst` must have a length of `n`");
+        // The partial sums of n + 1 exponential values, divided by the total sum,
+        // are distributed as the order statistics of n uniform values.
+        let mut sum = 0.0;
+        for v in dst.iter_mut() {
+            sum += self.f64_exponential();
+            *v = sum;
+        }
+        sum += self.f64_exponential();
+        for v in dst.iter_mut() {
+            // Rounding can push values up to exactly 1.0.
+            *v = (*v / sum).min(1.0 - f64::EPSILON / 2.0);
+        }
+    }
+
+    /// Partitions the unit square into a grid of `nx` by `ny` equally sized cells,
+    /// and fills `dst` with one uniformly distributed point from each cell.
+    ///
+    /// Points are written in row-major order, so the point at index `y * nx + x`
+    /// lies within column `x` and row `y` of the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// const NX: usize = 4;
+    /// const NY: usize = 3;
+    /// let mut rng = new_rng();
+    /// let mut points = [[0.0; 2]; NX * NY];
+    /// rng.jittered_2d(NX, NY, &mut points);
+    /// for (i, [x, y]) in points.into_iter().enumerate() {
+    ///     assert!((x * NX as f64) as usize == i % NX);
+    ///     assert!((y * NY as f64) as usize == i / NX);
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the length of `dst` isn't `nx * ny`.
+    #[inline(never)]
+    fn jittered_2d(&mut self, nx: usize, ny: usize, dst: &mut [[f64; 2]]) {
+        assert!(
+            nx.checked_mul(ny) == Some(dst.len()),
+            "`dst` must have a length of `nx * ny`"
+        );
+        let width = 1.0 / nx as f64;
+        let height = 1.0 / ny as f64;
+        for (y, row) in dst.chunks_exact_mut(nx.max(1)).enumerate() {
+            for (x, point) in row.iter_mut().enumerate() {
+                *point = [
+                    stratum_sample(self, x, width),
+                    stratum_sample(self, y, height),
+                ];
+            }
+        }
+    }
+
+    /// Returns a blue-noise set of points within [0.0, `width`) x [0.0, `height`),
+    /// where no two points are closer together than `min_dist`.
+    ///
+    /// Uses Bridson's algorithm, which runs in time proportional to the amount
+    /// of points generated, and leaves no gaps large enough to fit another point
+    /// with high probability.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let points = rng.poisson_disk(100.0, 50.0, 5.0);
+    /// assert!(points.len() > 50);
+    /// for (i, [x1, y1]) in points.iter().enumerate() {
+    ///     assert!((0.0..100.0).contains(x1) && (0.0..50.0).contains(y1));
+    ///     for [x2, y2] in &points[i + 1..] {
+    ///         assert!((x1 - x2).hypot(y1 - y2) >= 5.0);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `width` or `height` is negative or non-finite, or `min_dist`
+    /// isn't both positive and finite.
+    #[cfg(feature = "std")]
+    #[inline(never)]
+    fn poisson_disk(&mut self, width: f64, height: f64, min_dist: f64) -> Vec<[f64; 2]> {
+        poisson::bridson(self, width, height, min_dist)
+    }
+
     /// Returns two indepedent and normally distributed `f64` values with
     /// a `mean` of `0.0` and a `stddev` of `1.0`.
     #[cfg(feature = "std")]
@@ -487,6 +1574,23 @@ pub trait Generator: Sized {
         self.f64_exponential() / lambda
     }
 
+    /// Returns a standard Gumbel distributed `f64`, with a location of `0.0` and a scale
+    /// of `1.0`.
+    ///
+    /// The result is always finite.
+    #[cfg(feature = "std")]
+    #[inline]
+    fn f64_gumbel(&mut self) -> f64 {
+        // The negated log of a standard exponential value, skipping zero since
+        // it would result in infinity.
+        loop {
+            let e = self.f64_exponential();
+            if e != 0.0 {
+                return -e.ln();
+            }
+        }
+    }
+
     /// Returns a randomly chosen item from the iterator of `collection`.
     ///
     /// Returns `None` when the length of the iterator is zero.
@@ -535,14 +1639,330 @@ pub trait Generator: Sized {
         }
     }
 
-    /// Returns a randomly selected ASCII character from the pool of:
+    /// Returns a uniformly chosen item from `iter`, or `None` when it's empty.
     ///
-    /// `'A'..='Z'`, and`'a'..='z'`
-    #[inline]
-    fn ascii_alphabetic(&mut self) -> char {
-        *self.choose(&ALPHANUMERIC[..52]).unwrap() as char
-    }
-
+    /// Unlike [`Generator::choose`], the length of `iter` doesn't need to be known
+    /// up-front, which allows choosing from streams such as the lines of a reader.
+    /// This uses single-slot reservoir sampling, so the entire iterator is consumed
+    /// and one random value is used for every item after the first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let text = "alpha\nbeta\ngamma\ndelta";
+    /// let line = rng.choose_from_iter(text.lines()).unwrap();
+    /// assert!(text.lines().any(|l| l == line));
+    /// let evens = (0..1000).filter(|i| i % 2 == 0);
+    /// assert!(rng.choose_from_iter(evens).unwrap() % 2 == 0);
+    /// assert!(rng.choose_from_iter(core::iter::empty::<u8>()).is_none());
+    /// ```
+    fn choose_from_iter<I: IntoIterator>(&mut self, iter: I) -> Option<I::Item> {
+        let mut iter = iter.into_iter();
+        let mut chosen = iter.next()?;
+        for (seen, item) in (2..).zip(iter) {
+            // The `seen`th item replaces the chosen one with probability 1 / `seen`,
+            // which leaves every item equally likely to be the final choice.
+            if self.bound(seen) == 0 {
+                chosen = item;
+            }
+        }
+        Some(chosen)
+    }
+
+    /// Returns a uniformly chosen item of `slice` for which `excluded` returns `false`,
+    /// or `None` when every item is excluded.
+    ///
+    /// No filtered copy of `slice` is made. A few items are first chosen from all of
+    /// `slice`, which is fast when only a small part of it is excluded, such as a
+    /// single previous choice. If none of those are allowed, the allowed items are
+    /// counted and one of them is chosen directly, so this never takes more than
+    /// linear time. `excluded` must always return the same result for a given item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let players = ["ana", "ben", "cho", "dev"];
+    /// let previous = "cho";
+    /// let opponent = rng.choose_except(&players, |p| *p == previous).unwrap();
+    /// assert!(*opponent != previous);
+    /// assert!(rng.choose_except(&players, |_| true).is_none());
+    /// ```
+    fn choose_except<'a, T, F>(&mut self, slice: &'a [T], mut excluded: F) -> Option<&'a T>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        // Each attempt is a uniform choice among the allowed items when it succeeds,
+        // and so is the fallback, which keeps the final choice uniform.
+        for _ in 0..4 {
+            let item = self.choose(slice)?;
+            if !excluded(item) {
+                return Some(item);
+            }
+        }
+        let allowed = slice.iter().filter(|item| !excluded(item)).count();
+        if allowed == 0 {
+            return None;
+        }
+        let nth = self.bound(allowed as u64) as usize;
+        slice.iter().filter(|item| !excluded(item)).nth(nth)
+    }
+
+    /// Returns a uniformly chosen index in the interval [0, `len`) other than `except`,
+    /// or `None` when there isn't one.
+    ///
+    /// Always uses a single random value, and an `except` which is out of bounds
+    /// simply excludes nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let targets = ["primary", "replica-1", "replica-2"];
+    /// let failed = 0;
+    /// let retry = rng.choose_index_except(targets.len(), failed).unwrap();
+    /// assert!(retry != failed && retry < targets.len());
+    /// assert!(rng.choose_index_except(1, 0).is_none());
+    /// ```
+    #[inline]
+    fn choose_index_except(&mut self, len: usize, except: usize) -> Option<usize> {
+        let candidates = match except < len {
+            true => len - 1,
+            false => len,
+        };
+        if candidates == 0 {
+            return None;
+        }
+        let idx = self.bound(candidates as u64) as usize;
+        // Shifting everything from `except` onwards skips over it.
+        Some(idx + (idx >= except) as usize)
+    }
+
+    /// Returns a uniformly chosen entry of `map`, or `None` when it's empty.
+    ///
+    /// Works with anything which iterates over `(key, value)` pairs and knows its
+    /// length, such as references to a `HashMap` or `BTreeMap`. Since maps can't be
+    /// indexed, finding the chosen entry takes time linear in the size of `map`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let ports = HashMap::from([("http", 80), ("https", 443), ("ssh", 22)]);
+    /// let (name, port) = rng.choose_from_map(&ports).unwrap();
+    /// assert!(ports[name] == *port);
+    /// assert!(rng.choose_from_map(&HashMap::<u8, u8>::new()).is_none());
+    /// ```
+    #[inline]
+    fn choose_from_map<M, K, V>(&mut self, map: M) -> Option<(K, V)>
+    where
+        M: IntoIterator<Item = (K, V)>,
+        M::IntoIter: ExactSizeIterator,
+    {
+        self.choose(map)
+    }
+
+    /// Returns a uniformly chosen key of `map`, or `None` when it's empty.
+    ///
+    /// Identical to [`Generator::choose_from_map`], but only the key is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let servers = BTreeMap::from([("eu-1", 12), ("us-1", 3), ("us-2", 7)]);
+    /// let name = rng.choose_key(&servers).unwrap();
+    /// assert!(servers.contains_key(name));
+    /// ```
+    #[inline]
+    fn choose_key<M, K, V>(&mut self, map: M) -> Option<K>
+    where
+        M: IntoIterator<Item = (K, V)>,
+        M::IntoIter: ExactSizeIterator,
+    {
+        self.choose(map).map(|(key, _)| key)
+    }
+
+    /// Returns a uniformly chosen value of `map`, or `None` when it's empty.
+    ///
+    /// Identical to [`Generator::choose_from_map`], but only the value is returned.
+    /// Passing a mutable reference to `map` allows the value to be modified in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let mut scores = HashMap::from([("alice", 0), ("bob", 0)]);
+    /// *rng.choose_value(&mut scores).unwrap() += 1;
+    /// assert!(scores.values().sum::<i32>() == 1);
+    /// ```
+    #[inline]
+    fn choose_value<M, K, V>(&mut self, map: M) -> Option<V>
+    where
+        M: IntoIterator<Item = (K, V)>,
+        M::IntoIter: ExactSizeIterator,
+    {
+        self.choose(map).map(|(_, value)| value)
+    }
+
+    /// Returns an item from `items` chosen with probability proportional to its
+    /// `weight`, or `None` when there are no items with a positive weight.
+    ///
+    /// Weights are computed on the fly, so items can be picked by a field or a map
+    /// value without first collecting the weights. This uses single-slot weighted
+    /// reservoir sampling, keeping a running total of the weights, so `items` is
+    /// only iterated once and one random value is used for every item with a
+    /// positive weight.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let stock = BTreeMap::from([("apple", 3), ("banana", 0), ("cherry", 12)]);
+    /// let (fruit, _) = rng.choose_by_weight(&stock, |(_, count)| **count as f64).unwrap();
+    /// assert!(*fruit != "banana");
+    /// assert!(rng.choose_by_weight([1, 2], |_| 0.0).is_none());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If any weight is negative, infinite, or NaN.
+    fn choose_by_weight<I, F>(&mut self, items: I, mut weight: F) -> Option<I::Item>
+    where
+        I: IntoIterator,
+        F: FnMut(&I::Item) -> f64,
+    {
+        let mut chosen = None;
+        let mut total = 0.0;
+        for item in items {
+            let w = weight(&item);
+            assert!(
+                w >= 0.0 && w.is_finite(),
+                "weights must be finite and non-negative"
+            );
+            if w > 0.0 {
+                total += w;
+                // Replacing the chosen item with probability `w / total` leaves every
+                // item seen so far chosen with probability proportional to its weight.
+                if self.f64() * total < w {
+                    chosen = Some(item);
+                }
+            }
+        }
+        chosen
+    }
+
+    /// Returns a uniformly chosen variant of `T`.
+    ///
+    /// See [`crate::random_variant`] for declaring enums which implement [`RandomVariant`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// random_variant! {
+    ///     #[derive(Debug, PartialEq, Eq)]
+    ///     enum Direction {
+    ///         North,
+    ///         East,
+    ///         South,
+    ///         West,
+    ///     }
+    /// }
+    ///
+    /// let mut rng = new_rng();
+    /// let directions: Vec<Direction> = (0..1000).map(|_| rng.variant()).collect();
+    /// assert!(directions.contains(&Direction::West));
+    /// ```
+    #[inline]
+    fn variant<T: RandomVariant>(&mut self) -> T {
+        T::random_variant(self)
+    }
+
+    /// Chooses up to `k` distinct items from `items`, with every item being chosen
+    /// with probability proportional to its `weight`.
+    ///
+    /// The returned items are ordered as if they were drawn one at a time without
+    /// replacement, so the first item is a weighted choice among all of the items,
+    /// the second is a weighted choice among the remaining items, and so on.
+    /// Items with a weight of zero are never chosen, which means fewer than `k` items
+    /// are returned when there aren't enough items with a positive weight.
+    ///
+    /// This uses the exponent-key method of Efraimidis and Spirakis, which
+    /// requires a single pass over `items` and one random value per item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let tickets = [("alice", 1.0), ("bob", 5.0), ("carol", 0.0), ("dave", 2.0)];
+    /// let winners = rng.choose_multiple_weighted(&tickets, 2, |(_, weight)| *weight);
+    /// assert!(winners.len() == 2);
+    /// assert!(winners[0] != winners[1]);
+    /// assert!(winners.iter().all(|(name, _)| *name != "carol"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If any weight is negative, infinite, or NaN.
+    #[cfg(feature = "std")]
+    fn choose_multiple_weighted<I, F>(&mut self, items: I, k: usize, mut weight: F) -> Vec<I::Item>
+    where
+        I: IntoIterator,
+        F: FnMut(&I::Item) -> f64,
+    {
+        let mut keyed = items
+            .into_iter()
+            .filter_map(|item| {
+                let w = weight(&item);
+                assert!(
+                    w >= 0.0 && w.is_finite(),
+                    "weights must be finite and non-negative"
+                );
+                // Equivalent to the key u^(1/w) from the paper, but computed in the log domain
+                // since raising to a large power would underflow for small weights.
+                (w > 0.0).then(|| (-self.f64_exponential() / w, item))
+            })
+            .collect::<Vec<_>>();
+        let by_key_descending = |x: &(f64, _), y: &(f64, _)| y.0.total_cmp(&x.0);
+        if k < keyed.len() {
+            keyed.select_nth_unstable_by(k, by_key_descending);
+            keyed.truncate(k);
+        }
+        keyed.sort_unstable_by(by_key_descending);
+        keyed.into_iter().map(|(_, item)| item).collect()
+    }
+
+    /// Returns a randomly selected ASCII character from the pool of:
+    ///
+    /// `'A'..='Z'`, and`'a'..='z'`
+    #[inline]
+    fn ascii_alphabetic(&mut self) -> char {
+        *self.choose(&ALPHANUMERIC[..52]).unwrap() as char
+    }
+
     /// Returns a randomly selected ASCII character from the pool of:
     ///
     /// `'A'..='Z'`
@@ -575,6 +1995,178 @@ pub trait Generator: Sized {
         *self.choose(&ALPHANUMERIC[52..]).unwrap() as char
     }
 
+    /// Fills `dst` with bytes randomly selected from `charset`.
+    ///
+    /// Unlike repeatedly calling [`Generator::choose`], which uses a full `u64` per
+    /// byte, as many unbiased selections as possible are extracted from each `u64`:
+    /// a single value in [0, `len`<sup>k</sup>) is generated and split into `k` base-`len`
+    /// digits. For a charset of 62 characters this is 10 selections per `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let mut id = [0; 16];
+    /// rng.fill_from_charset(b"0123456789abcdef", &mut id);
+    /// assert!(id.iter().all(|c| c.is_ascii_hexdigit()));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `charset` is empty.
+    #[inline]
+    fn fill_from_charset(&mut self, charset: &[u8], dst: &mut [u8]) {
+        assert!(!charset.is_empty(), "`charset` must not be empty");
+        let len = charset.len() as u64;
+        if len == 1 {
+            dst.fill(charset[0]);
+            return;
+        }
+        // Largest power of `len` which fits in a `u64`.
+        let mut range = len;
+        let mut per_draw = 1;
+        while let Some(next) = range.checked_mul(len) {
+            range = next;
+            per_draw += 1;
+        }
+        for chunk in dst.chunks_mut(per_draw) {
+            let mut digits = self.bound(range);
+            for cur in chunk {
+                *cur = charset[(digits % len) as usize];
+                digits /= len;
+            }
+        }
+    }
+
+    /// Returns a `String` of `len` characters, which has the same distribution as calling
+    /// [`Generator::ascii_alphabetic`] `len` times, but is much faster.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn ascii_alphabetic_str(&mut self, len: usize) -> String {
+        charset_string(self, &ALPHANUMERIC[..52], len)
+    }
+
+    /// Returns a `String` of `len` characters, which has the same distribution as calling
+    /// [`Generator::ascii_uppercase`] `len` times, but is much faster.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn ascii_uppercase_str(&mut self, len: usize) -> String {
+        charset_string(self, &ALPHANUMERIC[..26], len)
+    }
+
+    /// Returns a `String` of `len` characters, which has the same distribution as calling
+    /// [`Generator::ascii_lowercase`] `len` times, but is much faster.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn ascii_lowercase_str(&mut self, len: usize) -> String {
+        charset_string(self, &ALPHANUMERIC[26..52], len)
+    }
+
+    /// Returns a `String` of `len` characters, which has the same distribution as calling
+    /// [`Generator::ascii_alphanumeric`] `len` times, but is much faster.
+    ///
+    /// Uses [`Generator::fill_from_charset`], so roughly one `u64` is
+    /// generated for every 10 characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let id = rng.ascii_alphanumeric_str(12);
+    /// assert!(id.len() == 12);
+    /// assert!(id.chars().all(|c| c.is_ascii_alphanumeric()));
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn ascii_alphanumeric_str(&mut self, len: usize) -> String {
+        charset_string(self, ALPHANUMERIC, len)
+    }
+
+    /// Returns a `String` of `len` characters, which has the same distribution as calling
+    /// [`Generator::ascii_digit`] `len` times, but is much faster.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn ascii_digit_str(&mut self, len: usize) -> String {
+        charset_string(self, &ALPHANUMERIC[52..], len)
+    }
+
+    /// Returns a human friendly name made of an adjective, a noun, and a four
+    /// digit number, separated by hyphens (e.g. `brave-otter-7291`).
+    ///
+    /// Shorthand for `slug_with(2, "-", 4)`, which has about 40 million possible
+    /// values, so it's meant for naming things rather than as a unique identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let name = rng.slug();
+    /// let parts = name.split('-').collect::<Vec<_>>();
+    /// assert!(parts.len() == 3);
+    /// assert!(parts[2].len() == 4);
+    /// assert!(parts[2].bytes().all(|c| c.is_ascii_digit()));
+    /// ```
+    #[cfg(feature = "slug")]
+    #[inline]
+    fn slug(&mut self) -> String {
+        self.slug_with(2, "-", 4)
+    }
+
+    /// Returns a human friendly name made of `words` lowercase words and a
+    /// zero-padded number of `digits` digits, all joined by `separator`.
+    ///
+    /// Every word but the last is an adjective, and the last is a noun. Either part
+    /// can be left out by passing 0, and each word multiplies the amount of possible
+    /// values by 64.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let name = rng.slug_with(3, "_", 0);
+    /// assert!(name.split('_').count() == 3);
+    /// assert!(name.bytes().all(|c| c == b'_' || c.is_ascii_lowercase()));
+    /// assert!(rng.slug_with(0, "-", 6).len() == 6);
+    /// assert!(rng.slug_with(0, "-", 0).is_empty());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `digits` is greater than 19.
+    #[cfg(feature = "slug")]
+    fn slug_with(&mut self, words: usize, separator: &str, digits: u32) -> String {
+        use core::fmt::Write;
+        assert!(digits <= 19, "`digits` must not be greater than 19");
+        let mut ret = String::new();
+        for i in 0..words {
+            let list = match i + 1 == words {
+                true => &slug::NOUNS,
+                false => &slug::ADJECTIVES,
+            };
+            if i != 0 {
+                ret.push_str(separator);
+            }
+            ret.push_str(self.choose(list).unwrap());
+        }
+        if digits != 0 {
+            if words != 0 {
+                ret.push_str(separator);
+            }
+            let num = self.bound(10_u64.pow(digits));
+            // Writing to a `String` can't fail.
+            let _ = write!(ret, "{num:0width$}", width = digits as usize);
+        }
+        ret
+    }
+
     /// Performs a Fisher-Yates shuffle on the contents of `slice`.
     ///
     /// This implementation is the modern variant introduced by
@@ -609,6 +2201,437 @@ pub trait Generator: Sized {
         }
     }
 
+    /// Performs a Fisher-Yates shuffle on the contents of any [`Swappable`] collection,
+    /// such as a `VecDeque`.
+    ///
+    /// Consumes exactly the same random values as [`Generator::shuffle`] and performs
+    /// the same swaps, so both produce the same order when given the same seed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::VecDeque;
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let mut queue = (0..1000).collect::<VecDeque<_>>();
+    /// queue.rotate_left(300);
+    /// rng.shuffle_indexed(&mut queue);
+    /// assert!(!queue.iter().is_sorted());
+    /// ```
+    #[inline(never)]
+    fn shuffle_indexed<S: Swappable + ?Sized>(&mut self, collection: &mut S) {
+        for i in (1..collection.len()).rev() {
+            let j = self.bound_inclusive(i as u64) as usize;
+            collection.swap(i, j);
+        }
+    }
+
+    /// Interprets `slice` as a row-major matrix with `columns` columns,
+    /// and shuffles the order of its rows.
+    ///
+    /// The contents of every row are left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// // Three samples with two features each.
+    /// let mut dataset = [1, 10, 2, 20, 3, 30];
+    /// rng.shuffle_rows(&mut dataset, 2);
+    /// assert!(dataset.chunks(2).all(|row| row[1] == row[0] * 10));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `columns` is zero, or the length of `slice` isn't a multiple of `columns`.
+    #[inline(never)]
+    fn shuffle_rows<T>(&mut self, slice: &mut [T], columns: usize) {
+        assert!(
+            columns != 0 && slice.len().is_multiple_of(columns),
+            "slice length must be a non-zero multiple of `columns`"
+        );
+        let rows = slice.len() / columns;
+        for i in (1..rows).rev() {
+            let j = self.bound_inclusive(i as u64) as usize;
+            if i != j {
+                let (head, tail) = slice.split_at_mut(i * columns);
+                head[j * columns..][..columns].swap_with_slice(&mut tail[..columns]);
+            }
+        }
+    }
+
+    /// Interprets `slice` as a row-major matrix with `columns` columns,
+    /// and shuffles the order of its columns.
+    ///
+    /// The same permutation is applied to every row, so the contents
+    /// of every column are left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let mut matrix = [1, 2, 3, 10, 20, 30];
+    /// rng.shuffle_columns(&mut matrix, 3);
+    /// let (first, second) = matrix.split_at(3);
+    /// assert!(first.iter().zip(second).all(|(x, y)| *y == x * 10));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `columns` is zero, or the length of `slice` isn't a multiple of `columns`.
+    #[inline(never)]
+    fn shuffle_columns<T>(&mut self, slice: &mut [T], columns: usize) {
+        assert!(
+            columns != 0 && slice.len().is_multiple_of(columns),
+            "slice length must be a non-zero multiple of `columns`"
+        );
+        for i in (1..columns).rev() {
+            let j = self.bound_inclusive(i as u64) as usize;
+            if i != j {
+                slice
+                    .chunks_exact_mut(columns)
+                    .for_each(|row| row.swap(i, j));
+            }
+        }
+    }
+
+    /// Returns a `String` containing exactly `byte_budget` bytes of
+    /// randomly selected Unicode scalar values.
+    ///
+    /// Each character is generated by first choosing its encoded length
+    /// (1 to 4 bytes, limited by the remaining budget), and then uniformly
+    /// choosing a scalar value of that length. This means multi-byte and
+    /// astral-plane characters appear frequently, which makes the output
+    /// useful as fuzzing input for parsers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let s = rng.utf8_string(1738);
+    /// assert!(s.len() == 1738);
+    /// assert!(s.chars().any(|c| c.len_utf8() == 4));
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn utf8_string(&mut self, byte_budget: usize) -> String {
+        const SURROGATE_START: u64 = 0xD800;
+        const SURROGATE_LEN: u64 = 0x800;
+        let mut s = String::with_capacity(byte_budget);
+        let mut remaining = byte_budget;
+        while remaining != 0 {
+            let width = self.bound(remaining.min(4) as u64) + 1;
+            let scalar = match width {
+                1 => self.bound(0x80),
+                2 => self.bound(0x800 - 0x80) + 0x80,
+                3 => {
+                    // Skip over the surrogate range, which
+                    // doesn't contain any valid scalar values.
+                    let v = self.bound(0x10000 - 0x800 - SURROGATE_LEN) + 0x800;
+                    if v >= SURROGATE_START {
+                        v + SURROGATE_LEN
+                    } else {
+                        v
+                    }
+                }
+                _ => self.bound(0x110000 - 0x10000) + 0x10000,
+            };
+            // SAFETY: All the above intervals only contain valid Unicode scalar values.
+            let c = unsafe { char::from_u32_unchecked(scalar as u32) };
+            debug_assert!(c.len_utf8() as u64 == width);
+            s.push(c);
+            remaining -= width as usize;
+        }
+        s
+    }
+
+    /// Returns a uniformly distributed subset of a set with `n_bits` elements,
+    /// as a bitmask where bit `i` is set when element `i` is part of the subset.
+    ///
+    /// The value of `n_bits` is clamped to 64.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let flags = ["verbose", "color", "unicode", "tabs", "wrap"];
+    /// let mask = rng.subset_mask(flags.len() as u32);
+    /// assert!(mask < 1 << flags.len());
+    /// let enabled = (0..flags.len()).filter(|i| mask & (1 << i) != 0).map(|i| flags[i]);
+    /// assert!(enabled.count() == mask.count_ones() as usize);
+    /// ```
+    #[inline]
+    fn subset_mask(&mut self, n_bits: u32) -> u64 {
+        // The only subset of an empty set is the empty set, and
+        // `bits` can't shift away all 64 bits of its input.
+        if n_bits == 0 {
+            return 0;
+        }
+        self.bits(n_bits)
+    }
+
+    /// Returns a `u64` with exactly `ones` of its lowest `width` bits set, where every
+    /// such pattern is equally likely, and all bits at or above `width` cleared.
+    ///
+    /// This is a [`Generator::subset_mask`] restricted to subsets of a fixed size, and
+    /// uses Floyd's algorithm on the bits, needing at most `width / 2` random values.
+    /// The value of `width` is clamped to 64.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// // Flip exactly 3 bits of a 32-bit word.
+    /// let fault = rng.bits_with_weight(32, 3);
+    /// assert!(fault.count_ones() == 3);
+    /// assert!(fault < 1 << 32);
+    /// assert!(rng.bits_with_weight(64, 64) == u64::MAX);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `ones` is greater than `width`, after clamping.
+    fn bits_with_weight(&mut self, width: u32, ones: u32) -> u64 {
+        let width = width.min(u64::BITS);
+        assert!(ones <= width, "`ones` must not be greater than `width`");
+        // Placing whichever of the ones or zeros is rarer keeps the loop short.
+        let (count, invert) = match ones <= width / 2 {
+            true => (ones, false),
+            false => (width - ones, true),
+        };
+        let mut mask = 0_u64;
+        for j in width - count..width {
+            let t = self.bound_inclusive(j as u64);
+            mask |= match mask & (1 << t) == 0 {
+                true => 1 << t,
+                false => 1 << j,
+            };
+        }
+        match invert {
+            // Inverting implies `width` isn't zero, so the shift can't overflow.
+            true => !mask & (u64::MAX >> (u64::BITS - width)),
+            false => mask,
+        }
+    }
+
+    /// Returns a uniformly distributed `k`-combination of `0..n`,
+    /// which is a subset of exactly `k` elements, sorted in ascending order.
+    ///
+    /// This uses Floyd's algorithm, which only needs `k` random values
+    /// and is efficient even when `n` is enormous.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let lottery = rng.combination(49, 6);
+    /// assert!(lottery.len() == 6);
+    /// assert!(lottery.is_sorted());
+    /// assert!(lottery.windows(2).all(|w| w[0] != w[1]));
+    /// assert!(lottery.iter().all(|&v| v < 49));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `k` is greater than `n`.
+    #[cfg(feature = "alloc")]
+    fn combination(&mut self, n: u64, k: usize) -> Vec<u64> {
+        assert!(k as u64 <= n, "`k` must not be greater than `n`");
+        let mut set = BTreeSet::new();
+        for j in n - k as u64..n {
+            let t = self.bound_inclusive(j);
+            if !set.insert(t) {
+                set.insert(j);
+            }
+        }
+        set.into_iter().collect()
+    }
+
+    /// Returns `k` distinct values which are uniformly distributed in the interval
+    /// [0, `bound`), in random order.
+    ///
+    /// Identical to [`Generator::combination`] followed by [`Generator::shuffle`], so
+    /// it never needs to retry when a value repeats, unlike repeatedly drawing from
+    /// [`Generator::bound`] until enough unique values have been collected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let ids = rng.unique_u64s(100, 1000);
+    /// assert!(ids.len() == 100);
+    /// assert!(ids.iter().all(|&id| id < 1000));
+    /// let mut sorted = ids.clone();
+    /// sorted.sort();
+    /// sorted.dedup();
+    /// assert!(sorted.len() == 100);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `k` is greater than `bound`.
+    #[cfg(feature = "alloc")]
+    fn unique_u64s(&mut self, k: usize, bound: u64) -> Vec<u64> {
+        let mut ret = self.combination(bound, k);
+        self.shuffle(&mut ret);
+        ret
+    }
+
+    /// Returns a `HashSet` of `k` distinct values which are uniformly distributed
+    /// in the interval [0, `bound`).
+    ///
+    /// Uses the same algorithm as [`Generator::combination`], but without
+    /// the cost of keeping the values in sorted order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let ids = rng.unique_u64s_set(100, 1000);
+    /// assert!(ids.len() == 100);
+    /// assert!(ids.iter().all(|&id| id < 1000));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `k` is greater than `bound`.
+    #[cfg(feature = "std")]
+    fn unique_u64s_set(&mut self, k: usize, bound: u64) -> HashSet<u64> {
+        assert!(k as u64 <= bound, "`k` must not be greater than `bound`");
+        let mut set = HashSet::with_capacity(k);
+        for j in bound - k as u64..bound {
+            let t = self.bound_inclusive(j);
+            if !set.insert(t) {
+                set.insert(j);
+            }
+        }
+        set
+    }
+
+    /// Splits `total` into `parts` non-negative summands, chosen uniformly
+    /// among all the ways of doing so (also known as weak compositions).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let loot = rng.composition(100, 4);
+    /// assert!(loot.len() == 4);
+    /// assert!(loot.iter().sum::<u64>() == 100);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `parts` is zero while `total` isn't, or `total + parts` overflows.
+    #[cfg(feature = "alloc")]
+    fn composition(&mut self, total: u64, parts: usize) -> Vec<u64> {
+        if parts == 0 {
+            assert!(
+                total == 0,
+                "a non-zero total can't be split into zero parts"
+            );
+            return Vec::new();
+        }
+        // Stars and bars: choosing the positions of `parts - 1` bars among
+        // `total + parts - 1` slots uniquely determines every summand.
+        let slots = total
+            .checked_add(parts as u64 - 1)
+            .expect("`total + parts` must not overflow");
+        let bars = self.combination(slots, parts - 1);
+        let mut prev = 0;
+        let mut ret = Vec::with_capacity(parts);
+        for bar in bars.into_iter().chain([slots]) {
+            ret.push(bar - prev);
+            // Only wraps for the final "bar" when `slots` is `u64::MAX`,
+            // after which `prev` is never read again.
+            prev = bar.wrapping_add(1);
+        }
+        ret
+    }
+
+    /// Splits `total` into `parts` positive summands, chosen uniformly
+    /// among all the ways of doing so (also known as compositions).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let load = rng.composition_positive(1000, 8);
+    /// assert!(load.len() == 8);
+    /// assert!(load.iter().all(|&v| v > 0));
+    /// assert!(load.iter().sum::<u64>() == 1000);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `parts` is greater than `total`, or `parts` is zero while `total` isn't.
+    #[cfg(feature = "alloc")]
+    fn composition_positive(&mut self, total: u64, parts: usize) -> Vec<u64> {
+        assert!(
+            parts as u64 <= total,
+            "`total` can't be split into more than `total` positive parts"
+        );
+        let mut ret = self.composition(total - parts as u64, parts);
+        ret.iter_mut().for_each(|v| *v += 1);
+        ret
+    }
+
+    /// Returns a uniformly distributed derangement of `0..len`, which is
+    /// a permutation where no element stays in its original position.
+    ///
+    /// Returns `None` when `len` is 1, since no such permutation exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng();
+    /// let people = ["alice", "bob", "carol", "dave"];
+    /// let assignment = rng.derangement(people.len()).unwrap();
+    /// for (giver, &receiver) in assignment.iter().enumerate() {
+    ///     assert!(giver != receiver);
+    ///     println!("{} gives a gift to {}", people[giver], people[receiver]);
+    /// }
+    /// assert!(rng.derangement(1).is_none());
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn derangement(&mut self, len: usize) -> Option<Vec<usize>> {
+        if len == 1 {
+            return None;
+        }
+        let mut v = (0..len).collect::<Vec<_>>();
+        // Roughly 1/e of all permutations are derangements, so
+        // rejection takes less than 3 attempts on average.
+        loop {
+            self.shuffle(&mut v);
+            if v.iter().enumerate().all(|(i, &x)| i != x) {
+                return Some(v);
+            }
+            v.iter_mut().enumerate().for_each(|(i, x)| *x = i);
+        }
+    }
+
     /// Clones `slice` into a new `Vec`, calls [`Generator::shuffle`]
     /// on it, and returns the result.
     #[cfg(feature = "alloc")]
@@ -619,3 +2642,185 @@ pub trait Generator: Sized {
         v
     }
 }
+
+/// Returns a `String` of `len` characters randomly selected from the ASCII `charset`.
+#[cfg(feature = "alloc")]
+#[inline]
+fn charset_string<G: Generator>(rng: &mut G, charset: &[u8], len: usize) -> String {
+    debug_assert!(charset.is_ascii());
+    let mut bytes = vec![0; len];
+    rng.fill_from_charset(charset, &mut bytes);
+    // SAFETY: Every byte was selected from `charset`, which only contains ascii values.
+    unsafe { String::from_utf8_unchecked(bytes) }
+}
+
+/// The rarely taken rejection path of [`Generator::bound`], where `high` and `low`
+/// are the halves of the initial product.
+#[cold]
+#[inline(never)]
+fn bound_rejection<G: Generator>(rng: &mut G, max: u64, mut high: u64, mut low: u64) -> u64 {
+    // The dreaded division.
+    let threshold = max.wrapping_neg() % max;
+    while low < threshold {
+        (high, low) = util::wide_mul(rng.u64(), max);
+    }
+    high
+}
+
+/// Resolves a pair of range bounds into an inclusive interval \[`min`, `max`\].
+#[inline]
+fn inclusive_bounds(start: Bound<&u64>, end: Bound<&u64>) -> (u64, u64) {
+    let min = match start {
+        Bound::Included(&v) => Some(v),
+        Bound::Excluded(&v) => v.checked_add(1),
+        Bound::Unbounded => Some(0),
+    };
+    let max = match end {
+        Bound::Included(&v) => Some(v),
+        Bound::Excluded(&v) => v.checked_sub(1),
+        Bound::Unbounded => Some(u64::MAX),
+    };
+    match (min, max) {
+        (Some(min), Some(max)) if min <= max => (min, max),
+        _ => panic!("range must not be empty"),
+    }
+}
+
+/// An infinite iterator over the output of a borrowed [`Generator`].
+///
+/// Created by [`Generator::by_ref_iter`].
+#[derive(Debug)]
+pub struct GeneratorIter<'a, G: Generator>(&'a mut G);
+
+impl<G: Generator> Iterator for GeneratorIter<'_, G> {
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.0.u64())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<G: Generator> FusedIterator for GeneratorIter<'_, G> {}
+
+/// Object-safe subset of [`Generator`], for choosing a generator at runtime.
+///
+/// [`Generator`] can't be used as a trait object, since it has constructors and generic
+/// methods. This trait is implemented for every [`Generator`], and boxed trait objects
+/// implement [`Generator`] in turn, so all of its methods remain available through the box.
+///
+/// Boxed trait objects can't be created from entropy, so [`Generator::try_new`] fails
+/// with [`ErrorKind::Unsupported`] for them. The methods of this trait are prefixed
+/// with `dyn_` so they never collide with methods from `rand` on the same generators.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// let secure = false;
+/// let mut rng: Box<dyn DynGenerator> = match secure {
+///     true => Box::new(new_rng_secure()),
+///     false => Box::new(new_rng()),
+/// };
+/// let val = rng.bound(69);
+/// assert!(val < 69);
+/// let mut buf = [0; 13];
+/// rng.dyn_fill(&mut buf);
+/// ```
+pub trait DynGenerator {
+    /// Returns a uniformly distributed `u64` in the interval [0, 2<sup>64</sup>).
+    fn dyn_u64(&mut self) -> u64;
+
+    /// Fills `dst` with uniformly distributed bytes.
+    fn dyn_fill(&mut self, dst: &mut [u8]);
+}
+
+impl<G: Generator> DynGenerator for G {
+    #[inline]
+    fn dyn_u64(&mut self) -> u64 {
+        self.u64()
+    }
+
+    #[inline]
+    fn dyn_fill(&mut self, dst: &mut [u8]) {
+        for chunk in dst.chunks_mut(size_of::<u64>()) {
+            let bytes = self.u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+}
+
+macro_rules! impl_generator_for_dyn {
+    ($($ty:ty),+) => {$(
+        #[cfg(feature = "alloc")]
+        impl Generator for $ty {
+            #[inline]
+            fn try_new() -> Result<Self, Error> {
+                Err(Error::new(ErrorKind::Unsupported))
+            }
+
+            #[inline]
+            fn u64(&mut self) -> u64 {
+                (**self).dyn_u64()
+            }
+        }
+    )+};
+}
+
+impl_generator_for_dyn!(Box<dyn DynGenerator>, Box<dyn DynGenerator + Send>);
+
+/// Fills `dst` with independent standard normal values.
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn fill_normal<G: Generator>(rng: &mut G, dst: &mut [f64]) {
+    for chunk in dst.chunks_mut(2) {
+        let (x, y) = rng.f64_normal();
+        chunk[0] = x;
+        if let Some(v) = chunk.get_mut(1) {
+            *v = y;
+        }
+    }
+}
+
+/// Returns a uniformly distributed `f64` within the `index`th cell of [0.0, 1.0),
+/// when it's partitioned into cells of size `width`.
+#[inline]
+fn stratum_sample<G: Generator>(rng: &mut G, index: usize, width: f64) -> f64 {
+    let v = (index as f64 + rng.f64()) * width;
+    // Rounding can push values from the last cell up to exactly 1.0.
+    v.min(1.0 - f64::EPSILON / 2.0)
+}
+
+/// Returns a uniformly distributed `u128` in the interval [0, `max`).
+#[cfg(feature = "std")]
+#[inline]
+fn bound_u128<G: Generator>(rng: &mut G, max: u128) -> u128 {
+    if max <= u64::MAX as u128 {
+        return rng.bound(max as u64) as u128;
+    }
+    // Rejection sampling over the smallest power of 2 containing the interval,
+    // which succeeds at least half the time.
+    let shift = (max - 1).leading_zeros();
+    loop {
+        let x = ((rng.u64() as u128) << u64::BITS | rng.u64() as u128) >> shift;
+        if x < max {
+            return x;
+        }
+    }
+}
+
+/// Converts an amount of nanoseconds into a `Duration`.
+#[cfg(feature = "std")]
+#[inline]
+fn duration_from_nanos(nanos: u128) -> Duration {
+    const NANOS_PER_SEC: u128 = 1_000_000_000;
+    let secs = (nanos / NANOS_PER_SEC) as u64;
+    let subsec_nanos = (nanos % NANOS_PER_SEC) as u32;
+    Duration::new(secs, subsec_nanos)
+}
@@ -1,5 +1,8 @@
+use crate::entropy::{EntropySource, OsEntropy};
+use crate::error::Error;
 use crate::rng::{Generator, SeedableGenerator};
 use crate::util;
+use core::fmt;
 
 /// Rust implementation of the RomuQuad PRNG.
 ///
@@ -7,35 +10,53 @@ use crate::util;
 /// but not cryptographically secure.
 ///
 /// More information can be found at: <https://romu-random.org/>.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct RomuQuad {
     state: [u64; 4],
 }
 
+impl fmt::Debug for RomuQuad {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("all `RomuQuad` fields are private")
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for RomuQuad {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "all `RomuQuad` fields are private");
+    }
+}
+
 impl Default for RomuQuad {
     fn default() -> Self {
         Self::new_with_seed(0)
     }
 }
 
-impl SeedableGenerator for RomuQuad {
-    fn new_with_seed(seed: u64) -> Self {
+impl RomuQuad {
+    /// Identical to [`SeedableGenerator::new_with_seed`], but usable
+    /// in `const` and `static` contexts.
+    #[inline]
+    pub const fn new_with_seed(seed: u64) -> Self {
         let state = util::state_from_seed(seed);
         let mut ret = Self { state };
-        let _discard_first = ret.u64();
+        let _discard_first = ret.step();
         ret
     }
-}
 
-impl Generator for RomuQuad {
+    /// Returns the internal state of the generator.
+    ///
+    /// Anyone who sees the state can reproduce all future output of the generator, which
+    /// is why it's left out of the `Debug` output. Only use this where that's acceptable,
+    /// such as when debugging a simulation.
     #[inline]
-    fn try_new() -> Result<Self, getrandom::Error> {
-        let state = util::state_from_entropy()?;
-        Ok(Self { state })
+    pub const fn expose_state(&self) -> [u64; 4] {
+        self.state
     }
 
-    #[cfg_attr(feature = "inline", inline)]
-    fn u64(&mut self) -> u64 {
+    #[inline(always)]
+    const fn step(&mut self) -> u64 {
         let tmp = self.state;
         self.state[0] = tmp[3].wrapping_mul(15241094284759029579);
         self.state[1] = tmp[3].wrapping_add(tmp[0].rotate_left(52));
@@ -44,3 +65,28 @@ impl Generator for RomuQuad {
         tmp[1]
     }
 }
+
+impl SeedableGenerator for RomuQuad {
+    #[inline]
+    fn new_with_seed(seed: u64) -> Self {
+        Self::new_with_seed(seed)
+    }
+}
+
+impl Generator for RomuQuad {
+    #[inline]
+    fn try_new() -> Result<Self, Error> {
+        Self::try_new_from(&mut OsEntropy)
+    }
+
+    #[inline]
+    fn try_new_from<S: EntropySource + ?Sized>(source: &mut S) -> Result<Self, Error> {
+        let state = util::state_from_entropy(source)?;
+        Ok(Self { state })
+    }
+
+    #[cfg_attr(feature = "inline", inline)]
+    fn u64(&mut self) -> u64 {
+        self.step()
+    }
+}
@@ -1,5 +1,8 @@
+use crate::entropy::{EntropySource, OsEntropy};
+use crate::error::Error;
 use crate::rng::{Generator, SeedableGenerator};
 use crate::util;
+use core::fmt;
 
 /// Rust implementation of the RomuTrio PRNG.
 ///
@@ -7,35 +10,53 @@ use crate::util;
 /// but not cryptographically secure.
 ///
 /// More information can be found at: <https://romu-random.org/>.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct RomuTrio {
     state: [u64; 3],
 }
 
+impl fmt::Debug for RomuTrio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("all `RomuTrio` fields are private")
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for RomuTrio {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "all `RomuTrio` fields are private");
+    }
+}
+
 impl Default for RomuTrio {
     fn default() -> Self {
         Self::new_with_seed(0)
     }
 }
 
-impl SeedableGenerator for RomuTrio {
-    fn new_with_seed(seed: u64) -> Self {
+impl RomuTrio {
+    /// Identical to [`SeedableGenerator::new_with_seed`], but usable
+    /// in `const` and `static` contexts.
+    #[inline]
+    pub const fn new_with_seed(seed: u64) -> Self {
         let state = util::state_from_seed(seed);
         let mut ret = Self { state };
-        let _discard_first = ret.u64();
+        let _discard_first = ret.step();
         ret
     }
-}
 
-impl Generator for RomuTrio {
+    /// Returns the internal state of the generator.
+    ///
+    /// Anyone who sees the state can reproduce all future output of the generator, which
+    /// is why it's left out of the `Debug` output. Only use this where that's acceptable,
+    /// such as when debugging a simulation.
     #[inline]
-    fn try_new() -> Result<Self, getrandom::Error> {
-        let state = util::state_from_entropy()?;
-        Ok(Self { state })
+    pub const fn expose_state(&self) -> [u64; 3] {
+        self.state
     }
 
-    #[cfg_attr(feature = "inline", inline)]
-    fn u64(&mut self) -> u64 {
+    #[inline(always)]
+    const fn step(&mut self) -> u64 {
         let tmp = self.state;
         self.state[0] = tmp[2].wrapping_mul(15241094284759029579);
         self.state[1] = tmp[1].wrapping_sub(tmp[0]).rotate_left(12);
@@ -43,3 +64,28 @@ impl Generator for RomuTrio {
         tmp[0]
     }
 }
+
+impl SeedableGenerator for RomuTrio {
+    #[inline]
+    fn new_with_seed(seed: u64) -> Self {
+        Self::new_with_seed(seed)
+    }
+}
+
+impl Generator for RomuTrio {
+    #[inline]
+    fn try_new() -> Result<Self, Error> {
+        Self::try_new_from(&mut OsEntropy)
+    }
+
+    #[inline]
+    fn try_new_from<S: EntropySource + ?Sized>(source: &mut S) -> Result<Self, Error> {
+        let state = util::state_from_entropy(source)?;
+        Ok(Self { state })
+    }
+
+    #[cfg_attr(feature = "inline", inline)]
+    fn u64(&mut self) -> u64 {
+        self.step()
+    }
+}
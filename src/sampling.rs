@@ -0,0 +1,203 @@
+//! Sampling from scores, such as the logits produced by a language model.
+//!
+//! Scores of negative infinity are never chosen, which can be used to mask out
+//! invalid choices.
+//!
+//! # Examples
+//!
+//! ```
+//! use ya_rand::*;
+//!
+//! let mut rng = new_rng();
+//! let logits = [2.0, -1.0, 3.5, f32::NEG_INFINITY];
+//! let mut scratch = [0; 4];
+//! let token = sampling::top_p(&mut rng, &logits, 0.95, 0.7, &mut scratch);
+//! assert!(token != 3);
+//! ```
+
+use crate::rng::Generator;
+
+/// Returns the index of the largest value of `scores` after adding independent
+/// standard Gumbel noise to each of them, or `None` if `scores` is empty.
+///
+/// This is the Gumbel-max trick: the index is chosen with probability proportional
+/// to `exp(score)`, the softmax of `scores`, without needing to normalize them. This
+/// takes a single pass, so it can choose from scores as they're computed. Scores of
+/// negative infinity are never chosen, unless all of the scores are.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// let mut rng = new_rng();
+/// // Log-weights of 1, 2, and 0.
+/// let scores = [0.0, 2.0_f64.ln(), f64::NEG_INFINITY];
+/// let chosen = sampling::argmax_with_gumbel(&mut rng, &scores).unwrap();
+/// assert!(chosen < 2);
+/// assert!(sampling::argmax_with_gumbel(&mut rng, &[]).is_none());
+/// ```
+pub fn argmax_with_gumbel(rng: &mut impl Generator, scores: &[f64]) -> Option<usize> {
+    scores
+        .iter()
+        .map(|&score| score + rng.f64_gumbel())
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(i, _)| i)
+}
+
+/// Returns the index of a value from `logits` sampled using top-k sampling.
+///
+/// Only the `k` largest logits are considered, and one of them is chosen with
+/// probability proportional to `exp(logit / temperature)`, which is the softmax of the
+/// remaining logits at the given `temperature`. Lower temperatures make the largest
+/// logits more likely, while higher ones flatten the distribution. Logits of negative
+/// infinity are never chosen, which can be used to mask out invalid choices.
+///
+/// `scratch` must have the same length as `logits`, and is used to avoid allocating,
+/// so the same buffer can be reused for every call. Finding the top `k` logits takes
+/// linear time.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// let mut rng = new_rng();
+/// let logits = [2.0, -1.0, 3.5, 0.0, 3.0];
+/// let mut scratch = [0; 5];
+/// let token = sampling::top_k(&mut rng, &logits, 2, 0.8, &mut scratch);
+/// assert!(token == 2 || token == 4);
+/// ```
+///
+/// # Panics
+///
+/// If `k` is zero, `temperature` isn't positive and finite, the length of `scratch`
+/// isn't the length of `logits`, or none of the considered logits are finite.
+pub fn top_k(
+    rng: &mut impl Generator,
+    logits: &[f32],
+    k: usize,
+    temperature: f32,
+    scratch: &mut [usize],
+) -> usize {
+    assert!(k != 0, "`k` must not be zero");
+    let indices = sorted_indices(logits, scratch);
+    let k = k.min(indices.len());
+    if k < indices.len() {
+        indices.select_nth_unstable_by(k - 1, |&a, &b| logits[b].total_cmp(&logits[a]));
+    }
+    sample_softmax(rng, logits, &indices[..k], temperature)
+}
+
+/// Returns the index of a value from `logits` sampled using nucleus (top-p) sampling.
+///
+/// The logits are converted into probabilities by taking their softmax at the given
+/// `temperature`, and only the smallest set of the most likely logits whose total
+/// probability is at least `p` is considered. One of those is chosen with probability
+/// proportional to its own probability. Lower temperatures make the largest logits more
+/// likely, while higher ones flatten the distribution. Logits of negative infinity are
+/// never chosen, which can be used to mask out invalid choices.
+///
+/// `scratch` must have the same length as `logits`, and is used to avoid allocating,
+/// so the same buffer can be reused for every call. This sorts the logits, so it takes
+/// O(n log n) time.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// let mut rng = new_rng();
+/// let logits = [5.0, -1.0, 5.0, 0.0];
+/// let mut scratch = [0; 4];
+/// // The two largest logits make up more than 99% of the probability.
+/// let token = sampling::top_p(&mut rng, &logits, 0.9, 1.0, &mut scratch);
+/// assert!(token == 0 || token == 2);
+/// ```
+///
+/// # Panics
+///
+/// If `p` isn't within the interval (0.0, 1.0], `temperature` isn't positive and finite,
+/// the length of `scratch` isn't the length of `logits`, or none of the logits are finite.
+pub fn top_p(
+    rng: &mut impl Generator,
+    logits: &[f32],
+    p: f64,
+    temperature: f32,
+    scratch: &mut [usize],
+) -> usize {
+    assert!(0.0 < p && p <= 1.0, "`p` must be within (0.0, 1.0]");
+    let indices = sorted_indices(logits, scratch);
+    indices.sort_unstable_by(|&a, &b| logits[b].total_cmp(&logits[a]));
+    let weight = softmax_weight(logits, indices, temperature);
+    let total = indices.iter().map(|&i| weight(logits[i])).sum::<f64>();
+    // Stop at the first prefix reaching `p` of the total, which always includes the
+    // largest logit, even when rounding keeps the sum from ever reaching `p`.
+    let mut len = 0;
+    let mut mass = 0.0;
+    for &i in indices.iter() {
+        mass += weight(logits[i]);
+        len += 1;
+        if mass >= p * total {
+            break;
+        }
+    }
+    sample_softmax(rng, logits, &indices[..len], temperature)
+}
+
+/// Fills `scratch` with the indices of `logits`, which must have the same length.
+#[inline]
+fn sorted_indices<'a>(logits: &[f32], scratch: &'a mut [usize]) -> &'a mut [usize] {
+    assert!(
+        scratch.len() == logits.len(),
+        "`scratch` must have the same length as `logits`"
+    );
+    scratch.iter_mut().enumerate().for_each(|(i, v)| *v = i);
+    scratch
+}
+
+/// Returns a function mapping each of the logits at `indices` to its unnormalized
+/// softmax weight at `temperature`.
+#[inline]
+fn softmax_weight(logits: &[f32], indices: &[usize], temperature: f32) -> impl Fn(f32) -> f64 {
+    assert!(
+        temperature > 0.0 && temperature.is_finite(),
+        "`temperature` must be positive and finite"
+    );
+    let max = indices
+        .iter()
+        .map(|&i| logits[i])
+        .max_by(f32::total_cmp)
+        .filter(|max| max.is_finite())
+        .expect("logits must contain a finite value");
+    // Subtracting the largest logit keeps every weight in [0.0, 1.0], so nothing overflows.
+    move |logit| ((f64::from(logit) - f64::from(max)) / f64::from(temperature)).exp()
+}
+
+/// Returns one of `indices`, chosen with probability proportional to the softmax
+/// weight of its logit at `temperature`.
+fn sample_softmax<G: Generator>(
+    rng: &mut G,
+    logits: &[f32],
+    indices: &[usize],
+    temperature: f32,
+) -> usize {
+    let weight = softmax_weight(logits, indices, temperature);
+    let total = indices.iter().map(|&i| weight(logits[i])).sum::<f64>();
+    let mut target = rng.f64() * total;
+    let mut chosen = indices[0];
+    for &i in indices {
+        let w = weight(logits[i]);
+        // Skipping zero weights keeps masked logits from being chosen due to rounding.
+        if w == 0.0 {
+            continue;
+        }
+        chosen = i;
+        if target < w {
+            break;
+        }
+        target -= w;
+    }
+    chosen
+}
@@ -1,17 +1,139 @@
+use crate::entropy::{EntropySource, OsEntropy};
+use crate::error::{Error, ErrorKind};
 use crate::rng::{Generator, SecureGenerator};
+use crate::util;
 use chachacha::{BUF_LEN_U64, ChaCha8Djb, SEED_LEN_U8};
 use core::fmt;
 use core::mem::MaybeUninit;
+use core::ptr;
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
 
 /// A cryptographically secure random number generator.
 ///
 /// The current implementation uses ChaCha with 8 rounds and a 64-bit counter.
 /// This allows for 1 ZiB (2<sup>70</sup> bytes) of output before repeating.
 /// That's over 147 **quintillion** calls to [`SecureRng::u64`].
+///
+/// By default the generator is never reseeded. Use [`SecureRng::with_reseed_policy`]
+/// to bound how much output can be produced from a single seed.
+///
+/// Instances aren't protected against `fork()`: the parent and child continue from the
+/// same state and produce identical output. Create a new instance in the child, or use
+/// `secure_global`, which is reseeded automatically after a fork.
+// Laid out so the fields touched by every call to `u64` come first and start
+// on a cache line boundary, while everything else lives in `cold` at the end.
+#[repr(C, align(64))]
 pub struct SecureRng {
-    buf: [u64; BUF_LEN_U64],
     index: usize,
+    buf: [u64; BUF_LEN_U64],
     internal: ChaCha8Djb,
+    pub(crate) cold: Cold,
+}
+
+/// Reseed policy, seed, and metrics of a [`SecureRng`], which are only
+/// touched when the buffer is refilled or when queried directly.
+#[repr(C)]
+pub(crate) struct Cold {
+    policy: ReseedPolicy,
+    bytes_since_reseed: u64,
+    seed: [u8; SEED_LEN_U8],
+    /// Amount of blocks between `seed` and the next block `internal` produces.
+    block_pos: u64,
+    /// Amount of blocks between `seed` and the first block in `buf`.
+    buf_block: u64,
+    /// Amount of bytes handed out, not counting the `index` values taken from `buf`.
+    bytes_out: u64,
+    blocks_computed: u64,
+    reseeds: u64,
+    #[cfg(feature = "std")]
+    seeded_at: Instant,
+}
+
+/// Size of a single ChaCha block in bytes.
+const BLOCK_LEN: usize = 64;
+/// Amount of ChaCha blocks used by every refill of the buffer.
+const BUF_BLOCKS: u64 = (BUF_LEN_U64 * size_of::<u64>() / BLOCK_LEN) as u64;
+/// Amount of `u64` values in a single ChaCha block.
+const BLOCK_LEN_U64: u128 = (BLOCK_LEN / size_of::<u64>()) as u128;
+/// Offset of the 64-bit block counter in the seed, which `chachacha` lays out
+/// as the key, followed by the counter, followed by the nonce.
+const COUNTER_OFFSET: usize = 32;
+
+/// Seed of the known-answer self-test: key bytes 0 through 31, a block counter two
+/// blocks before its low 32 bits wrap around, and nonce bytes 64 through 71.
+pub(crate) const KAT_SEED: [u8; SEED_LEN_U8] = {
+    let mut seed = [0; SEED_LEN_U8];
+    let mut i = 0;
+    while i < COUNTER_OFFSET {
+        seed[i] = i as u8;
+        i += 1;
+    }
+    let counter = 0xffff_fffe_u64.to_le_bytes();
+    while i < COUNTER_OFFSET + size_of::<u64>() {
+        seed[i] = counter[i - COUNTER_OFFSET];
+        i += 1;
+    }
+    while i < SEED_LEN_U8 {
+        seed[i] = i as u8 + 24;
+        i += 1;
+    }
+    seed
+};
+/// The first 4 blocks of ChaCha8 keystream for `KAT_SEED`, as little-endian `u64` values.
+pub(crate) const KAT_WORDS: [u64; 32] = [
+    0xb17e_4b62_678a_1204,
+    0x61ee_5207_5589_2601,
+    0x7c73_c7c5_1a6b_e2b6,
+    0xd72e_72b0_7a67_a093,
+    0xb3b9_1683_6cde_0c39,
+    0x1a2e_4e18_c0c2_68bd,
+    0x7aad_1286_9bf5_dbf5,
+    0x1565_71d5_1e61_88a0,
+    0xa965_341b_14cd_080f,
+    0xef25_586a_b2e2_7d91,
+    0xbde2_cd79_49bd_e939,
+    0xe45c_3b52_ab72_d874,
+    0xf832_f5fe_7d1b_afd8,
+    0x9f52_3d91_2833_e112,
+    0xa9ab_804c_22c5_88b4,
+    0xa3cf_b765_3ac4_37af,
+    0x2cc3_9ee8_884a_a0fa,
+    0x0d6d_03a9_8381_0205,
+    0x1cc5_f59e_4bb3_2d29,
+    0x5f50_1fa3_937a_d1f2,
+    0xdf7a_9b9b_474a_0aa2,
+    0x9893_8162_bdc4_6035,
+    0x4194_eac2_7416_3921,
+    0x3068_7adb_c79f_34c9,
+    0x9167_61b1_3346_8ad6,
+    0xc17c_7c61_cc00_729e,
+    0x9edd_7d0d_6121_0ccd,
+    0x6554_f775_8e94_0f60,
+    0x2013_9df5_5101_8255,
+    0x779f_6d7c_d780_4f68,
+    0x9a21_009b_a7b0_165f,
+    0xa571_fbcc_3ed7_a332,
+];
+/// State of the self-test run by constructors when the **self-test** feature is enabled.
+#[cfg(feature = "self-test")]
+static SELF_TEST: core::sync::atomic::AtomicU8 =
+    core::sync::atomic::AtomicU8::new(SELF_TEST_PENDING);
+#[cfg(feature = "self-test")]
+const SELF_TEST_PENDING: u8 = 0;
+#[cfg(feature = "self-test")]
+const SELF_TEST_PASSED: u8 = 1;
+#[cfg(feature = "self-test")]
+const SELF_TEST_FAILED: u8 = 2;
+
+/// Returns `seed` with its block counter advanced by `blocks`.
+#[inline]
+fn seed_at(seed: &[u8; SEED_LEN_U8], blocks: u64) -> [u8; SEED_LEN_U8] {
+    let mut ret = *seed;
+    let counter = &mut ret[COUNTER_OFFSET..COUNTER_OFFSET + size_of::<u64>()];
+    let start = u64::from_le_bytes((&*counter).try_into().unwrap());
+    counter.copy_from_slice(&start.wrapping_add(blocks).to_le_bytes());
+    ret
 }
 
 impl fmt::Debug for SecureRng {
@@ -20,39 +142,485 @@ impl fmt::Debug for SecureRng {
     }
 }
 
+#[cfg(feature = "defmt")]
+impl defmt::Format for SecureRng {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "all `SecureRng` fields are private");
+    }
+}
+
+impl SecureRng {
+    /// Creates a generator from an explicit 256-bit `key`, starting at position 0.
+    ///
+    /// Unlike generators created from entropy, the output of the generator is fully
+    /// determined by `key`, which allows for reproducible results and seeking using
+    /// [`SecureRng::set_word_pos`]. The generator is only as secure as the key, so it
+    /// should come from a trusted source of randomness and be kept secret.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng1 = SecureRng::new_with_key([69; 32]);
+    /// let mut rng2 = SecureRng::new_with_key([69; 32]);
+    /// assert!(rng1.u64() == rng2.u64());
+    /// ```
+    #[inline]
+    pub fn new_with_key(key: [u8; 32]) -> Self {
+        let mut seed = [0; SEED_LEN_U8];
+        seed[..COUNTER_OFFSET].copy_from_slice(&key);
+        let mut source: &[u8] = &seed;
+        // Infallible, since the slice source just copies its contents.
+        Self::try_new_from(&mut source).unwrap()
+    }
+
+    /// Creates a generator from a seed derived from `master_key` and a `context` label.
+    ///
+    /// Lets a service derive separate generators for every purpose (e.g. `"session-tokens"`
+    /// and `"csrf"`) from a single provisioned secret. Generators with different contexts
+    /// are independent of each other, and seeing the output of one reveals nothing about
+    /// the others or the master key. The seed is the first 48 bytes of HKDF-SHA256 from
+    /// RFC 5869, with `master_key` as the input keying material, an empty salt, and
+    /// `context` as the info parameter.
+    ///
+    /// The generator is only as secure as `master_key`, which should contain at least
+    /// 256 bits of entropy and be kept secret.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let master_key = [69; 32];
+    /// let mut tokens = SecureRng::derive(&master_key, "session-tokens");
+    /// let mut csrf = SecureRng::derive(&master_key, "csrf");
+    /// assert!(tokens.u64() != csrf.u64());
+    /// let mut again = SecureRng::derive(&master_key, "csrf");
+    /// assert!(again.u64() == SecureRng::derive(&master_key, "csrf").u64());
+    /// ```
+    #[cfg(feature = "hkdf")]
+    pub fn derive(master_key: &[u8], context: &str) -> Self {
+        let mut seed = [0; SEED_LEN_U8];
+        util::hkdf_sha256(master_key, context.as_bytes(), &mut seed);
+        let mut source: &[u8] = &seed;
+        // Infallible, since the slice source just copies its contents.
+        Self::try_new_from(&mut source).unwrap()
+    }
+
+    /// Checks the ChaCha implementation used by the generator against known answers.
+    ///
+    /// The keystream is computed from an embedded seed through both the wide buffer
+    /// refill used by [`SecureRng::u64`] and the byte oriented path used by
+    /// [`SecureGenerator::fill_bytes`], including a partial block, and compared against
+    /// an embedded test vector. The seed places the 64-bit block counter right before
+    /// its low half wraps around, so carrying into the high half is checked as well.
+    /// This catches miscompilation and bugs in the SIMD backend selected for the
+    /// current processor, which would otherwise silently produce a broken keystream.
+    ///
+    /// Fails with [`ErrorKind::SelfTest`] if any output doesn't match. Enabling the
+    /// **self-test** feature runs this once, the first time any generator is created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// SecureRng::self_test().expect("ChaCha backend is broken");
+    /// ```
+    ///
+    /// [`ErrorKind::SelfTest`]: crate::ErrorKind::SelfTest
+    pub fn self_test() -> Result<(), Error> {
+        let mut words = [0; BUF_LEN_U64];
+        ChaCha8Djb::from(KAT_SEED).fill_block_u64(&mut words);
+        let len = BUF_LEN_U64.min(KAT_WORDS.len());
+        let mut bytes = [0; KAT_WORDS.len() * size_of::<u64>()];
+        ChaCha8Djb::from(KAT_SEED).fill(&mut bytes);
+        let mut partial = [0; BLOCK_LEN + BLOCK_LEN / 2 + 3];
+        ChaCha8Djb::from(KAT_SEED).fill(&mut partial);
+        let expected =
+            |i: usize| KAT_WORDS[i / size_of::<u64>()].to_le_bytes()[i % size_of::<u64>()];
+        let passed = words[..len] == KAT_WORDS[..len]
+            && bytes.iter().enumerate().all(|(i, &b)| b == expected(i))
+            && partial.iter().enumerate().all(|(i, &b)| b == expected(i));
+        match passed {
+            true => Ok(()),
+            false => Err(Error::new(ErrorKind::SelfTest)),
+        }
+    }
+
+    /// Returns the position of the next value [`SecureRng::u64`] will return,
+    /// counted in `u64` values since the generator was (re)seeded.
+    ///
+    /// Note that `rand_chacha` counts positions in 32-bit words instead, so its
+    /// positions are twice as large.
+    #[inline]
+    pub fn word_pos(&self) -> u128 {
+        let (block, index) = match self.index < self.buf.len() {
+            true => (self.cold.buf_block, self.index),
+            // The next call to `u64` refills the buffer.
+            false => (self.cold.block_pos, 0),
+        };
+        block as u128 * BLOCK_LEN_U64 + index as u128
+    }
+
+    /// Returns the total amount of bytes the generator has handed out since it was created,
+    /// including output from before any reseeds.
+    ///
+    /// Every call to [`SecureRng::u64`] (which every other method is built on) counts
+    /// as 8 bytes, and every call to [`SecureRng::fill_bytes`] counts as the length of
+    /// its destination. Values which are buffered but haven't been handed out yet aren't
+    /// counted, and neither is seeking using [`SecureRng::set_word_pos`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng_secure();
+    /// rng.u64();
+    /// rng.fill_bytes(&mut [0; 13]);
+    /// assert!(rng.bytes_generated() == 21);
+    /// ```
+    #[inline]
+    pub fn bytes_generated(&self) -> u64 {
+        self.cold
+            .bytes_out
+            .wrapping_add((self.index * size_of::<u64>()) as u64)
+    }
+
+    /// Returns the total amount of 64-byte ChaCha blocks the generator has computed since
+    /// it was created, including blocks which were buffered but never handed out.
+    ///
+    /// Together with [`SecureRng::bytes_generated`], this shows how much keystream is
+    /// wasted by seeking and reseeding.
+    #[inline]
+    pub fn blocks_computed(&self) -> u64 {
+        self.cold.blocks_computed
+    }
+
+    /// Returns the amount of times the generator has reseeded itself since it was created,
+    /// as configured using [`SecureRng::with_reseed_policy`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let policy = ReseedPolicy::NEVER.with_max_bytes(1 << 10);
+    /// let mut rng = new_rng_secure().with_reseed_policy(policy);
+    /// rng.fill_bytes(&mut [0; 1 << 12]);
+    /// assert!(rng.reseeds() >= 1);
+    /// assert!(rng.blocks_computed() >= (1 << 12) / 64);
+    /// ```
+    #[inline]
+    pub fn reseeds(&self) -> u64 {
+        self.cold.reseeds
+    }
+
+    /// Moves the generator to position `pos` in its output stream, so the next
+    /// call to [`SecureRng::u64`] returns the value at that position.
+    ///
+    /// Runs in constant time regardless of the distance. Positions are taken modulo
+    /// 2<sup>67</sup>, which is the length of the stream. Output produced afterwards by
+    /// [`SecureRng::fill_bytes`] continues after the values at `pos` which are buffered
+    /// for `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = SecureRng::new_with_key([69; 32]);
+    /// let vals = rng.array_u64::<100>();
+    /// rng.set_word_pos(42);
+    /// assert!(rng.u64() == vals[42]);
+    /// assert!(rng.word_pos() == 43);
+    /// ```
+    #[inline]
+    pub fn set_word_pos(&mut self, pos: u128) {
+        let block = (pos / BLOCK_LEN_U64) as u64;
+        self.internal = ChaCha8Djb::from(seed_at(&self.cold.seed, block));
+        self.internal.fill_block_u64(&mut self.buf);
+        self.cold.blocks_computed = self.cold.blocks_computed.wrapping_add(BUF_BLOCKS);
+        self.cold.buf_block = block;
+        self.cold.block_pos = block.wrapping_add(BUF_BLOCKS);
+        self.set_index((pos % BLOCK_LEN_U64) as usize);
+    }
+
+    /// Makes the generator reseed itself using entropy from the operating system
+    /// whenever `policy` says it's due.
+    ///
+    /// The policy is only checked when the internal buffer is refilled, and at the start
+    /// of every call to [`SecureRng::fill_bytes`], so the overhead is negligible. As a
+    /// consequence, limits can be exceeded by up to 256 bytes of buffered output, or
+    /// by the length of a single `fill_bytes` call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ya_rand::*;
+    ///
+    /// let policy = ReseedPolicy::NEVER
+    ///     .with_max_bytes(1 << 30)
+    ///     .with_max_age(Duration::from_secs(60 * 60));
+    /// let mut rng = SecureRng::new().with_reseed_policy(policy);
+    /// let val = rng.bound(69);
+    /// assert!(val < 69);
+    /// ```
+    ///
+    /// Reseeding never panics. If the operating system fails to provide entropy, the
+    /// current seed stays in use and reseeding is attempted again on the next call.
+    #[inline]
+    pub fn with_reseed_policy(mut self, policy: ReseedPolicy) -> Self {
+        self.cold.policy = policy;
+        self
+    }
+
+    /// Seeds a generator from `source` directly inside of `dst`.
+    ///
+    /// The entropy is written straight into the `seed` field, and the buffer is filled
+    /// in place, so the seed and keystream aren't copied through the stack of the caller.
+    /// Only the ChaCha state itself is constructed by value.
+    ///
+    /// # Safety
+    ///
+    /// `dst` must be valid for writes and suitably aligned. Its contents are only
+    /// initialized when `Ok` is returned.
+    pub(crate) unsafe fn try_init_in_place<S: EntropySource + ?Sized>(
+        dst: *mut Self,
+        source: &mut S,
+    ) -> Result<(), Error> {
+        #[cfg(feature = "self-test")]
+        self_test_once()?;
+        // SAFETY: The caller guarantees that every field of `dst` is valid for writes,
+        // and references are only created to fields which have been initialized.
+        unsafe {
+            let seed = ptr::addr_of_mut!((*dst).cold.seed);
+            seed.write([0; SEED_LEN_U8]);
+            let seed = &mut *seed;
+            // We want to randomize **all** bits of the matrix, even the counter.
+            if let Err(err) = source.fill(seed) {
+                util::wipe_slice(seed);
+                return Err(err);
+            }
+            event!(
+                debug,
+                source = core::any::type_name::<S>(),
+                "created SecureRng"
+            );
+            let internal = ptr::addr_of_mut!((*dst).internal);
+            internal.write(ChaCha8Djb::from(*seed));
+            let buf = ptr::addr_of_mut!((*dst).buf);
+            buf.write([0; BUF_LEN_U64]);
+            (*internal).fill_block_u64(&mut *buf);
+            ptr::addr_of_mut!((*dst).index).write(0);
+            ptr::addr_of_mut!((*dst).cold.policy).write(ReseedPolicy::NEVER);
+            ptr::addr_of_mut!((*dst).cold.bytes_since_reseed)
+                .write(size_of::<[u64; BUF_LEN_U64]>() as u64);
+            ptr::addr_of_mut!((*dst).cold.block_pos).write(BUF_BLOCKS);
+            ptr::addr_of_mut!((*dst).cold.buf_block).write(0);
+            ptr::addr_of_mut!((*dst).cold.bytes_out).write(0);
+            ptr::addr_of_mut!((*dst).cold.blocks_computed).write(BUF_BLOCKS);
+            ptr::addr_of_mut!((*dst).cold.reseeds).write(0);
+            #[cfg(feature = "std")]
+            ptr::addr_of_mut!((*dst).cold.seeded_at).write(Instant::now());
+        }
+        Ok(())
+    }
+
+    /// Accounts for `len` bytes of output, reseeding the generator first if it's due.
+    #[inline]
+    fn consume(&mut self, len: usize) {
+        self.cold.bytes_since_reseed = self.cold.bytes_since_reseed.saturating_add(len as u64);
+        if self.cold.policy.is_due(self) {
+            // Failures are already reported by `OsEntropy`. The current seed stays in use,
+            // and since the policy is still due, the next call tries to reseed again.
+            let _ = self.try_reseed();
+        }
+    }
+
+    /// Refills the buffer, which only happens once every `BUF_LEN_U64` calls to `u64`.
+    ///
+    /// Kept out of line so the hot path of `u64` stays tiny, until the `unlikely`
+    /// hint gets stabilized.
+    #[cold]
+    #[inline(never)]
+    fn refill(&mut self) {
+        self.consume(size_of_val(&self.buf));
+        self.internal.fill_block_u64(&mut self.buf);
+        self.cold.blocks_computed = self.cold.blocks_computed.wrapping_add(BUF_BLOCKS);
+        self.cold.buf_block = self.cold.block_pos;
+        self.cold.block_pos = self.cold.block_pos.wrapping_add(BUF_BLOCKS);
+        self.set_index(0);
+    }
+
+    /// Moves to `index` in the buffer, without changing [`SecureRng::bytes_generated`].
+    #[inline]
+    fn set_index(&mut self, index: usize) {
+        self.cold.bytes_out = self
+            .cold
+            .bytes_out
+            .wrapping_add((self.index * size_of::<u64>()) as u64)
+            .wrapping_sub((index * size_of::<u64>()) as u64);
+        self.index = index;
+    }
+
+    /// Moves `internal` forward past the `len` bytes just written by `fill_bytes`.
+    ///
+    /// Output is always produced in whole blocks, and `internal` is recreated
+    /// at the new position so it never depends on how the tail of the last block
+    /// was handled.
+    #[inline]
+    fn advance(&mut self, len: usize) {
+        let blocks = len.div_ceil(BLOCK_LEN) as u64;
+        self.cold.bytes_out = self.cold.bytes_out.wrapping_add(len as u64);
+        self.cold.blocks_computed = self.cold.blocks_computed.wrapping_add(blocks);
+        self.cold.block_pos = self.cold.block_pos.wrapping_add(blocks);
+        self.internal = ChaCha8Djb::from(seed_at(&self.cold.seed, self.cold.block_pos));
+    }
+
+    /// Fills `dst` with random data using all threads of the global rayon pool,
+    /// producing exactly the same output as [`SecureRng::fill_bytes`].
+    ///
+    /// ChaCha can produce any block of its output directly from the block counter, so
+    /// `dst` is split into chunks which are generated independently. This is only worth
+    /// it for large buffers, so anything up to a few dozen kilobytes is filled on the
+    /// current thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = new_rng_secure();
+    /// let mut buf = vec![0; 1 << 20];
+    /// rng.fill_bytes_par(&mut buf);
+    /// assert!(buf.iter().any(|&b| b != 0));
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn fill_bytes_par(&mut self, dst: &mut [u8]) {
+        use rayon::prelude::*;
+        const CHUNK_LEN: usize = 1 << 16;
+        if dst.len() <= CHUNK_LEN {
+            self.fill_bytes(dst);
+            return;
+        }
+        self.consume(dst.len());
+        let seed = self.cold.seed;
+        let start = self.cold.block_pos;
+        dst.par_chunks_mut(CHUNK_LEN)
+            .enumerate()
+            .for_each(|(i, chunk)| {
+                let offset = (i * (CHUNK_LEN / BLOCK_LEN)) as u64;
+                let mut internal = ChaCha8Djb::from(seed_at(&seed, start.wrapping_add(offset)));
+                internal.fill(chunk);
+            });
+        self.advance(dst.len());
+    }
+
+    /// Reseeds the generator using entropy from the operating system,
+    /// discarding any buffered output.
+    ///
+    /// # Panics
+    ///
+    /// If the operating system fails to provide entropy. Use
+    /// [`SecureRng::try_reseed`] to handle that case instead.
+    #[inline]
+    pub fn reseed(&mut self) {
+        self.try_reseed()
+            .expect("retrieving random data from the operating system should never fail");
+    }
+
+    /// Reseeds the generator using entropy from the operating system,
+    /// discarding any buffered output.
+    ///
+    /// Unlike [`SecureRng::reseed`], failing to retrieve entropy returns an error
+    /// and leaves the generator unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = SecureRng::new();
+    /// rng.try_reseed().unwrap();
+    /// assert!(rng.reseeds() == 1);
+    /// ```
+    #[cold]
+    #[inline(never)]
+    pub fn try_reseed(&mut self) -> Result<(), Error> {
+        event!(
+            debug,
+            bytes_since_reseed = self.cold.bytes_since_reseed,
+            "reseeding SecureRng"
+        );
+        let mut state = [0; SEED_LEN_U8];
+        if let Err(err) = OsEntropy.fill(&mut state) {
+            util::wipe_slice(&mut state);
+            return Err(err);
+        }
+        self.internal = ChaCha8Djb::from(state);
+        self.cold.seed = state;
+        util::wipe_slice(&mut state);
+        self.cold.block_pos = 0;
+        // Values buffered using the previous seed are discarded.
+        self.set_index(self.buf.len());
+        self.cold.bytes_since_reseed = 0;
+        self.cold.reseeds = self.cold.reseeds.wrapping_add(1);
+        #[cfg(feature = "std")]
+        {
+            self.cold.seeded_at = Instant::now();
+        }
+        Ok(())
+    }
+}
+
 impl SecureGenerator for SecureRng {
     #[inline]
     fn fill_bytes(&mut self, dst: &mut [u8]) {
-        // The `chachacha` crate provides a thoroughly tested and
-        // extremely fast fill implementation.
-        self.internal.fill(dst);
+        self.consume(dst.len());
+        // SAFETY: Any bit pattern is a valid `u64`.
+        match unsafe { dst.align_to_mut::<[u64; BUF_LEN_U64]>() } {
+            // When `dst` is suitably aligned and made up of whole blocks (which is
+            // usually the case when it's backed by a `[u64]`), the blocks can be
+            // written directly into it. Blocks are made up of native-endian words,
+            // so this only matches the little-endian output of `fill` on
+            // little-endian targets.
+            #[cfg(target_endian = "little")]
+            ([], blocks, []) => blocks
+                .iter_mut()
+                .for_each(|block| self.internal.fill_block_u64(block)),
+            // The `chachacha` crate provides a thoroughly tested and
+            // extremely fast fill implementation.
+            _ => self.internal.fill(dst),
+        }
+        self.advance(dst.len());
     }
 }
 
 impl Generator for SecureRng {
     #[inline]
-    fn try_new() -> Result<Self, getrandom::Error> {
-        // We want to randomize **all** bits of the matrix, even the counter.
-        // They're all going to be overwritten so no need to initialize them.
-        #[allow(clippy::uninit_assumed_init, invalid_value)]
-        let mut state = unsafe { MaybeUninit::<[u8; SEED_LEN_U8]>::uninit().assume_init() };
-        getrandom::fill(&mut state)?;
-        let mut internal = ChaCha8Djb::from(state);
-        let buf = internal.get_block_u64();
-        let index = 0;
-        Ok(Self {
-            buf,
-            index,
-            internal,
-        })
+    fn try_new() -> Result<Self, Error> {
+        Self::try_new_from(&mut OsEntropy)
+    }
+
+    #[inline]
+    fn try_new_from<S: EntropySource + ?Sized>(source: &mut S) -> Result<Self, Error> {
+        let mut rng = MaybeUninit::uninit();
+        // SAFETY: `rng` is valid for writes and aligned, and is only
+        // assumed to be initialized when seeding succeeded.
+        unsafe {
+            Self::try_init_in_place(rng.as_mut_ptr(), source)?;
+            Ok(rng.assume_init())
+        }
     }
 
     #[cfg_attr(feature = "inline", inline)]
     fn u64(&mut self) -> u64 {
-        // TODO: Use the `unlikely` hint when it comes to stable.
         if self.index >= self.buf.len() {
-            self.index = 0;
-            self.internal.fill_block_u64(&mut self.buf);
+            self.refill();
         }
         // SAFETY: We've just guaranteed that `self.index` will be
         // in bounds in the above if-statement.
@@ -61,3 +629,84 @@ impl Generator for SecureRng {
         ret
     }
 }
+
+/// Runs [`SecureRng::self_test`] the first time it's called, and returns its cached
+/// result afterwards.
+#[cfg(feature = "self-test")]
+fn self_test_once() -> Result<(), Error> {
+    use core::sync::atomic::Ordering;
+    let state = match SELF_TEST.load(Ordering::Acquire) {
+        // Racing threads may both run the test, which is harmless.
+        SELF_TEST_PENDING => {
+            let state = match SecureRng::self_test() {
+                Ok(()) => SELF_TEST_PASSED,
+                Err(_) => SELF_TEST_FAILED,
+            };
+            SELF_TEST.store(state, Ordering::Release);
+            state
+        }
+        state => state,
+    };
+    match state {
+        SELF_TEST_PASSED => Ok(()),
+        _ => Err(Error::new(ErrorKind::SelfTest)),
+    }
+}
+
+/// Limits on how much output a [`SecureRng`] can produce before being reseeded,
+/// whichever is reached first.
+///
+/// Bounds the amount of output exposed by a hypothetical compromise of the
+/// generator's state. Created from [`ReseedPolicy::NEVER`] using the `with_*` methods.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ReseedPolicy {
+    max_bytes: u64,
+    #[cfg(feature = "std")]
+    max_age: Option<Duration>,
+}
+
+impl Default for ReseedPolicy {
+    #[inline]
+    fn default() -> Self {
+        Self::NEVER
+    }
+}
+
+impl ReseedPolicy {
+    /// A policy which never reseeds the generator.
+    pub const NEVER: Self = Self {
+        max_bytes: u64::MAX,
+        #[cfg(feature = "std")]
+        max_age: None,
+    };
+
+    /// Reseeds the generator after it has produced `max_bytes` bytes of output.
+    #[inline]
+    pub const fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Reseeds the generator once `max_age` has elapsed since it was last seeded.
+    ///
+    /// Checking the age requires reading the system clock, which makes refills
+    /// slightly more expensive.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub const fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    #[inline]
+    fn is_due(&self, rng: &SecureRng) -> bool {
+        if rng.cold.bytes_since_reseed >= self.max_bytes {
+            return true;
+        }
+        #[cfg(feature = "std")]
+        if let Some(max_age) = self.max_age {
+            return rng.cold.seeded_at.elapsed() >= max_age;
+        }
+        false
+    }
+}
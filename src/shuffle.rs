@@ -0,0 +1,90 @@
+#[cfg(feature = "alloc")]
+use alloc::{collections::VecDeque, vec::Vec};
+
+/// Collections whose elements are addressed by index and can be swapped in place.
+///
+/// Used by [`crate::Generator::shuffle_indexed`], allowing containers which aren't
+/// backed by a single contiguous slice, such as ring buffers, to be shuffled
+/// without first copying their contents into a `Vec`.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// struct Pair<T>([T; 2], [T; 2]);
+///
+/// impl<T> Swappable for Pair<T> {
+///     fn len(&self) -> usize {
+///         4
+///     }
+///
+///     fn swap(&mut self, i: usize, j: usize) {
+///         let (left, right) = (&mut self.0, &mut self.1);
+///         match (i < 2, j < 2) {
+///             (true, true) => left.swap(i, j),
+///             (false, false) => right.swap(i - 2, j - 2),
+///             (true, false) => core::mem::swap(&mut left[i], &mut right[j - 2]),
+///             (false, true) => core::mem::swap(&mut right[i - 2], &mut left[j]),
+///         }
+///     }
+/// }
+///
+/// let mut rng = new_rng();
+/// let mut pair = Pair([1, 2], [3, 4]);
+/// rng.shuffle_indexed(&mut pair);
+/// let mut all = [pair.0, pair.1].concat();
+/// all.sort();
+/// assert!(all == [1, 2, 3, 4]);
+/// ```
+pub trait Swappable {
+    /// Returns the amount of elements in the collection.
+    fn len(&self) -> usize;
+
+    /// Swaps the elements at indices `i` and `j`, which are both less than `self.len()`.
+    fn swap(&mut self, i: usize, j: usize);
+
+    /// Returns `true` if the collection contains no elements.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Swappable for [T] {
+    #[inline]
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+
+    #[inline]
+    fn swap(&mut self, i: usize, j: usize) {
+        <[T]>::swap(self, i, j);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Swappable for Vec<T> {
+    #[inline]
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    #[inline]
+    fn swap(&mut self, i: usize, j: usize) {
+        <[T]>::swap(self, i, j);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Swappable for VecDeque<T> {
+    #[inline]
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+
+    #[inline]
+    fn swap(&mut self, i: usize, j: usize) {
+        VecDeque::swap(self, i, j);
+    }
+}
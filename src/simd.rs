@@ -0,0 +1,104 @@
+use crate::rng::SecureGenerator;
+use crate::util;
+use core::arch::x86_64::*;
+
+/// Amount of characters produced per call to `fill_bytes`.
+const BATCH_LEN: usize = 256;
+
+/// Fills as much of `dst` as possible with characters randomly selected from `charset`
+/// using SSSE3 table lookups, returning the part of `dst` which is left to be filled.
+///
+/// Only charsets with 16 or 64 characters are supported, anything else is left untouched.
+/// No random bits are wasted: exactly 4 bits are used per character for 16 characters,
+/// and exactly 6 bits per character for 64 characters.
+#[inline]
+pub fn fill_text<'a, G: SecureGenerator>(
+    charset: &[u8],
+    rng: &mut G,
+    dst: &'a mut [u8],
+) -> &'a mut [u8] {
+    match charset.len() {
+        16 => fill_nibbles(charset, rng, dst),
+        64 => fill_sextets(charset, rng, dst),
+        _ => dst,
+    }
+}
+
+/// Every random byte is split into two nibbles, which index into a 16 character table.
+#[inline(never)]
+fn fill_nibbles<'a, G: SecureGenerator>(
+    charset: &[u8],
+    rng: &mut G,
+    dst: &'a mut [u8],
+) -> &'a mut [u8] {
+    let mut random = [0; BATCH_LEN / 2];
+    let mut chunks = dst.chunks_exact_mut(BATCH_LEN);
+    for chunk in &mut chunks {
+        rng.fill_bytes(&mut random);
+        // SAFETY: SSSE3 is enabled at compile time, `charset` is 16 bytes long,
+        // and every load and store is 16 bytes within the bounds of its slice.
+        unsafe {
+            let table = _mm_loadu_si128(charset.as_ptr().cast());
+            let low_mask = _mm_set1_epi8(0x0F);
+            for (src, out) in random.chunks_exact(16).zip(chunk.chunks_exact_mut(32)) {
+                let v = _mm_loadu_si128(src.as_ptr().cast());
+                let low = _mm_and_si128(v, low_mask);
+                let high = _mm_and_si128(_mm_srli_epi16(v, 4), low_mask);
+                let out = out.as_mut_ptr();
+                _mm_storeu_si128(out.cast(), _mm_shuffle_epi8(table, low));
+                _mm_storeu_si128(out.add(16).cast(), _mm_shuffle_epi8(table, high));
+            }
+        }
+    }
+    // The random bytes can be used to reconstruct the characters written to `dst`.
+    util::wipe_slice(&mut random);
+    chunks.into_remainder()
+}
+
+/// Every 12 random bytes are split into 16 sextets, in the same way as base64 encoding,
+/// which index into a 64 character table made up of four 16 character lookups.
+#[inline(never)]
+fn fill_sextets<'a, G: SecureGenerator>(
+    charset: &[u8],
+    rng: &mut G,
+    dst: &'a mut [u8],
+) -> &'a mut [u8] {
+    const RANDOM_LEN: usize = BATCH_LEN / 4 * 3;
+    // The last 16 byte load only uses 12 bytes, so it would read past the end
+    // of the random data without some padding.
+    let mut random = [0; RANDOM_LEN + 4];
+    let mut chunks = dst.chunks_exact_mut(BATCH_LEN);
+    for chunk in &mut chunks {
+        rng.fill_bytes(&mut random[..RANDOM_LEN]);
+        // SAFETY: SSSE3 is enabled at compile time, `charset` is 64 bytes long,
+        // and every load and store is 16 bytes within the bounds of its slice.
+        unsafe {
+            let tables = [0, 16, 32, 48].map(|i| _mm_loadu_si128(charset[i..].as_ptr().cast()));
+            // Technique from "Faster Base64 Encoding and Decoding using AVX2 Instructions"
+            // by Wojciech Muła, Daniel Lemire: https://arxiv.org/abs/1704.00605.
+            let shuffle = _mm_setr_epi8(1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10);
+            let low_mask = _mm_set1_epi8(0x0F);
+            for (i, out) in chunk.chunks_exact_mut(16).enumerate() {
+                let v = _mm_loadu_si128(random[i * 12..].as_ptr().cast());
+                let v = _mm_shuffle_epi8(v, shuffle);
+                let t0 = _mm_and_si128(v, _mm_set1_epi32(0x0FC0FC00));
+                let t1 = _mm_mulhi_epu16(t0, _mm_set1_epi32(0x04000040));
+                let t2 = _mm_and_si128(v, _mm_set1_epi32(0x003F03F0));
+                let t3 = _mm_mullo_epi16(t2, _mm_set1_epi32(0x01000010));
+                let indices = _mm_or_si128(t1, t3);
+                let low = _mm_and_si128(indices, low_mask);
+                let high = _mm_and_si128(_mm_srli_epi16(indices, 4), low_mask);
+                let mut chars = _mm_setzero_si128();
+                for (k, table) in tables.into_iter().enumerate() {
+                    let selected = _mm_cmpeq_epi8(high, _mm_set1_epi8(k as i8));
+                    let looked_up = _mm_shuffle_epi8(table, low);
+                    chars = _mm_or_si128(chars, _mm_and_si128(looked_up, selected));
+                }
+                _mm_storeu_si128(out.as_mut_ptr().cast(), chars);
+            }
+        }
+    }
+    // The random bytes can be used to reconstruct the characters written to `dst`.
+    util::wipe_slice(&mut random);
+    chunks.into_remainder()
+}
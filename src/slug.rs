@@ -0,0 +1,26 @@
+//! Word lists used by [`crate::Generator::slug`].
+//!
+//! Every word is short, lowercase ascii, and unlikely to cause offense when
+//! combined with any other word from the lists.
+
+#[rustfmt::skip]
+pub(crate) const ADJECTIVES: [&str; 64] = [
+    "able", "agile", "amber", "ancient", "bold", "brave", "breezy", "bright", "brisk", "calm",
+    "clever", "cosmic", "crisp", "curious", "daring", "dapper", "eager", "early", "fancy", "fast",
+    "fluffy", "frosty", "gentle", "giant", "golden", "happy", "hidden", "honest", "humble", "jolly",
+    "keen", "kind", "lively", "lucky", "mellow", "merry", "mighty", "misty", "modest", "noble",
+    "patient", "plucky", "polite", "proud", "quick", "quiet", "rapid", "rustic", "shiny", "silent",
+    "silver", "sleepy", "snowy", "solid", "spry", "steady", "sunny", "swift", "tidy", "tiny",
+    "vivid", "warm", "wise", "witty",
+];
+
+#[rustfmt::skip]
+pub(crate) const NOUNS: [&str; 64] = [
+    "acorn", "badger", "beacon", "beaver", "bison", "brook", "canyon", "cedar", "comet", "condor",
+    "coral", "cricket", "delta", "dolphin", "falcon", "fern", "finch", "fjord", "forest", "fox",
+    "gazelle", "glacier", "harbor", "hawk", "heron", "island", "jaguar", "kestrel", "koala", "lagoon",
+    "lark", "lemur", "lynx", "maple", "meadow", "meteor", "moose", "nebula", "orchid", "otter",
+    "owl", "panda", "pebble", "pelican", "penguin", "pine", "planet", "puffin", "quartz", "raven",
+    "reef", "river", "robin", "salmon", "sparrow", "spruce", "summit", "tiger", "toucan", "tundra",
+    "valley", "walrus", "willow", "zebra",
+];
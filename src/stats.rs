@@ -0,0 +1,393 @@
+/*!
+Goodness-of-fit tests for checking that samples follow an expected distribution,
+and a [`Histogram`] for quickly inspecting them.
+
+These are intended for tests of code built on top of this crate, so that distributional
+correctness can be asserted instead of eyeballed.
+
+# Examples
+
+```
+use ya_rand::*;
+use ya_rand::stats::*;
+
+let mut rng = new_rng();
+let samples = (0..10_000).map(|_| rng.f64());
+let result = ks_test(samples, |x| x.clamp(0.0, 1.0));
+assert!(result.p_value > 1e-6);
+```
+*/
+
+use alloc::{string::String, vec, vec::Vec};
+use core::fmt::{self, Write};
+
+/// Result of a goodness-of-fit test.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GoodnessOfFit {
+    /// Value of the test statistic.
+    pub statistic: f64,
+    /// Probability of observing a statistic at least this extreme,
+    /// assuming the samples follow the expected distribution.
+    pub p_value: f64,
+}
+
+impl GoodnessOfFit {
+    /// Returns `true` if the null hypothesis (that the samples follow the expected
+    /// distribution) can't be rejected at the significance level `alpha`.
+    #[inline]
+    pub fn passes(&self, alpha: f64) -> bool {
+        self.p_value >= alpha
+    }
+}
+
+/// Performs Pearson's chi-squared test, comparing the `observed` count of every
+/// category against its `expected` count.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+/// use ya_rand::stats::*;
+///
+/// let mut rng = new_rng();
+/// let mut observed = [0; 6];
+/// for _ in 0..6000 {
+///     observed[rng.bound(6) as usize] += 1;
+/// }
+/// let result = chi_square(&observed, &[1000.0; 6]);
+/// assert!(result.passes(1e-6));
+/// ```
+///
+/// # Panics
+///
+/// If `observed` and `expected` have different lengths, there are less than two
+/// categories, or any expected count isn't positive.
+pub fn chi_square(observed: &[u64], expected: &[f64]) -> GoodnessOfFit {
+    assert!(observed.len() == expected.len(), "lengths must be equal");
+    assert!(observed.len() >= 2, "there must be at least two categories");
+    let statistic = observed
+        .iter()
+        .zip(expected)
+        .map(|(&o, &e)| {
+            assert!(e > 0.0, "expected counts must be positive");
+            let delta = o as f64 - e;
+            delta * delta / e
+        })
+        .sum::<f64>();
+    let degrees_of_freedom = (observed.len() - 1) as f64;
+    let p_value = gamma_q(degrees_of_freedom / 2.0, statistic / 2.0);
+    GoodnessOfFit { statistic, p_value }
+}
+
+/// Performs the one-sample Kolmogorov–Smirnov test, comparing the empirical
+/// distribution of `samples` against the cumulative distribution function `cdf`.
+///
+/// The p-value is calculated using the asymptotic Kolmogorov distribution,
+/// which is accurate once there are more than a few dozen samples.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+/// use ya_rand::stats::*;
+///
+/// let mut rng = new_rng();
+/// let samples = (0..10_000).map(|_| rng.f64_exponential());
+/// let result = ks_test(samples, |x| 1.0 - (-x).exp());
+/// assert!(result.passes(1e-6));
+/// ```
+///
+/// # Panics
+///
+/// If `samples` is empty or contains NaN.
+pub fn ks_test<I, F>(samples: I, cdf: F) -> GoodnessOfFit
+where
+    I: IntoIterator<Item = f64>,
+    F: Fn(f64) -> f64,
+{
+    let mut samples = samples.into_iter().collect::<Vec<_>>();
+    assert!(!samples.is_empty(), "there must be at least one sample");
+    assert!(samples.iter().all(|v| !v.is_nan()), "samples can't be NaN");
+    samples.sort_unstable_by(f64::total_cmp);
+    let n = samples.len() as f64;
+    let statistic = samples
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| {
+            let y = cdf(x);
+            let below = y - i as f64 / n;
+            let above = (i + 1) as f64 / n - y;
+            below.max(above)
+        })
+        .fold(0.0, f64::max);
+    let sqrt_n = n.sqrt();
+    // Correction from Stephens (1970), which improves accuracy for small sample sizes.
+    let lambda = (sqrt_n + 0.12 + 0.11 / sqrt_n) * statistic;
+    let p_value = kolmogorov_q(lambda);
+    GoodnessOfFit { statistic, p_value }
+}
+
+/// Counts of samples over a fixed number of equally sized bins, along with
+/// the mean and variance of all samples.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+/// use ya_rand::stats::Histogram;
+///
+/// let mut rng = new_rng();
+/// let mut histogram = Histogram::new(0.0, 1.0, 10);
+/// histogram.extend((0..10_000).map(|_| rng.f64()));
+/// assert!(histogram.len() == 10_000);
+/// assert!(histogram.counts().iter().all(|&c| c > 800));
+/// assert!((histogram.mean() - 0.5).abs() < 0.05);
+/// // Prints a bar for every bin, followed by a summary.
+/// println!("{histogram}");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    min: f64,
+    max: f64,
+    bins: Vec<u64>,
+    outliers: u64,
+    len: u64,
+    mean: f64,
+    m2: f64,
+}
+
+impl Histogram {
+    /// Width of the bars created by the `Display` implementation.
+    const BAR_WIDTH: usize = 50;
+
+    /// Creates an empty histogram with `bins` bins evenly covering [`min`, `max`).
+    ///
+    /// # Panics
+    ///
+    /// If `bins` is zero, or `min` isn't less than `max`.
+    pub fn new(min: f64, max: f64, bins: usize) -> Self {
+        assert!(bins != 0, "there must be at least one bin");
+        assert!(min < max, "`min` must be less than `max`");
+        Self {
+            min,
+            max,
+            bins: vec![0; bins],
+            outliers: 0,
+            len: 0,
+            mean: 0.0,
+            m2: 0.0,
+        }
+    }
+
+    /// Adds `value` to the histogram.
+    ///
+    /// Values outside of the range of the histogram (including NaN) aren't placed into any bin,
+    /// and are instead counted as outliers. They still contribute to the mean and variance.
+    pub fn add(&mut self, value: f64) {
+        if self.min <= value && value < self.max {
+            let scaled = (value - self.min) / (self.max - self.min) * self.bins.len() as f64;
+            // Rounding can push values right below `max` into a non-existent bin.
+            let index = (scaled as usize).min(self.bins.len() - 1);
+            self.bins[index] += 1;
+        } else {
+            self.outliers += 1;
+        }
+        // Welford's online algorithm.
+        self.len += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.len as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    /// Returns the counts of every bin, in ascending order.
+    #[inline]
+    pub fn counts(&self) -> &[u64] {
+        &self.bins
+    }
+
+    /// Returns the amount of values which didn't fit into any bin.
+    #[inline]
+    pub fn outliers(&self) -> u64 {
+        self.outliers
+    }
+
+    /// Returns the amount of values which have been added.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if no values have been added.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the mean of all values, or NaN if the histogram is empty.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        match self.len {
+            0 => f64::NAN,
+            _ => self.mean,
+        }
+    }
+
+    /// Returns the sample variance of all values, or NaN if less than two values have been added.
+    #[inline]
+    pub fn variance(&self) -> f64 {
+        match self.len {
+            0 | 1 => f64::NAN,
+            len => self.m2 / (len - 1) as f64,
+        }
+    }
+
+    /// Returns a textual rendering of the histogram, with the longest bar being `width` characters.
+    pub fn render(&self, width: usize) -> String {
+        let mut s = String::new();
+        self.render_into(&mut s, width)
+            .expect("writing to a `String` should never fail");
+        s
+    }
+
+    fn render_into<W: Write>(&self, dst: &mut W, width: usize) -> fmt::Result {
+        let largest = self.bins.iter().copied().max().unwrap_or(0).max(1);
+        let bin_width = (self.max - self.min) / self.bins.len() as f64;
+        for (i, &count) in self.bins.iter().enumerate() {
+            let low = self.min + bin_width * i as f64;
+            let high = low + bin_width;
+            let bar = (count as u128 * width as u128 / largest as u128) as usize;
+            write!(dst, "[{low:>12.4}, {high:>12.4}) {count:>10} |")?;
+            for _ in 0..bar {
+                dst.write_char('#')?;
+            }
+            dst.write_char('\n')?;
+        }
+        write!(
+            dst,
+            "count = {}, outliers = {}, mean = {:.6}, variance = {:.6}",
+            self.len,
+            self.outliers,
+            self.mean(),
+            self.variance()
+        )
+    }
+}
+
+impl fmt::Display for Histogram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.render_into(f, Self::BAR_WIDTH)
+    }
+}
+
+impl Extend<f64> for Histogram {
+    fn extend<T: IntoIterator<Item = f64>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|v| self.add(v));
+    }
+}
+
+impl Extend<u64> for Histogram {
+    fn extend<T: IntoIterator<Item = u64>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|v| self.add(v as f64));
+    }
+}
+
+/// Survival function of the Kolmogorov distribution.
+fn kolmogorov_q(lambda: f64) -> f64 {
+    // The series converges very slowly for small values, where the result is 1 anyway.
+    if lambda < 0.2 {
+        return 1.0;
+    }
+    let mut sum = 0.0;
+    let mut sign = 1.0;
+    for j in 1..=100 {
+        let j = j as f64;
+        let term = (-2.0 * j * j * lambda * lambda).exp();
+        sum += sign * term;
+        if term < 1e-16 * sum.abs() {
+            break;
+        }
+        sign = -sign;
+    }
+    (2.0 * sum).clamp(0.0, 1.0)
+}
+
+/// Regularized upper incomplete gamma function, Q(a, x).
+fn gamma_q(a: f64, x: f64) -> f64 {
+    const EPSILON: f64 = 1e-15;
+    const MAX_ITERATIONS: usize = 1000;
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let ln_prefix = a * x.ln() - x - ln_gamma(a);
+    if x < a + 1.0 {
+        // Series representation of P(a, x).
+        let mut denom = a;
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        for _ in 0..MAX_ITERATIONS {
+            denom += 1.0;
+            term *= x / denom;
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+        (1.0 - sum * ln_prefix.exp()).clamp(0.0, 1.0)
+    } else {
+        // Continued fraction representation of Q(a, x), using the modified Lentz method.
+        let tiny = f64::MIN_POSITIVE / EPSILON;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..MAX_ITERATIONS {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < EPSILON {
+                break;
+            }
+        }
+        (h * ln_prefix.exp()).clamp(0.0, 1.0)
+    }
+}
+
+/// Natural logarithm of the gamma function for positive `x`, using the Lanczos approximation.
+fn ln_gamma(x: f64) -> f64 {
+    const G: f64 = 7.0;
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // Reflection formula.
+        let pi = core::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + G + 0.5;
+    let sum = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |acc, (i, &c)| {
+            acc + c / (x + (i + 1) as f64)
+        });
+    0.5 * (2.0 * core::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
@@ -0,0 +1,509 @@
+/*!
+Utilities for testing code which consumes random data.
+
+[`MockRng`] and [`StepRng`] are deterministic test doubles which implement [`Generator`],
+so code accepting `impl Generator` can be tested without writing a custom fake.
+[`RecordingRng`] wraps any generator and records every value it produces, so the exact
+values which triggered a failing randomized test can be dumped and inspected, and
+[`ReplayRng`] feeds those recorded values back to reproduce the failure exactly.
+[`CountingRng`] keeps track of how much output a piece of code consumes.
+
+When the **proptest** feature is enabled, this module also provides the glue needed to drive
+[`proptest`] using the generators from this crate.
+*/
+
+use crate::entropy::{EntropySource, OsEntropy};
+use crate::error::{Error, ErrorKind};
+use crate::rng::{Generator, SeedableGenerator};
+
+#[cfg(feature = "secure")]
+use crate::rng::SecureGenerator;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "proptest")]
+use {
+    core::fmt,
+    proptest::{
+        prelude::RngCore,
+        strategy::Strategy,
+        test_runner::{Config, RngAlgorithm, TestRng, TestRunner},
+    },
+};
+
+/// A generator which returns a scripted sequence of `u64` values.
+///
+/// Keep in mind that most methods of [`Generator`] transform the raw values in some way,
+/// and some of them (like [`Generator::bound`]) may consume more than one value.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+/// use ya_rand::testing::MockRng;
+///
+/// let mut rng = MockRng::new_with_values([1, 2, 3]);
+/// assert!(rng.u64() == 1);
+/// assert!(rng.u64() == 2);
+/// assert!(rng.remaining() == 1);
+/// assert!(rng.u64() == 3);
+/// ```
+///
+/// # Panics
+///
+/// Calling [`MockRng::u64`] after all values have been returned.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MockRng<const N: usize> {
+    values: [u64; N],
+    index: usize,
+}
+
+impl<const N: usize> MockRng<N> {
+    /// Creates a generator which returns `values` in order.
+    #[inline]
+    pub const fn new_with_values(values: [u64; N]) -> Self {
+        Self { values, index: 0 }
+    }
+
+    /// Returns how many values can still be returned by the generator.
+    #[inline]
+    pub const fn remaining(&self) -> usize {
+        N - self.index
+    }
+}
+
+impl<const N: usize> Generator for MockRng<N> {
+    #[inline]
+    fn try_new() -> Result<Self, Error> {
+        Self::try_new_from(&mut OsEntropy)
+    }
+
+    /// Creates a generator whose script is filled using `source`.
+    #[inline]
+    fn try_new_from<S: EntropySource + ?Sized>(source: &mut S) -> Result<Self, Error> {
+        let mut values = [0; N];
+        for v in &mut values {
+            let mut bytes = [0; size_of::<u64>()];
+            source.fill(&mut bytes)?;
+            *v = u64::from_le_bytes(bytes);
+        }
+        Ok(Self::new_with_values(values))
+    }
+
+    #[inline]
+    fn u64(&mut self) -> u64 {
+        assert!(self.index < N, "`MockRng` has run out of scripted values");
+        let ret = self.values[self.index];
+        self.index += 1;
+        ret
+    }
+}
+
+/// A generator which returns an arithmetic progression of `u64` values,
+/// wrapping around on overflow.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+/// use ya_rand::testing::StepRng;
+///
+/// let mut rng = StepRng::new_with_step(10, 5);
+/// assert!(rng.u64() == 10);
+/// assert!(rng.u64() == 15);
+/// assert!(rng.u64() == 20);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StepRng {
+    value: u64,
+    step: u64,
+}
+
+impl StepRng {
+    /// Creates a generator which starts at `start`, and increases by `step` every call.
+    #[inline]
+    pub const fn new_with_step(start: u64, step: u64) -> Self {
+        Self { value: start, step }
+    }
+}
+
+impl Generator for StepRng {
+    #[inline]
+    fn try_new() -> Result<Self, Error> {
+        Self::try_new_from(&mut OsEntropy)
+    }
+
+    /// Creates a generator with a start and step chosen using `source`.
+    #[inline]
+    fn try_new_from<S: EntropySource + ?Sized>(source: &mut S) -> Result<Self, Error> {
+        let mut bytes = [0; 2 * size_of::<u64>()];
+        source.fill(&mut bytes)?;
+        let (start, step) = bytes.split_at(size_of::<u64>());
+        Ok(Self::new_with_step(
+            u64::from_le_bytes(start.try_into().unwrap()),
+            u64::from_le_bytes(step.try_into().unwrap()),
+        ))
+    }
+
+    #[inline]
+    fn u64(&mut self) -> u64 {
+        let ret = self.value;
+        self.value = self.value.wrapping_add(self.step);
+        ret
+    }
+}
+
+/// Trait for types which can record the values produced by a [`RecordingRng`].
+///
+/// Implemented for `Vec<u64>` when the **alloc** feature is enabled, and for any
+/// `FnMut(u64)` closure, which can be used to forward values to a writer or logger.
+pub trait Tape {
+    /// Records `value`.
+    fn record(&mut self, value: u64);
+
+    /// Returns a new empty tape, if this type of tape can be created from nothing.
+    ///
+    /// Used by [`RecordingRng::try_new`] and [`RecordingRng::try_new_from`], which fail with
+    /// [`ErrorKind::Unsupported`] when this returns `None` (causing
+    /// [`RecordingRng::new`] to panic).
+    #[inline]
+    fn empty() -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Tape for Vec<u64> {
+    #[inline]
+    fn record(&mut self, value: u64) {
+        self.push(value);
+    }
+
+    #[inline]
+    fn empty() -> Option<Self> {
+        Some(Vec::new())
+    }
+}
+
+impl<F: FnMut(u64)> Tape for F {
+    #[inline]
+    fn record(&mut self, value: u64) {
+        self(value);
+    }
+}
+
+/// A wrapper which transparently records every value produced by the wrapped generator.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+/// use ya_rand::testing::RecordingRng;
+///
+/// let mut rng = RecordingRng::new_with_tape(new_rng(), Vec::new());
+/// let val = rng.u64();
+/// let other = rng.u64();
+/// assert!(rng.tape() == &[val, other]);
+///
+/// // Closures can be used to forward values elsewhere, like a writer.
+/// use std::io::Write;
+/// let mut log = Vec::new();
+/// let mut rng = RecordingRng::new_with_tape(new_rng(), |v| writeln!(log, "{v}").unwrap());
+/// let val = rng.u64();
+/// drop(rng);
+/// assert!(log == format!("{val}\n").as_bytes());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordingRng<G, T> {
+    rng: G,
+    tape: T,
+}
+
+impl<G: Generator, T: Tape> RecordingRng<G, T> {
+    /// Wraps `rng`, recording all of its output to `tape`.
+    #[inline]
+    pub const fn new_with_tape(rng: G, tape: T) -> Self {
+        Self { rng, tape }
+    }
+
+    /// Returns a reference to the tape.
+    #[inline]
+    pub const fn tape(&self) -> &T {
+        &self.tape
+    }
+
+    /// Returns the wrapped generator and the tape.
+    #[inline]
+    pub fn into_parts(self) -> (G, T) {
+        (self.rng, self.tape)
+    }
+}
+
+impl<G: Generator, T: Tape> Generator for RecordingRng<G, T> {
+    /// Creates the wrapped generator using OS entropy, with an empty tape.
+    #[inline]
+    fn try_new() -> Result<Self, Error> {
+        let tape = T::empty().ok_or(Error::new(ErrorKind::Unsupported))?;
+        let rng = G::try_new()?;
+        Ok(Self::new_with_tape(rng, tape))
+    }
+
+    /// Creates the wrapped generator using `source`, with an empty tape.
+    #[inline]
+    fn try_new_from<S: EntropySource + ?Sized>(source: &mut S) -> Result<Self, Error> {
+        let tape = T::empty().ok_or(Error::new(ErrorKind::Unsupported))?;
+        let rng = G::try_new_from(source)?;
+        Ok(Self::new_with_tape(rng, tape))
+    }
+
+    #[inline]
+    fn u64(&mut self) -> u64 {
+        let ret = self.rng.u64();
+        self.tape.record(ret);
+        ret
+    }
+}
+
+/// A generator which replays a tape of previously recorded values,
+/// usually obtained from a [`RecordingRng`].
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+/// use ya_rand::testing::{RecordingRng, ReplayRng};
+///
+/// let mut rng = RecordingRng::new_with_tape(new_rng(), Vec::new());
+/// let mut original = [1, 2, 3, 4, 5, 6, 7, 8];
+/// rng.shuffle(&mut original);
+/// let (_, tape) = rng.into_parts();
+///
+/// let mut rng = ReplayRng::new_with_tape(&tape);
+/// let mut replayed = [1, 2, 3, 4, 5, 6, 7, 8];
+/// rng.shuffle(&mut replayed);
+/// assert!(original == replayed);
+/// assert!(rng.try_u64().is_none());
+/// ```
+///
+/// # Panics
+///
+/// Calling [`ReplayRng::u64`] after the tape has been exhausted.
+/// Use [`ReplayRng::try_u64`] if this should be handled gracefully.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReplayRng<'a> {
+    tape: &'a [u64],
+    index: usize,
+}
+
+impl<'a> ReplayRng<'a> {
+    /// Creates a generator which returns the values of `tape` in order.
+    #[inline]
+    pub const fn new_with_tape(tape: &'a [u64]) -> Self {
+        Self { tape, index: 0 }
+    }
+
+    /// Returns the next value of the tape, or `None` if it has been exhausted.
+    #[inline]
+    pub fn try_u64(&mut self) -> Option<u64> {
+        let ret = self.tape.get(self.index).copied();
+        self.index += ret.is_some() as usize;
+        ret
+    }
+
+    /// Returns how many values of the tape haven't been replayed yet.
+    #[inline]
+    pub const fn remaining(&self) -> usize {
+        self.tape.len() - self.index
+    }
+}
+
+impl Generator for ReplayRng<'_> {
+    /// Always fails with [`ErrorKind::Unsupported`], since
+    /// a tape can't be created from entropy. [`Generator::new`] panics for the same reason.
+    #[inline]
+    fn try_new() -> Result<Self, Error> {
+        Err(Error::new(ErrorKind::Unsupported))
+    }
+
+    #[inline]
+    fn u64(&mut self) -> u64 {
+        self.try_u64()
+            .expect("`ReplayRng` has run out of recorded values")
+    }
+}
+
+/// A wrapper which counts how much output is consumed from the wrapped generator.
+///
+/// Every call to [`Generator::u64`] is counted as a draw, and bytes requested through
+/// [`SecureGenerator::fill_bytes`] are counted separately. The only overhead is the
+/// incrementing of the counters, and only for code using the wrapper.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+/// use ya_rand::testing::CountingRng;
+///
+/// let mut rng = CountingRng::new_with_rng(new_rng());
+/// let mut data = [0; 100];
+/// rng.shuffle(&mut data);
+/// // Every swap needs at least one draw.
+/// assert!(rng.draws() >= 99);
+///
+/// rng.reset();
+/// assert!(rng.draws() == 0);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CountingRng<G> {
+    rng: G,
+    draws: u64,
+    bytes: u64,
+}
+
+impl<G: Generator> CountingRng<G> {
+    /// Wraps `rng`, with both counters starting at zero.
+    #[inline]
+    pub const fn new_with_rng(rng: G) -> Self {
+        Self {
+            rng,
+            draws: 0,
+            bytes: 0,
+        }
+    }
+
+    /// Returns how many `u64` values have been drawn from the wrapped generator.
+    #[inline]
+    pub const fn draws(&self) -> u64 {
+        self.draws
+    }
+
+    /// Returns how many bytes have been requested through [`SecureGenerator::fill_bytes`].
+    #[inline]
+    pub const fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Sets both counters back to zero.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.draws = 0;
+        self.bytes = 0;
+    }
+
+    /// Returns the wrapped generator.
+    #[inline]
+    pub fn into_inner(self) -> G {
+        self.rng
+    }
+}
+
+impl<G: SeedableGenerator> SeedableGenerator for CountingRng<G> {
+    #[inline]
+    fn new_with_seed(seed: u64) -> Self {
+        Self::new_with_rng(G::new_with_seed(seed))
+    }
+}
+
+#[cfg(feature = "secure")]
+impl<G: SecureGenerator> SecureGenerator for CountingRng<G> {
+    #[inline]
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.bytes += dst.len() as u64;
+        self.rng.fill_bytes(dst);
+    }
+}
+
+impl<G: Generator> Generator for CountingRng<G> {
+    #[inline]
+    fn try_new() -> Result<Self, Error> {
+        G::try_new().map(Self::new_with_rng)
+    }
+
+    #[inline]
+    fn try_new_from<S: EntropySource + ?Sized>(source: &mut S) -> Result<Self, Error> {
+        G::try_new_from(source).map(Self::new_with_rng)
+    }
+
+    #[inline]
+    fn u64(&mut self) -> u64 {
+        self.draws += 1;
+        self.rng.u64()
+    }
+}
+
+/// Returns a proptest `Strategy` which produces generators of type `G`.
+///
+/// Generators are created using [`SeedableGenerator::new_with_seed`], with the seed
+/// being chosen by proptest. This means failing cases are persisted and replayed
+/// exactly like any other input, and shrink towards `G::new_with_seed(0)`.
+///
+/// # Examples
+///
+/// ```
+/// use proptest::prelude::*;
+/// use ya_rand::*;
+///
+/// proptest! {
+///     fn bound_is_respected(mut rng in testing::seeded::<ShiroRng>(), max in 1..u64::MAX) {
+///         prop_assert!(rng.bound(max) < max);
+///     }
+/// }
+/// # bound_is_respected();
+/// ```
+#[cfg(feature = "proptest")]
+pub fn seeded<G>() -> impl Strategy<Value = G>
+where
+    G: SeedableGenerator + fmt::Debug,
+{
+    proptest::num::u64::ANY.prop_map(G::new_with_seed)
+}
+
+/// Creates a proptest `TestRunner` using `config`, with the random number
+/// generator of the runner being seeded from the output of `rng`.
+///
+/// # Examples
+///
+/// ```
+/// use proptest::test_runner::Config;
+/// use ya_rand::*;
+///
+/// let mut rng = ShiroRng::new_with_seed(1738);
+/// let mut runner = testing::test_runner(&mut rng, Config::default());
+/// let result = runner.run(&(0..10_u64), |v| {
+///     assert!(v < 10);
+///     Ok(())
+/// });
+/// assert!(result.is_ok());
+/// ```
+#[cfg(feature = "proptest")]
+pub fn test_runner<G: Generator>(rng: &mut G, config: Config) -> TestRunner {
+    let seed = rng.array::<32>();
+    let test_rng = TestRng::from_seed(RngAlgorithm::ChaCha, &seed);
+    TestRunner::new_with_rng(config, test_rng)
+}
+
+/// Allows the generator of a proptest `TestRunner` to be used directly
+/// with all of the methods provided by this crate.
+#[cfg(feature = "proptest")]
+impl Generator for TestRng {
+    #[inline]
+    fn try_new() -> Result<Self, Error> {
+        Self::try_new_from(&mut OsEntropy)
+    }
+
+    #[inline]
+    fn try_new_from<S: EntropySource + ?Sized>(source: &mut S) -> Result<Self, Error> {
+        let mut seed = [0; 32];
+        source.fill(&mut seed)?;
+        Ok(Self::from_seed(RngAlgorithm::ChaCha, &seed))
+    }
+
+    #[inline]
+    fn u64(&mut self) -> u64 {
+        self.next_u64()
+    }
+}
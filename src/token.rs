@@ -0,0 +1,146 @@
+use crate::util;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A secret `String` value, such as a session identifier or API key.
+///
+/// Usually created using [`crate::SecureGeneratorExt::token`], but any existing `String`
+/// can be converted into a `SecureToken` using the `From` implementation.
+///
+/// Comparisons between tokens take time which is only dependent on their length,
+/// never on their contents. The entire buffer of the token, including any spare
+/// capacity, is overwritten with zeros when it's dropped, and both the `Debug` and `Display` implementations
+/// will never output the contents of the token.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+/// use ya_rand::encoding::{Base64Url, Encoder};
+///
+/// let mut rng = new_rng_secure();
+/// let token = rng.token::<Base64Url>(Base64Url::MIN_LEN);
+/// assert!(token.as_str().len() == Base64Url::MIN_LEN);
+/// assert!(format!("{:?}", token) == "SecureToken(<redacted>)");
+/// assert!(format!("{}", token) == "<redacted>");
+///
+/// let copy = SecureToken::from(token.as_str().to_owned());
+/// assert!(token == copy);
+/// ```
+pub struct SecureToken {
+    inner: String,
+}
+
+impl SecureToken {
+    /// Returns the contents of the token as a string slice.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.inner
+    }
+
+    /// Returns the contents of the token as a byte slice.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.inner.as_bytes()
+    }
+}
+
+impl From<String> for SecureToken {
+    #[inline]
+    fn from(inner: String) -> Self {
+        Self { inner }
+    }
+}
+
+impl PartialEq for SecureToken {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        util::ct_eq(self.as_bytes(), other.as_bytes())
+    }
+}
+
+impl Eq for SecureToken {}
+
+impl Drop for SecureToken {
+    fn drop(&mut self) {
+        // SAFETY: The `String` is dropped right after this, so it
+        // doesn't matter that its contents are no longer valid UTF-8.
+        let bytes = unsafe { self.inner.as_mut_vec() };
+        util::wipe(bytes);
+    }
+}
+
+impl fmt::Debug for SecureToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("SecureToken(<redacted>)")
+    }
+}
+
+impl fmt::Display for SecureToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+/// A secret byte buffer, such as a symmetric key or nonce.
+///
+/// Usually created using [`crate::SecureGeneratorExt::token_bytes`], but any existing
+/// `Vec<u8>` can be converted into a `SecureBytes` using the `From` implementation.
+///
+/// Provides the same guarantees as [`SecureToken`]: comparisons take time which is only
+/// dependent on the length of the buffers, the entire buffer is overwritten with zeros
+/// when it's dropped, and the `Debug` implementation never outputs the contents.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// let mut rng = new_rng_secure();
+/// let key = rng.token_bytes(32);
+/// assert!(key.as_bytes().len() == 32);
+/// assert!(format!("{:?}", key) == "SecureBytes(<redacted>)");
+///
+/// let copy = SecureBytes::from(key.as_bytes().to_vec());
+/// assert!(key == copy);
+/// ```
+pub struct SecureBytes {
+    inner: Vec<u8>,
+}
+
+impl SecureBytes {
+    /// Returns the contents of the buffer as a byte slice.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.inner
+    }
+}
+
+impl From<Vec<u8>> for SecureBytes {
+    #[inline]
+    fn from(inner: Vec<u8>) -> Self {
+        Self { inner }
+    }
+}
+
+impl PartialEq for SecureBytes {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        util::ct_eq(self.as_bytes(), other.as_bytes())
+    }
+}
+
+impl Eq for SecureBytes {}
+
+impl Drop for SecureBytes {
+    fn drop(&mut self) {
+        util::wipe(&mut self.inner);
+    }
+}
+
+impl fmt::Debug for SecureBytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("SecureBytes(<redacted>)")
+    }
+}
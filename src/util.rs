@@ -1,3 +1,6 @@
+use crate::entropy::EntropySource;
+use crate::error::Error;
+
 /// Converts `slice` into a mutable slice of bytes, providing access
 /// to the raw data of the underlying buffer.
 ///
@@ -16,30 +19,113 @@ pub unsafe fn as_raw_bytes_mut<T>(slice: &mut [T]) -> &mut [u8] {
 /// Returns an array filled with pseudorandom data from the output of
 /// a SplitMix64 PRNG, which is seeded using `seed`.
 #[inline(never)]
-pub fn state_from_seed<const SIZE: usize>(seed: u64) -> [u64; SIZE] {
+pub const fn state_from_seed<const SIZE: usize>(seed: u64) -> [u64; SIZE] {
     let mut state = [0; SIZE];
     let mut x = seed;
     // SplitMix64 implementation from https://prng.di.unimi.it/splitmix64.c.
-    for v in &mut state {
-        x = x.wrapping_add(0x9E3779B97F4A7C15);
-        let mut z = x;
-        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
-        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
-        *v = z ^ (z >> 31);
+    // Iterators can't be used in a const context.
+    let mut i = 0;
+    while i < SIZE {
+        x = x.wrapping_add(GOLDEN_GAMMA);
+        state[i] = mix64(x);
+        i += 1;
     }
     state
 }
 
-/// Attempts to return an array filled with random data from operating system entropy.
+/// Increment of SplitMix64, derived from the golden ratio.
+const GOLDEN_GAMMA: u64 = 0x9E3779B97F4A7C15;
+
+/// The output function of SplitMix64, a bijection which thoroughly mixes the bits of `z`.
+#[inline]
+pub const fn mix64(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+/// Deterministic entropy source derived from a seed and a stream id, used by
+/// [`crate::SeedableGenerator::new_stream`].
+///
+/// Every word of output is a bijective function of the seed for a fixed stream id,
+/// and of the stream id for a fixed seed, so distinct pairs produce unrelated states.
+pub struct StreamSource {
+    seed: u64,
+    stream: u64,
+    counter: u64,
+}
+
+impl StreamSource {
+    #[inline]
+    pub const fn new(seed: u64, stream: u64) -> Self {
+        Self {
+            seed: mix64(seed),
+            stream: mix64(stream ^ GOLDEN_GAMMA),
+            counter: 0,
+        }
+    }
+
+    #[inline]
+    pub fn u64(&mut self) -> u64 {
+        self.counter = self.counter.wrapping_add(GOLDEN_GAMMA);
+        let x = mix64(self.stream ^ mix64(self.counter));
+        mix64(self.seed.wrapping_add(x))
+    }
+}
+
+impl EntropySource for StreamSource {
+    #[inline]
+    fn fill(&mut self, dst: &mut [u8]) -> Result<(), Error> {
+        for chunk in dst.chunks_mut(size_of::<u64>()) {
+            chunk.copy_from_slice(&self.u64().to_le_bytes()[..chunk.len()]);
+        }
+        Ok(())
+    }
+}
+
+/// Attempts to return an array filled with random data from `source`.
 #[inline]
-pub fn state_from_entropy<const SIZE: usize>() -> Result<[u64; SIZE], getrandom::Error> {
+pub fn state_from_entropy<const SIZE: usize, S: EntropySource + ?Sized>(
+    source: &mut S,
+) -> Result<[u64; SIZE], Error> {
     let mut state = [0; SIZE];
     // SAFETY: I'm over here strokin' my dick I got lotion on my dick right now.
     let state_as_bytes = unsafe { as_raw_bytes_mut(&mut state) };
-    getrandom::fill(state_as_bytes)?;
+    source.fill(state_as_bytes)?;
+    // An all-zero state is a fixed point of every generator seeded through this
+    // function, which a faulty or user-supplied source can easily produce.
+    if state.iter().all(|&v| v == 0) {
+        state = state_from_seed(0);
+    }
     Ok(state)
 }
 
+/// Converts the upper bits of `x` into a uniformly distributed
+/// `f64` in the interval [0.0, 1.0).
+#[inline]
+pub fn u64_to_f64(x: u64) -> f64 {
+    const MANT: u32 = f64::MANTISSA_DIGITS;
+    (x >> (u64::BITS - MANT)) as f64 / (1_u64 << MANT) as f64
+}
+
+/// Converts the upper 52 bits of `x` into a uniformly distributed `f64` in the
+/// interval [0.0, 1.0), by using them as the mantissa of a value in [1.0, 2.0)
+/// and subtracting 1.0. Avoids the integer to float conversion of [`u64_to_f64`],
+/// at the cost of one bit of resolution.
+#[inline]
+pub fn u64_to_f64_bitcast(x: u64) -> f64 {
+    const ONE: u64 = 1.0_f64.to_bits();
+    f64::from_bits(ONE | (x >> (u64::BITS - (f64::MANTISSA_DIGITS - 1)))) - 1.0
+}
+
+/// Identical to [`u64_to_f64_bitcast`], but for `f32` using the upper 23 bits of `x`.
+#[inline]
+pub fn u64_to_f32_bitcast(x: u64) -> f32 {
+    const ONE: u32 = 1.0_f32.to_bits();
+    let mantissa = (x >> (u64::BITS - (f32::MANTISSA_DIGITS - 1))) as u32;
+    f32::from_bits(ONE | mantissa) - 1.0
+}
+
 /// Performs 128-bit multiplication on `x` and `y`, returning the
 /// result as a tuple of `u64` values in the format (high, low).
 #[inline]
@@ -49,3 +135,76 @@ pub fn wide_mul(x: u64, y: u64) -> (u64, u64) {
     let low = product as u64;
     (high, low)
 }
+
+/// Compares `x` and `y` for equality in time which is only dependent
+/// on their lengths, and never on their contents.
+#[cfg(all(feature = "alloc", feature = "secure"))]
+#[inline(never)]
+pub fn ct_eq(x: &[u8], y: &[u8]) -> bool {
+    if x.len() != y.len() {
+        return false;
+    }
+    let diff = x
+        .iter()
+        .zip(y)
+        .fold(0, |acc, (a, b)| acc | core::hint::black_box(a ^ b));
+    diff == 0
+}
+
+/// Overwrites the entire allocation of `dst` with zeros, including any spare
+/// capacity, in a way that won't be optimized out by the compiler.
+#[cfg(all(feature = "alloc", feature = "secure"))]
+#[inline]
+pub fn wipe(dst: &mut alloc::vec::Vec<u8>) {
+    let len = dst.len();
+    // Initializing the spare capacity (which never reallocates) lets the entire
+    // allocation be wiped as a single slice.
+    dst.resize(dst.capacity(), 0);
+    wipe_slice(dst);
+    dst.truncate(len);
+}
+
+/// Overwrites `dst` with zeros in a way that won't be optimized out by the compiler,
+/// for scratch buffers which held random data that went into a secret.
+///
+/// Every other wipe in the crate is built on top of this function.
+#[cfg(feature = "secure")]
+#[inline(never)]
+pub fn wipe_slice(dst: &mut [u8]) {
+    for byte in dst {
+        // SAFETY: `byte` is a valid reference, and zero is a valid `u8`.
+        unsafe { core::ptr::write_volatile(byte, 0) };
+    }
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+}
+
+/// Fills `okm` using HKDF-SHA256 from RFC 5869, with an empty salt.
+///
+/// # Panics
+///
+/// Panics if `okm` is longer than 8160 bytes, the maximum output length of HKDF-SHA256.
+#[cfg(feature = "hkdf")]
+pub fn hkdf_sha256(ikm: &[u8], info: &[u8], okm: &mut [u8]) {
+    use hmac::{Hmac, Mac};
+    type HmacSha256 = Hmac<sha2::Sha256>;
+    assert!(
+        okm.len() <= 255 * 32,
+        "HKDF-SHA256 output is limited to 8160 bytes"
+    );
+    // The unwraps get optimized out since HMAC accepts keys of any length.
+    let mut mac = HmacSha256::new_from_slice(&[]).unwrap();
+    mac.update(ikm);
+    let prk = mac.finalize().into_bytes();
+    let mut prev = [0; 32];
+    for (i, chunk) in okm.chunks_mut(32).enumerate() {
+        let mut mac = HmacSha256::new_from_slice(&prk).unwrap();
+        // The first block isn't preceded by a previous one.
+        if i != 0 {
+            mac.update(&prev);
+        }
+        mac.update(info);
+        mac.update(&[i as u8 + 1]);
+        prev.copy_from_slice(&mac.finalize().into_bytes());
+        chunk.copy_from_slice(&prev[..chunk.len()]);
+    }
+}
@@ -0,0 +1,92 @@
+use crate::rng::Generator;
+
+/// Types which have a finite set of values to choose uniformly from, such as field-less enums.
+///
+/// Usually implemented using the [`crate::random_variant`] macro, and used through
+/// [`Generator::variant`].
+pub trait RandomVariant: Sized {
+    /// Returns a uniformly chosen value of the type, using `rng` as the source of randomness.
+    fn random_variant<G: Generator>(rng: &mut G) -> Self;
+}
+
+impl RandomVariant for bool {
+    #[inline]
+    fn random_variant<G: Generator>(rng: &mut G) -> Self {
+        rng.bool()
+    }
+}
+
+/// Declares an enum and implements [`RandomVariant`] for it.
+///
+/// Every variant is chosen with equal probability. Variants may be tuple variants,
+/// as long as all of their fields implement [`RandomVariant`], in which case the
+/// fields are chosen recursively after the variant itself has been chosen.
+/// Attributes and explicit discriminants are passed through to the declaration.
+///
+/// Struct variants and generic enums aren't supported.
+///
+/// # Examples
+///
+/// ```
+/// use ya_rand::*;
+///
+/// random_variant! {
+///     #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///     pub enum Suit {
+///         Clubs,
+///         Diamonds,
+///         Hearts,
+///         Spades,
+///     }
+/// }
+///
+/// random_variant! {
+///     #[derive(Debug, PartialEq)]
+///     enum Card {
+///         Joker,
+///         Face(Suit, bool),
+///     }
+/// }
+///
+/// let mut rng = new_rng();
+/// let suit: Suit = rng.variant();
+/// assert!(matches!(suit, Suit::Clubs | Suit::Diamonds | Suit::Hearts | Suit::Spades));
+/// let card: Card = rng.variant();
+/// assert!(card == Card::Joker || matches!(card, Card::Face(_, _)));
+/// ```
+#[macro_export]
+macro_rules! random_variant {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident $(($($field:ty),* $(,)?))? $(= $discriminant:expr)?
+            ),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant $(($($field),*))? $(= $discriminant)?
+            ),+
+        }
+
+        impl $crate::RandomVariant for $name {
+            fn random_variant<G: $crate::Generator>(rng: &mut G) -> Self {
+                const COUNT: u64 = [$(stringify!($variant)),+].len() as u64;
+                let mut index = rng.bound(COUNT);
+                $(
+                    if index == 0 {
+                        return Self::$variant $((
+                            $(<$field as $crate::RandomVariant>::random_variant(rng)),*
+                        ))?;
+                    }
+                    index -= 1;
+                )+
+                unreachable!("`bound` returned a value greater than or equal to its argument")
+            }
+        }
+    };
+}
@@ -1,5 +1,34 @@
+use crate::entropy::{EntropySource, OsEntropy};
+use crate::error::Error;
+use crate::jump;
 use crate::rng::{Generator, SeedableGenerator};
 use crate::util;
+use core::fmt;
+
+/// Characteristic polynomial of the state transition, without its leading
+/// x<sup>256</sup> term.
+const CHARACTERISTIC: [u64; 4] = [
+    0x9D116F2BB0F0F001,
+    0x0280002BCEFD1A5E,
+    0x04B4EDCF26259F85,
+    0x0003C03C3F3ECB19,
+];
+
+/// Jump polynomial for 2<sup>128</sup> steps, from the reference implementation.
+const JUMP: [u64; 4] = [
+    0x180EC6D33CFD0ABA,
+    0xD5A61266F0C9392C,
+    0xA9582618E03FC9AA,
+    0x39ABDC4529B1661C,
+];
+
+/// Jump polynomial for 2<sup>192</sup> steps, from the reference implementation.
+const LONG_JUMP: [u64; 4] = [
+    0x76E15D3EFEFDCBBF,
+    0xC5004E441C522FB3,
+    0x77710069854EE241,
+    0x39109BB02ACBE635,
+];
 
 /// Rust implementation of the xoshiro256++ PRNG.
 ///
@@ -7,35 +36,102 @@ use crate::util;
 /// but not cryptographically secure.
 ///
 /// More information can be found at: <https://prng.di.unimi.it/>.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct Xoshiro256pp {
     state: [u64; 4],
 }
 
+impl fmt::Debug for Xoshiro256pp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("all `Xoshiro256pp` fields are private")
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Xoshiro256pp {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "all `Xoshiro256pp` fields are private");
+    }
+}
+
 impl Default for Xoshiro256pp {
     fn default() -> Self {
         Self::new_with_seed(0)
     }
 }
 
-impl SeedableGenerator for Xoshiro256pp {
-    fn new_with_seed(seed: u64) -> Self {
+impl Xoshiro256pp {
+    /// Identical to [`SeedableGenerator::new_with_seed`], but usable
+    /// in `const` and `static` contexts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// const RNG: Xoshiro256pp = Xoshiro256pp::new_with_seed(69);
+    /// let mut rng = RNG;
+    /// assert!(rng.u64() == Xoshiro256pp::new_with_seed(69).u64());
+    /// ```
+    #[inline]
+    pub const fn new_with_seed(seed: u64) -> Self {
         let state = util::state_from_seed(seed);
         let mut ret = Self { state };
-        let _discard_first = ret.u64();
+        let _discard_first = ret.step();
         ret
     }
-}
 
-impl Generator for Xoshiro256pp {
+    /// Returns the internal state of the generator.
+    ///
+    /// Anyone who sees the state can reproduce all future output of the generator, which
+    /// is why it's left out of the `Debug` output. Only use this where that's acceptable,
+    /// such as when debugging a simulation.
     #[inline]
-    fn try_new() -> Result<Self, getrandom::Error> {
-        let state = util::state_from_entropy()?;
-        Ok(Self { state })
+    pub const fn expose_state(&self) -> [u64; 4] {
+        self.state
     }
 
-    #[cfg_attr(feature = "inline", inline)]
-    fn u64(&mut self) -> u64 {
+    /// Advances the generator by 2<sup>`k`</sup> values, as if [`Generator::u64`]
+    /// had been called that many times.
+    ///
+    /// Since the period of the generator is 2<sup>256</sup> - 1, this can be used to
+    /// split it into non-overlapping streams for parallel computations: starting from
+    /// one generator, every stream is a clone which has been jumped one more time than
+    /// the previous stream. Any power of 2 can be chosen as the spacing, so the amount
+    /// of streams doesn't have to match a fixed jump distance.
+    /// `jump_by_pow2(128)` and `jump_by_pow2(192)` are equivalent to the `jump` and
+    /// `long_jump` functions of the reference implementation.
+    ///
+    /// Those two distances use the precomputed polynomials of the reference implementation.
+    /// Any other distance needs `k` polynomial squarings, which is far more expensive than
+    /// generating a value, so jumps should be done up front when creating the streams.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = Xoshiro256pp::new_with_seed(0);
+    /// let mut jumped = Xoshiro256pp::new_with_seed(0);
+    /// jumped.jump_by_pow2(3);
+    /// (0..8).for_each(|_| _ = rng.u64());
+    /// assert!(rng == jumped);
+    /// ```
+    pub fn jump_by_pow2(&mut self, k: u32) {
+        let poly = match k % 256 {
+            128 => JUMP,
+            192 => LONG_JUMP,
+            k => jump::pow2_poly(k, &CHARACTERISTIC),
+        };
+        jump::jump(&mut self.state, &poly, |state| {
+            let mut rng = Self { state: *state };
+            rng.step();
+            *state = rng.state;
+        });
+    }
+
+    #[inline(always)]
+    const fn step(&mut self) -> u64 {
         let result = self.state[0]
             .wrapping_add(self.state[3])
             .rotate_left(23)
@@ -53,3 +149,28 @@ impl Generator for Xoshiro256pp {
         result
     }
 }
+
+impl SeedableGenerator for Xoshiro256pp {
+    #[inline]
+    fn new_with_seed(seed: u64) -> Self {
+        Self::new_with_seed(seed)
+    }
+}
+
+impl Generator for Xoshiro256pp {
+    #[inline]
+    fn try_new() -> Result<Self, Error> {
+        Self::try_new_from(&mut OsEntropy)
+    }
+
+    #[inline]
+    fn try_new_from<S: EntropySource + ?Sized>(source: &mut S) -> Result<Self, Error> {
+        let state = util::state_from_entropy(source)?;
+        Ok(Self { state })
+    }
+
+    #[cfg_attr(feature = "inline", inline)]
+    fn u64(&mut self) -> u64 {
+        self.step()
+    }
+}
@@ -1,5 +1,46 @@
+use crate::entropy::{EntropySource, OsEntropy};
+use crate::error::Error;
+use crate::jump;
 use crate::rng::{Generator, SeedableGenerator};
 use crate::util;
+use core::fmt;
+
+/// Characteristic polynomial of the state transition, without its leading
+/// x<sup>512</sup> term.
+const CHARACTERISTIC: [u64; 8] = [
+    0xCF3CFF0C00000001,
+    0x7FDC78D886F00C63,
+    0xF05E63FCA6D7B781,
+    0x7A67058E7BBAB6F0,
+    0xF11EEF832E32518F,
+    0x51BA7C47EDC758AD,
+    0x8F2D27268CE4B20B,
+    0x0000500055D8B77F,
+];
+
+/// Jump polynomial for 2<sup>256</sup> steps, from the reference implementation.
+const JUMP: [u64; 8] = [
+    0x33ED89B6E7A353F9,
+    0x760083D7955323BE,
+    0x2837F2FBB5F22FAE,
+    0x4B8C5674D309511C,
+    0xB11AC47A7BA28C25,
+    0xF1BE7667092BCC1C,
+    0x53851EFDB6DF0AAF,
+    0x1EBBC8B23EAF25DB,
+];
+
+/// Jump polynomial for 2<sup>384</sup> steps, from the reference implementation.
+const LONG_JUMP: [u64; 8] = [
+    0x11467FEF8F921D28,
+    0xA2A819F2E79C8EA8,
+    0xA8299FC284B3959A,
+    0xB4D347340CA63EE1,
+    0x1CB0940BEDBFF6CE,
+    0xD956C5C4FA1F8E17,
+    0x915E38FD4EDA93BC,
+    0x5B3CCDFA5D7DACA5,
+];
 
 /// Rust implementation of the xoshiro512++ PRNG.
 ///
@@ -7,35 +48,92 @@ use crate::util;
 /// but not cryptographically secure.
 ///
 /// More information can be found at: <https://prng.di.unimi.it/>.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct Xoshiro512pp {
     state: [u64; 8],
 }
 
+impl fmt::Debug for Xoshiro512pp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("all `Xoshiro512pp` fields are private")
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Xoshiro512pp {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "all `Xoshiro512pp` fields are private");
+    }
+}
+
 impl Default for Xoshiro512pp {
     fn default() -> Self {
         Self::new_with_seed(0)
     }
 }
 
-impl SeedableGenerator for Xoshiro512pp {
-    fn new_with_seed(seed: u64) -> Self {
+impl Xoshiro512pp {
+    /// Identical to [`SeedableGenerator::new_with_seed`], but usable
+    /// in `const` and `static` contexts.
+    #[inline]
+    pub const fn new_with_seed(seed: u64) -> Self {
         let state = util::state_from_seed(seed);
         let mut ret = Self { state };
-        let _discard_first = ret.u64();
+        let _discard_first = ret.step();
         ret
     }
-}
 
-impl Generator for Xoshiro512pp {
+    /// Returns the internal state of the generator.
+    ///
+    /// Anyone who sees the state can reproduce all future output of the generator, which
+    /// is why it's left out of the `Debug` output. Only use this where that's acceptable,
+    /// such as when debugging a simulation.
     #[inline]
-    fn try_new() -> Result<Self, getrandom::Error> {
-        let state = util::state_from_entropy()?;
-        Ok(Self { state })
+    pub const fn expose_state(&self) -> [u64; 8] {
+        self.state
     }
 
-    #[cfg_attr(feature = "inline", inline)]
-    fn u64(&mut self) -> u64 {
+    /// Advances the generator by 2<sup>`k`</sup> values, as if [`Generator::u64`]
+    /// had been called that many times.
+    ///
+    /// Since the period of the generator is 2<sup>512</sup> - 1, this can be used to
+    /// split it into non-overlapping streams for parallel computations: starting from
+    /// one generator, every stream is a clone which has been jumped one more time than
+    /// the previous stream. Any power of 2 can be chosen as the spacing, so the amount
+    /// of streams doesn't have to match a fixed jump distance.
+    /// `jump_by_pow2(256)` and `jump_by_pow2(384)` are equivalent to the `jump` and
+    /// `long_jump` functions of the reference implementation.
+    ///
+    /// Those two distances use the precomputed polynomials of the reference implementation.
+    /// Any other distance needs `k` polynomial squarings, which is far more expensive than
+    /// generating a value, so jumps should be done up front when creating the streams.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ya_rand::*;
+    ///
+    /// let mut rng = Xoshiro512pp::new_with_seed(0);
+    /// let mut jumped = Xoshiro512pp::new_with_seed(0);
+    /// jumped.jump_by_pow2(3);
+    /// (0..8).for_each(|_| _ = rng.u64());
+    /// assert!(rng == jumped);
+    /// ```
+    pub fn jump_by_pow2(&mut self, k: u32) {
+        let poly = match k % 512 {
+            256 => JUMP,
+            384 => LONG_JUMP,
+            k => jump::pow2_poly(k, &CHARACTERISTIC),
+        };
+        jump::jump(&mut self.state, &poly, |state| {
+            let mut rng = Self { state: *state };
+            rng.step();
+            *state = rng.state;
+        });
+    }
+
+    #[inline(always)]
+    const fn step(&mut self) -> u64 {
         let result = self.state[0]
             .wrapping_add(self.state[2])
             .rotate_left(17)
@@ -57,3 +155,28 @@ impl Generator for Xoshiro512pp {
         result
     }
 }
+
+impl SeedableGenerator for Xoshiro512pp {
+    #[inline]
+    fn new_with_seed(seed: u64) -> Self {
+        Self::new_with_seed(seed)
+    }
+}
+
+impl Generator for Xoshiro512pp {
+    #[inline]
+    fn try_new() -> Result<Self, Error> {
+        Self::try_new_from(&mut OsEntropy)
+    }
+
+    #[inline]
+    fn try_new_from<S: EntropySource + ?Sized>(source: &mut S) -> Result<Self, Error> {
+        let state = util::state_from_entropy(source)?;
+        Ok(Self { state })
+    }
+
+    #[cfg_attr(feature = "inline", inline)]
+    fn u64(&mut self) -> u64 {
+        self.step()
+    }
+}